@@ -24,7 +24,7 @@ terminate_tmux_session() {
     cd \$WORKSPACE
 
     tmux kill-session -t snarkos-session
-    snarkos clean --dev $NODE_ID
+    snarkos clean --dev $NODE_ID --force
 
     exit  # Exit root user
 EOF
@@ -16,7 +16,7 @@
 
 #[cfg(test)]
 mod tests {
-    use snarkos_node_cdn::sync_ledger_with_cdn;
+    use snarkos_node_cdn::{sync_ledger_with_cdn, CdnVerification};
     use snarkvm::prelude::{
         block::Block,
         store::helpers::memory::ConsensusMemory,
@@ -42,7 +42,8 @@ mod tests {
         // Perform the sync.
         let rt = tokio::runtime::Runtime::new().unwrap();
         rt.block_on(async {
-            let completed_height = sync_ledger_with_cdn(TEST_BASE_URL, ledger.clone()).await.unwrap();
+            let completed_height =
+                sync_ledger_with_cdn(TEST_BASE_URL, ledger.clone(), CdnVerification::None).await.unwrap();
             assert_eq!(completed_height, ledger.latest_height());
         });
     }
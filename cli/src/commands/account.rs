@@ -13,18 +13,19 @@
 // limitations under the License.
 
 use snarkvm::console::{
-    account::PrivateKey,
+    account::{Address, PrivateKey, ViewKey},
     prelude::{Environment, Uniform},
     types::Field,
 };
 
-use anyhow::{anyhow, bail, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use clap::Parser;
 use colored::Colorize;
 use core::str::FromStr;
-use rand::SeedableRng;
+use rand::{CryptoRng, Rng, SeedableRng};
 use rand_chacha::ChaChaRng;
 use rayon::prelude::*;
+use serde_json::{json, Value};
 
 type Network = snarkvm::prelude::Testnet3;
 
@@ -33,44 +34,104 @@ type Network = snarkvm::prelude::Testnet3;
 pub enum Account {
     /// Generates a new Aleo account
     New {
-        /// Seed the RNG with a numeric value
+        /// Seed the RNG with a numeric value, or with exactly 32 bytes of hex prefixed by '0x'
         #[clap(short = 's', long)]
         seed: Option<String>,
         /// Try until an address with the vanity string is found
         #[clap(short = 'v', long)]
         vanity: Option<String>,
+        /// Try until an address starting with the prefix string (after 'aleo1') is found
+        #[clap(short = 'p', long)]
+        prefix: Option<String>,
+        /// Print the account as JSON, without any colors
+        #[clap(long)]
+        json: bool,
+        /// Specify the number of accounts to generate
+        #[clap(short = 'n', long, default_value = "1")]
+        count: u32,
+        /// Specify the verbosity of the account generator, where 2 prints the search rate of a prefix string
+        #[clap(long, default_value = "1")]
+        verbosity: u8,
+    },
+    /// Validates an Aleo private key, view key, or address
+    Validate {
+        /// The private key, view key, or address to validate
+        input: String,
     },
 }
 
 impl Account {
     pub fn parse(self) -> Result<String> {
         match self {
-            Self::New { seed, vanity } => {
-                // Ensure only the seed or the vanity string is specified.
+            Self::New { seed, vanity, prefix, json, count, verbosity } => {
+                // Ensure only one of the seed, the vanity string, or the prefix string is specified.
                 if seed.is_some() && vanity.is_some() {
                     bail!("Cannot specify both the '--seed' and '--vanity' flags");
                 }
-
-                // Generate a vanity account.
-                if let Some(vanity) = vanity {
-                    Self::new_vanity(&vanity)
+                if prefix.is_some() && (seed.is_some() || vanity.is_some()) {
+                    bail!("Cannot specify the '--prefix' flag with the '--seed' or '--vanity' flags");
+                }
+                // Ensure at least one account is generated, and a seed only yields a single account.
+                ensure!(count > 0, "The '--count' must be greater than 0");
+                if seed.is_some() && count > 1 {
+                    bail!("Cannot specify both the '--seed' and '--count' flags (see 'snarkos dev-accounts')");
                 }
-                // Default to generating a normal account, with an optional seed.
-                else {
-                    Self::new_seeded(seed)
+
+                // Initialize a single RNG for all of the accounts.
+                let rng = &mut rand::thread_rng();
+                let accounts = (0..count)
+                    .map(|_| match (&vanity, &prefix) {
+                        // Generate a vanity account.
+                        (Some(vanity), _) => Self::new_vanity(vanity),
+                        // Generate an account with the prefix string.
+                        (None, Some(prefix)) => Self::new_prefixed(prefix, verbosity),
+                        // Default to generating a normal account, with an optional seed.
+                        (None, None) => Self::new_seeded(seed.as_deref(), rng),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                match json {
+                    true => {
+                        // Print the warning to stderr, so that stdout only contains the JSON.
+                        eprintln!("⚠️  Keep the private keys secret - anyone who has one controls its account.\n");
+                        match count {
+                            1 => Ok(Self::to_json(&accounts[0]).to_string()),
+                            _ => Ok(Value::Array(accounts.iter().map(Self::to_json).collect()).to_string()),
+                        }
+                    }
+                    false => Ok(accounts.iter().map(|account| account.to_string()).collect::<Vec<_>>().join("\n\n")),
                 }
             }
+            Self::Validate { input } => Self::validate(&input),
         }
     }
 
-    /// Generates a new Aleo account with the given vanity string.
-    fn new_vanity(vanity: &str) -> Result<String> {
-        // A closure to generate a new Aleo account.
-        let sample_account = || snarkos_account::Account::<Network>::new(&mut rand::thread_rng());
+    /// Validates the given string as a private key, view key, or address,
+    /// returning the type and any derived keys if it is valid.
+    fn validate(input: &str) -> Result<String> {
+        let input = input.trim();
 
-        const ITERATIONS: u128 = u16::MAX as u128;
-        const ITERATIONS_STR: &str = "65,535";
+        // Attempt to parse the input as a private key.
+        if let Ok(private_key) = PrivateKey::<Network>::from_str(input) {
+            let account = snarkos_account::Account::<Network>::try_from(private_key)?;
+            return Ok(format!(" ✅ Valid private key\n\n{account}"));
+        }
+        // Attempt to parse the input as a view key.
+        if let Ok(view_key) = ViewKey::<Network>::from_str(input) {
+            let mut output = " ✅ Valid view key\n\n".to_string();
+            output += &format!(" {:>12}  {view_key}\n", "View Key".cyan().bold());
+            output += &format!(" {:>12}  {}", "Address".cyan().bold(), view_key.to_address());
+            return Ok(output);
+        }
+        // Attempt to parse the input as an address.
+        if let Ok(address) = Address::<Network>::from_str(input) {
+            return Ok(format!(" ✅ Valid address\n\n {:>12}  {address}", "Address".cyan().bold()));
+        }
+        bail!("Invalid input '{input}' (expected a private key, view key, or address)")
+    }
 
+    /// Generates a new Aleo account with the given vanity string.
+    fn new_vanity(vanity: &str) -> Result<snarkos_account::Account<Network>> {
         // Ensure the vanity string is valid.
         if !crate::helpers::is_in_bech32m_charset(vanity) {
             bail!(
@@ -83,62 +144,126 @@ impl Account {
         if vanity.len() > 4 {
             let message =
                 format!(" The vanity string '{vanity}' contains 5 or more characters and will take a while to find.\n");
-            println!("{}", message.yellow());
+            eprintln!("{}", message.yellow());
         }
 
+        // Search for an address that includes the desired vanity string at the start or end of the address.
+        Self::search_account(|address| crate::helpers::has_vanity_string(address, vanity), true)
+    }
+
+    /// Generates a new Aleo account whose address starts with the given prefix string, after 'aleo1'.
+    fn new_prefixed(prefix: &str, verbosity: u8) -> Result<snarkos_account::Account<Network>> {
+        // Ensure the prefix string is valid, as an address never contains a character outside of the charset.
+        // Note: The '1' is the separator of the bech32m string, so it never appears in the data part.
+        ensure!(!prefix.is_empty(), "The prefix string must not be empty");
+        if !crate::helpers::is_in_bech32m_charset(prefix) || prefix.contains('1') {
+            bail!(
+                "The prefix string '{prefix}' contains invalid bech32m characters. Try using characters from the bech32m character set: {}",
+                crate::helpers::BECH32M_CHARSET.trim_end_matches('1')
+            );
+        }
+
+        // Output a message if the prefix string is more than 4 characters.
+        if prefix.len() > 4 {
+            let message =
+                format!(" The prefix string '{prefix}' contains 5 or more characters and will take a while to find.\n");
+            eprintln!("{}", message.yellow());
+        }
+
+        // Search for the address on all of the available cores.
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(num_cpus::get()).build()?;
+        pool.install(|| {
+            Self::search_account(|address| crate::helpers::has_prefix_string(address, prefix), verbosity >= 2)
+        })
+    }
+
+    /// Samples accounts in parallel until one is found whose address matches the given predicate.
+    /// If `show_progress` is set, the sampling rate is printed to stderr after each batch of samples,
+    /// so that stdout only contains the generated account (e.g. with '--json').
+    fn search_account(
+        matches: impl Fn(&str) -> bool + Sync,
+        show_progress: bool,
+    ) -> Result<snarkos_account::Account<Network>> {
+        // A closure to generate a new Aleo account.
+        let sample_account = || snarkos_account::Account::<Network>::new(&mut rand::thread_rng());
+
+        const ITERATIONS: u128 = u16::MAX as u128;
+        const ITERATIONS_STR: &str = "65,535";
+
         loop {
             // Initialize a timer.
             let timer = std::time::Instant::now();
 
-            // Generates bech32m addresses in parallel until one is found that
-            // includes the desired vanity string at the start or end of the address.
+            // Generates bech32m addresses in parallel until one is found that matches.
             let account = (0..ITERATIONS).into_par_iter().find_map_any(|_| {
-                // Initialize the result.
-                let mut account = None;
                 // Sample a random account.
-                if let Ok(candidate) = sample_account() {
-                    // Encode the address as a bech32m string.
-                    let address = candidate.address().to_string();
-                    // Set the candidate if the address includes the desired vanity string
-                    // at the start or end of the address.
-                    if crate::helpers::has_vanity_string(&address, vanity) {
-                        account = Some(candidate);
-                    }
-                }
-                // Return the result.
-                account
+                let candidate = sample_account().ok()?;
+                // Return the candidate if its bech32m address matches.
+                matches(&candidate.address().to_string()).then_some(candidate)
             });
 
             // Return the result if a candidate was found.
             if let Some(account) = account {
-                println!(); // Add a newline for formatting.
-                return Ok(account.to_string());
-            } else {
-                let rate = ITERATIONS / timer.elapsed().as_millis();
-                let rate = format!("[{rate} a/ms]");
-                println!(" {} Sampled {ITERATIONS_STR} accounts, searching...", rate.dimmed());
+                if show_progress {
+                    eprintln!(); // Add a newline for formatting.
+                }
+                return Ok(account);
+            } else if show_progress {
+                let rate = (ITERATIONS as f64 / timer.elapsed().as_secs_f64()) as u64;
+                let rate = format!("[{rate} a/s]");
+                eprintln!(" {} Sampled {ITERATIONS_STR} accounts, searching...", rate.dimmed());
             }
         }
     }
 
-    /// Generates a new Aleo account with an optional seed.
-    fn new_seeded(seed: Option<String>) -> Result<String> {
+    /// Generates a new Aleo account with an optional seed, or from the given RNG otherwise.
+    fn new_seeded<R: Rng + CryptoRng>(seed: Option<&str>, rng: &mut R) -> Result<snarkos_account::Account<Network>> {
+        // If the seed is hex, sample the private key from a ChaCha RNG with the seed.
+        if let Some(seed) = seed.filter(|seed| seed.starts_with("0x")) {
+            let private_key = PrivateKey::new(&mut ChaChaRng::from_seed(Self::parse_hex_seed(seed)?))?;
+            return snarkos_account::Account::<Network>::try_from(private_key);
+        }
+
         // Recover the seed.
         let seed = match seed {
             // Recover the field element deterministically.
-            Some(seed) => {
-                Field::new(<Network as Environment>::Field::from_str(&seed).map_err(|e| anyhow!("Invalid seed - {e}"))?)
-            }
+            Some(seed) => Self::parse_seed(seed)?,
             // Sample a random field element.
-            None => Field::rand(&mut ChaChaRng::from_entropy()),
+            None => Field::rand(rng),
         };
         // Recover the private key from the seed as a field element.
         let private_key =
             PrivateKey::try_from(seed).map_err(|_| anyhow!("Failed to convert the seed into a valid private key"))?;
         // Construct the account.
-        let account = snarkos_account::Account::<Network>::try_from(private_key)?;
-        // Print the new Aleo account.
-        Ok(account.to_string())
+        snarkos_account::Account::<Network>::try_from(private_key)
+    }
+
+    /// Returns the given account as a JSON object of its private key, view key, and address.
+    fn to_json(account: &snarkos_account::Account<Network>) -> Value {
+        json!({
+            "private_key": account.private_key().to_string(),
+            "view_key": account.view_key().to_string(),
+            "address": account.address().to_string(),
+        })
+    }
+
+    /// Parses the given '0x'-prefixed seed as exactly 32 bytes of hex.
+    fn parse_hex_seed(seed: &str) -> Result<[u8; 32]> {
+        let hex = seed.strip_prefix("0x").unwrap_or(seed);
+        ensure!(
+            hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()),
+            "Invalid seed '{seed}' - a hex seed must be exactly 32 bytes (64 hex characters after '0x')"
+        );
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16)?;
+        }
+        Ok(bytes)
+    }
+
+    /// Parses the given numeric seed as a field element.
+    pub(crate) fn parse_seed(seed: &str) -> Result<Field<Network>> {
+        Ok(Field::new(<Network as Environment>::Field::from_str(seed).map_err(|e| anyhow!("Invalid seed - {e}"))?))
     }
 }
 
@@ -151,11 +276,36 @@ mod tests {
     #[test]
     fn test_new() {
         for _ in 0..3 {
-            let account = Account::New { seed: None, vanity: None };
+            let account = Account::New { seed: None, vanity: None, prefix: None, json: false, count: 1, verbosity: 1 };
             assert!(account.parse().is_ok());
         }
     }
 
+    #[test]
+    fn test_validate() {
+        let private_key = "APrivateKey1zkp2n22c19hNdGF8wuEoQcuiyuWbquY6up4CtG5DYKqPX2X";
+        let view_key = "AViewKey1pNxZHn79XVJ4D2WG5Vn2YWsAzf5wzAs3dAuQtUAmUFF7";
+        let address = "aleo1uxl69laseuv3876ksh8k0nd7tvpgjt6ccrgccedpjk9qwyfensxst9ftg5";
+
+        // Ensure the private key is recognized, and the view key and address are derived.
+        let output = Account::Validate { input: private_key.to_string() }.parse().unwrap();
+        assert!(output.contains("Valid private key"));
+        assert!(output.contains(view_key));
+        assert!(output.contains(address));
+
+        // Ensure the view key is recognized, and the address is derived.
+        let output = Account::Validate { input: view_key.to_string() }.parse().unwrap();
+        assert!(output.contains("Valid view key"));
+        assert!(output.contains(address));
+
+        // Ensure the address is recognized.
+        let output = Account::Validate { input: address.to_string() }.parse().unwrap();
+        assert!(output.contains("Valid address"));
+
+        // Ensure invalid input is rejected.
+        assert!(Account::Validate { input: "aleo1invalid".to_string() }.parse().is_err());
+    }
+
     #[test]
     fn test_new_seeded() {
         let seed = Some(1231275789u64.to_string());
@@ -177,7 +327,7 @@ mod tests {
         );
 
         let vanity = None;
-        let account = Account::New { seed, vanity };
+        let account = Account::New { seed, vanity, prefix: None, json: false, count: 1, verbosity: 1 };
         let actual = account.parse().unwrap();
         assert_eq!(expected, actual);
     }
@@ -203,8 +353,81 @@ mod tests {
         );
 
         let vanity = None;
-        let account = Account::New { seed, vanity };
+        let account = Account::New { seed, vanity, prefix: None, json: false, count: 1, verbosity: 1 };
         let actual = account.parse().unwrap();
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn test_new_seeded_json() {
+        let seed = Some(1231275789u64.to_string());
+
+        let expected = r#"{"private_key":"APrivateKey1zkp2n22c19hNdGF8wuEoQcuiyuWbquY6up4CtG5DYKqPX2X","view_key":"AViewKey1pNxZHn79XVJ4D2WG5Vn2YWsAzf5wzAs3dAuQtUAmUFF7","address":"aleo1uxl69laseuv3876ksh8k0nd7tvpgjt6ccrgccedpjk9qwyfensxst9ftg5"}"#;
+
+        let account = Account::New { seed, vanity: None, prefix: None, json: true, count: 1, verbosity: 1 };
+        let actual = account.parse().unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_new_count() {
+        // Ensure the given number of distinct accounts is generated, as a JSON array.
+        let account = Account::New { seed: None, vanity: None, prefix: None, json: true, count: 3, verbosity: 1 };
+        let output = account.parse().unwrap();
+        let accounts: Vec<serde_json::Value> = serde_json::from_str(&output).unwrap();
+        assert_eq!(accounts.len(), 3);
+        assert_ne!(accounts[0]["address"], accounts[1]["address"]);
+        assert_ne!(accounts[1]["address"], accounts[2]["address"]);
+
+        // Ensure the count is validated.
+        let account = Account::New { seed: None, vanity: None, prefix: None, json: false, count: 0, verbosity: 1 };
+        assert!(account.parse().is_err());
+        let seed = Some(1231275789u64.to_string());
+        let account = Account::New { seed, vanity: None, prefix: None, json: false, count: 2, verbosity: 1 };
+        assert!(account.parse().is_err());
+    }
+
+    #[test]
+    fn test_new_prefixed() {
+        // Ensure the address of the account starts with the prefix string.
+        let prefix = Some("q".to_string());
+        let account = Account::New { seed: None, vanity: None, prefix, json: true, count: 1, verbosity: 0 };
+        let output = account.parse().unwrap();
+        let account: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert!(account["address"].as_str().unwrap().starts_with("aleo1q"));
+
+        // Ensure invalid prefix strings are rejected.
+        for prefix in ["", "b", "o1", "aleo1q"] {
+            let prefix = Some(prefix.to_string());
+            let account = Account::New { seed: None, vanity: None, prefix, json: false, count: 1, verbosity: 0 };
+            assert!(account.parse().is_err());
+        }
+    }
+
+    #[test]
+    fn test_new_seeded_hex() {
+        let new = |seed: &str| {
+            let account = Account::New {
+                seed: Some(seed.to_string()),
+                vanity: None,
+                prefix: None,
+                json: true,
+                count: 1,
+                verbosity: 1,
+            };
+            account.parse()
+        };
+        let seed = format!("0x{}", "ab".repeat(32));
+
+        // Ensure the same hex seed always yields the same account, and a different seed does not.
+        let account = new(&seed).unwrap();
+        assert_eq!(account, new(&seed).unwrap());
+        assert_ne!(account, new(&format!("0x{}", "cd".repeat(32))).unwrap());
+
+        // Ensure a hex seed that is not exactly 32 bytes is rejected.
+        assert!(new("0x").is_err());
+        assert!(new(&format!("0x{}", "ab".repeat(31))).is_err());
+        assert!(new(&format!("0x{}", "ab".repeat(33))).is_err());
+        assert!(new(&format!("0x{}zz", "ab".repeat(31))).is_err());
+    }
 }
@@ -0,0 +1,212 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{
+    block::Block,
+    Address,
+    Entry,
+    Field,
+    GraphKey,
+    Identifier,
+    Literal,
+    Network,
+    Plaintext,
+    ProgramID,
+    Record,
+    Testnet3,
+    Value,
+    ViewKey,
+};
+
+use anyhow::{bail, ensure, Result};
+use clap::Parser;
+use indexmap::{IndexMap, IndexSet};
+use std::{io::Write, str::FromStr};
+
+type CurrentNetwork = Testnet3;
+
+/// Computes the spendable balance of an account from the local ledger, reading the ledger without modifying it.
+/// Note: The private balance is computed from the credits records of the account, and the public balance is read
+/// from the 'account' mapping of 'credits.aleo'. The ledger cannot be read while a node is running on it.
+#[derive(Debug, Parser)]
+pub struct Balance {
+    /// Specify the view key of the account
+    #[clap(long = "view-key")]
+    pub view_key: String,
+    /// Specify the block height to compute the balance at (default: the latest block height)
+    #[clap(long)]
+    pub height: Option<u32>,
+    /// Enables development mode, specify the unique ID of the node whose ledger to read
+    #[clap(long)]
+    pub dev: Option<u16>,
+}
+
+impl Balance {
+    /// Scans the ledger and reports the spendable balance.
+    pub fn parse(self) -> Result<String> {
+        let view_key = match ViewKey::<CurrentNetwork>::from_str(&self.view_key) {
+            Ok(view_key) => view_key,
+            Err(error) => bail!("Invalid view key - {error}"),
+        };
+        // Derive the tag secret key, to determine which records are spent.
+        let sk_tag = GraphKey::try_from(view_key)?.sk_tag();
+
+        // Open the ledger storage.
+        let store = crate::helpers::open_ledger_store::<CurrentNetwork>(self.dev)?;
+        let block_store = store.block_store();
+        let Some(latest_height) = block_store.max_height() else {
+            bail!("The ledger is empty");
+        };
+
+        // Determine the height to compute the balance at.
+        let end_height = self.height.unwrap_or(latest_height);
+        ensure!(end_height <= latest_height, "The '--height' exceeds the latest height ({latest_height})");
+        let total_blocks = end_height + 1;
+
+        let mut scan = BalanceScan::default();
+        for height in 0..=end_height {
+            // Load the block.
+            // Note: Every block up to the height must be present, as a missing block may hold records of the account.
+            let Some(block_hash) = block_store.get_block_hash(height)? else {
+                bail!("The ledger is missing block {height} (computing a balance requires a full ledger)");
+            };
+            let Some(block) = block_store.get_block(&block_hash)? else {
+                bail!("The ledger is missing block {height} (computing a balance requires a full ledger)");
+            };
+            scan.scan_block(&block, &view_key, sk_tag)?;
+
+            // Log the progress.
+            let num_scanned = height + 1;
+            if num_scanned % 1000 == 0 || num_scanned == total_blocks {
+                let percentage_complete = num_scanned as f64 * 100.0 / total_blocks as f64;
+                print!("\rScanning {total_blocks} blocks for records ({percentage_complete:.2}% complete)...");
+                std::io::stdout().flush()?;
+            }
+        }
+        println!();
+
+        let (balance, num_unspent) = scan.spendable_balance();
+        // Read the public balance.
+        // Note: The mappings only hold the latest state, so the public balance is always read at the latest height.
+        let address = Address::try_from(view_key)?;
+        let public_balance = store
+            .finalize_store()
+            .get_value_confirmed(
+                ProgramID::from_str("credits.aleo")?,
+                Identifier::from_str("account")?,
+                &Plaintext::from(Literal::Address(address)),
+            )?
+            .map(|value| public_microcredits(&value))
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(format!(
+            "✅ The spendable balance at block {end_height} is {balance} microcredits ({num_unspent} records)\n\
+             ✅ The public balance at block {latest_height} is {public_balance} microcredits"
+        ))
+    }
+}
+
+/// The credits records of an account found in the ledger, and the tags of the spent records.
+struct BalanceScan<N: Network> {
+    /// The map of record tags to the microcredits in the record.
+    records: IndexMap<Field<N>, u64>,
+    /// The set of tags of the spent records.
+    spent_tags: IndexSet<Field<N>>,
+}
+
+impl<N: Network> Default for BalanceScan<N> {
+    /// Initializes an empty scan.
+    fn default() -> Self {
+        Self { records: Default::default(), spent_tags: Default::default() }
+    }
+}
+
+impl<N: Network> BalanceScan<N> {
+    /// Records the credits records of the account, and the spent tags, in the given block.
+    fn scan_block(&mut self, block: &Block<N>, view_key: &ViewKey<N>, sk_tag: Field<N>) -> Result<()> {
+        for transition in block.transitions() {
+            // Record the tags of the spent records.
+            self.spent_tags.extend(transition.tags().copied());
+            // Only the records of the credits program hold credits.
+            if transition.program_id().to_string() != "credits.aleo" {
+                continue;
+            }
+            for (commitment, record) in transition.records() {
+                if record.is_owner(view_key) {
+                    let record = record.decrypt(view_key)?;
+                    let tag = Record::<N, Plaintext<N>>::tag(sk_tag, *commitment)?;
+                    self.records.insert(tag, microcredits(&record)?);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the total microcredits in the unspent records, and the number of unspent records.
+    fn spendable_balance(&self) -> (u64, usize) {
+        self.records
+            .iter()
+            .filter(|(tag, _)| !self.spent_tags.contains(*tag))
+            .fold((0u64, 0usize), |(balance, num_unspent), (_, amount)| {
+                (balance.saturating_add(*amount), num_unspent + 1)
+            })
+    }
+}
+
+/// Returns the microcredits in the given credits record.
+fn microcredits<N: Network>(record: &Record<N, Plaintext<N>>) -> Result<u64> {
+    match record.data().get(&Identifier::from_str("microcredits")?) {
+        Some(Entry::Private(Plaintext::Literal(Literal::U64(amount), _))) => Ok(**amount),
+        _ => bail!("The credits record is missing its 'microcredits' entry"),
+    }
+}
+
+/// Returns the microcredits in the given value of the 'account' mapping.
+fn public_microcredits<N: Network>(value: &Value<N>) -> Result<u64> {
+    match value {
+        Value::Plaintext(Plaintext::Literal(Literal::U64(amount), _)) => Ok(**amount),
+        _ => bail!("The 'account' mapping holds an invalid balance ({value})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spendable_balance() {
+        let mut scan = BalanceScan::<CurrentNetwork>::default();
+        assert_eq!(scan.spendable_balance(), (0, 0));
+
+        scan.records.insert(Field::from_u64(1), 100);
+        scan.records.insert(Field::from_u64(2), 250);
+        scan.records.insert(Field::from_u64(3), 50);
+        assert_eq!(scan.spendable_balance(), (400, 3));
+
+        // Ensure the spent records are excluded, and unrelated spent tags are ignored.
+        scan.spent_tags.insert(Field::from_u64(2));
+        scan.spent_tags.insert(Field::from_u64(4));
+        assert_eq!(scan.spendable_balance(), (150, 2));
+    }
+
+    #[test]
+    fn test_public_microcredits() {
+        let value = Value::<CurrentNetwork>::from_str("100u64").unwrap();
+        assert_eq!(public_microcredits(&value).unwrap(), 100);
+        // Ensure a value of another type is rejected.
+        let value = Value::<CurrentNetwork>::from_str("100u32").unwrap();
+        assert!(public_microcredits(&value).is_err());
+    }
+}
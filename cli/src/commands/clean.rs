@@ -12,28 +12,85 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::commands::DEFAULT_NETWORK_ID;
+
 use anyhow::{bail, Result};
 use clap::Parser;
 use colored::Colorize;
+use crossterm::tty::IsTty;
+use std::{io::Write, path::Path};
 
 /// Cleans the snarkOS node storage.
 #[derive(Debug, Parser)]
 pub struct Clean {
     /// Specify the network to remove from storage.
-    #[clap(default_value = "3", long = "network")]
+    #[clap(default_value_t = DEFAULT_NETWORK_ID, long = "network")]
     pub network: u16,
     /// Enables development mode, specify the unique ID of the local node to clean.
     #[clap(long)]
     pub dev: Option<u16>,
+    /// If the flag is set, prints the ledger path and its size, without removing anything.
+    #[clap(long = "dry-run")]
+    pub dry_run: bool,
+    /// If the flag is set, removes the ledger without asking for confirmation.
+    #[clap(short = 'f', long = "force")]
+    pub force: bool,
 }
 
 impl Clean {
     /// Cleans the snarkOS node storage.
     pub fn parse(self) -> Result<String> {
+        // If this is a dry run, only report what would be removed.
+        if self.dry_run {
+            return Self::inspect_ledger(self.network, self.dev);
+        }
+        // Unless forced, ask for confirmation before removing the ledger.
+        if !self.force && !Self::confirm_removal(self.network, self.dev)? {
+            return Ok("The snarkOS node storage was not removed".to_string());
+        }
         // Remove the specified ledger from storage.
         Self::remove_ledger(self.network, self.dev)
     }
 
+    /// Prompts on stdin to confirm the removal of the specified ledger, returning `true` if it is confirmed,
+    /// or if there is no ledger to remove. If stdin is not a terminal, the removal is refused instead.
+    fn confirm_removal(network: u16, dev: Option<u16>) -> Result<bool> {
+        // Construct the path to the ledger in storage, exactly as `remove_ledger` does.
+        let path = aleo_std::aleo_ledger_dir(network, dev);
+        if !path.exists() {
+            return Ok(true);
+        }
+
+        // Ensure the prompt can be answered, rather than blocking a script.
+        if !std::io::stdin().is_tty() {
+            let path = path.display();
+            bail!("Refusing to remove the ledger at '{path}' without confirmation (use '--force' in scripts)");
+        }
+
+        print!("Remove ledger at {}? [y/N] ", path.display());
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        Ok(is_confirmed(&answer))
+    }
+
+    /// Reports the path and size of the specified ledger in storage, without removing it.
+    fn inspect_ledger(network: u16, dev: Option<u16>) -> Result<String> {
+        // Construct the path to the ledger in storage, exactly as `remove_ledger` does.
+        let path = aleo_std::aleo_ledger_dir(network, dev);
+
+        // Prepare the path string.
+        let path_string = format!("(in \"{}\")", path.display()).dimmed();
+
+        // Check if the path to the ledger exists in storage.
+        if path.exists() {
+            let size = directory_size(&path)?;
+            Ok(format!("🔍 Would remove {size} bytes of snarkOS node storage {path_string}"))
+        } else {
+            Ok(format!("✅ No snarkOS node storage was found {path_string}"))
+        }
+    }
+
     /// Removes the specified ledger from storage.
     pub(crate) fn remove_ledger(network: u16, dev: Option<u16>) -> Result<String> {
         // Construct the path to the ledger in storage.
@@ -56,3 +113,60 @@ impl Clean {
         }
     }
 }
+
+/// Returns the total size in bytes of the files in the given directory, recursively.
+fn directory_size(path: &Path) -> Result<u64> {
+    let mut size = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        match metadata.is_dir() {
+            true => size += directory_size(&entry.path())?,
+            false => size += metadata.len(),
+        }
+    }
+    Ok(size)
+}
+
+/// Returns `true` if the given answer to a `[y/N]` prompt confirms it.
+fn is_confirmed(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::Start;
+
+    #[test]
+    fn test_default_network() {
+        // Ensure 'clean' targets the ledger of the network that the node runs on by default.
+        let clean = Clean::try_parse_from(["snarkos"].iter()).unwrap();
+        let start = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert_eq!(clean.network, start.network);
+    }
+
+    #[test]
+    fn test_is_confirmed() {
+        assert!(is_confirmed("y\n"));
+        assert!(is_confirmed(" Yes \n"));
+        // Ensure the removal is declined by default.
+        assert!(!is_confirmed("\n"));
+        assert!(!is_confirmed("n\n"));
+        assert!(!is_confirmed("yep\n"));
+    }
+
+    #[test]
+    fn test_directory_size() {
+        // Create a directory with a nested directory.
+        let path = std::env::temp_dir().join(format!("snarkos-test-clean-{}", std::process::id()));
+        std::fs::create_dir_all(path.join("nested")).unwrap();
+        std::fs::write(path.join("a"), [0u8; 10]).unwrap();
+        std::fs::write(path.join("nested").join("b"), [0u8; 32]).unwrap();
+
+        // Ensure the sizes of the files are summed, recursively.
+        let size = directory_size(&path);
+        std::fs::remove_dir_all(&path).unwrap();
+        assert_eq!(size.unwrap(), 42);
+    }
+}
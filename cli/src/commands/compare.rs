@@ -0,0 +1,102 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use serde_json::Value;
+
+/// Compares the block hashes of two running nodes at a given height, to detect a divergence.
+#[derive(Debug, Parser)]
+pub struct Compare {
+    /// Specify the REST endpoint of the first node
+    #[clap(long = "rpc-a")]
+    pub rpc_a: String,
+    /// Specify the REST endpoint of the second node
+    #[clap(long = "rpc-b")]
+    pub rpc_b: String,
+    /// Specify the block height to compare
+    #[clap(long)]
+    pub height: u32,
+}
+
+impl Compare {
+    /// Compares the block hashes of both nodes at the height, returning an error if they do not match.
+    /// On a mismatch, the error reports the last height at which both nodes have the same block.
+    pub fn parse(self) -> Result<String> {
+        let hash_a = Self::block_hash(&self.rpc_a, self.height)?;
+        let hash_b = Self::block_hash(&self.rpc_b, self.height)?;
+        if hash_a == hash_b {
+            return Ok(format!("✅ Both nodes have block '{hash_a}' at height {}", self.height));
+        }
+
+        // Search for the last height at which both nodes have the same block.
+        let fork_point = find_fork_point(self.height, |height| {
+            Ok(Self::block_hash(&self.rpc_a, height)? == Self::block_hash(&self.rpc_b, height)?)
+        })?;
+        let fork_point = match fork_point {
+            Some(height) => format!("the last common block is at height {height}"),
+            None => "they do not share a genesis block".to_string(),
+        };
+        bail!("❌ The nodes disagree at height {} ('{hash_a}' vs '{hash_b}'), {fork_point}", self.height)
+    }
+
+    /// Fetches the hash of the block at the given height from the node.
+    fn block_hash(rpc: &str, height: u32) -> Result<String> {
+        let endpoint = format!("{}/testnet3/block/{height}", rpc.trim_end_matches('/'));
+        let block: Value = match ureq::get(&endpoint).call() {
+            Ok(response) => response.into_json()?,
+            Err(error) => bail!("Failed to fetch block {height} from '{rpc}' - {error}"),
+        };
+        match block["block_hash"].as_str() {
+            Some(hash) => Ok(hash.to_string()),
+            None => bail!("The node at '{rpc}' returned an invalid block {height}"),
+        }
+    }
+}
+
+/// Returns the greatest height below the given height at which both nodes agree, given that they disagree
+/// at the given height, or `None` if they disagree on the genesis block. As the blocks of a chain commit to
+/// their predecessors, the nodes agree on every block below the fork point, so the search is a bisection.
+fn find_fork_point(height: u32, mut agree: impl FnMut(u32) -> Result<bool>) -> Result<Option<u32>> {
+    if height == 0 || !agree(0)? {
+        return Ok(None);
+    }
+    // Note: The nodes agree at `low`, and disagree at `high`.
+    let (mut low, mut high) = (0, height);
+    while high - low > 1 {
+        let middle = low + (high - low) / 2;
+        match agree(middle)? {
+            true => low = middle,
+            false => high = middle,
+        }
+    }
+    Ok(Some(low))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_fork_point() {
+        // Ensure the fork point is found, for every fork height.
+        for fork_height in 1..50 {
+            let fork_point = find_fork_point(49, |height| Ok(height < fork_height)).unwrap();
+            assert_eq!(fork_point, Some(fork_height - 1));
+        }
+        // Ensure a disagreement on the genesis block is reported.
+        assert_eq!(find_fork_point(49, |_| Ok(false)).unwrap(), None);
+        assert_eq!(find_fork_point(0, |_| Ok(false)).unwrap(), None);
+    }
+}
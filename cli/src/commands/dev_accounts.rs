@@ -0,0 +1,103 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::commands::Account;
+use snarkvm::console::{account::PrivateKey, types::Field};
+
+use anyhow::{anyhow, ensure, Result};
+use clap::Parser;
+use serde::Serialize;
+
+type Network = snarkvm::prelude::Testnet3;
+
+/// Generates a deterministic set of development accounts from a master seed.
+#[derive(Debug, Parser)]
+pub struct DevAccounts {
+    /// Specify the numeric master seed (the same seed always derives the same accounts)
+    #[clap(short = 's', long)]
+    pub seed: String,
+    /// Specify the number of accounts to derive
+    #[clap(short = 'n', long, default_value = "4")]
+    pub count: u16,
+}
+
+/// A development account, as printed in the JSON output.
+#[derive(Debug, Serialize)]
+struct DevAccount {
+    index: u16,
+    private_key: String,
+    view_key: String,
+    address: String,
+}
+
+impl DevAccounts {
+    /// Derives the accounts, and returns them as a JSON array.
+    pub fn parse(self) -> Result<String> {
+        ensure!(self.count > 0, "The '--count' must be greater than 0");
+
+        // Recover the master seed.
+        let seed = Account::parse_seed(&self.seed)?;
+        // Derive each account from the master seed plus its index.
+        let accounts = (0..self.count)
+            .map(|index| {
+                let private_key = PrivateKey::<Network>::try_from(seed + Field::from_u16(index))
+                    .map_err(|_| anyhow!("Failed to derive a valid private key for account {index}"))?;
+                let account = snarkos_account::Account::<Network>::try_from(private_key)?;
+                Ok(DevAccount {
+                    index,
+                    private_key: account.private_key().to_string(),
+                    view_key: account.view_key().to_string(),
+                    address: account.address().to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(serde_json::to_string_pretty(&accounts)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn dev_accounts(seed: &str, count: u16) -> Vec<Value> {
+        let output = DevAccounts { seed: seed.to_string(), count }.parse().unwrap();
+        serde_json::from_str(&output).unwrap()
+    }
+
+    #[test]
+    fn test_dev_accounts() {
+        let accounts = dev_accounts("1231275789", 3);
+        assert_eq!(accounts.len(), 3);
+
+        // The first account matches the account generated with the same seed by 'account new'.
+        assert_eq!(accounts[0]["index"], 0);
+        assert_eq!(accounts[0]["private_key"], "APrivateKey1zkp2n22c19hNdGF8wuEoQcuiyuWbquY6up4CtG5DYKqPX2X");
+        assert_eq!(accounts[0]["address"], "aleo1uxl69laseuv3876ksh8k0nd7tvpgjt6ccrgccedpjk9qwyfensxst9ftg5");
+
+        // The accounts are deterministic, and distinct.
+        assert_eq!(accounts, dev_accounts("1231275789", 3));
+        assert_ne!(accounts[0]["address"], accounts[1]["address"]);
+        assert_ne!(accounts[1]["address"], accounts[2]["address"]);
+        // A prefix of the same seed yields the same accounts.
+        assert_eq!(accounts[..2], dev_accounts("1231275789", 2)[..]);
+    }
+
+    #[test]
+    fn test_dev_accounts_invalid() {
+        assert!(DevAccounts { seed: "1".to_string(), count: 0 }.parse().is_err());
+        assert!(DevAccounts { seed: "not-a-seed".to_string(), count: 1 }.parse().is_err());
+    }
+}
@@ -0,0 +1,309 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CurrentNetwork;
+
+use snarkvm::{
+    console::collections::merkle_tree::MerklePath,
+    prelude::{Field, FromBytes, Network, ToBytes},
+};
+
+use anyhow::{bail, ensure, Result};
+use clap::Parser;
+use rand::Rng;
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// The depth of the merkle tree used by the merkle utilities.
+const MERKLE_TREE_DEPTH: u8 = 32;
+
+/// Computes the merkle root of a set of leaves.
+#[derive(Debug, Parser)]
+pub struct MerkleRoot {
+    /// The path to a file of hex-encoded 32-byte leaves, one per line.
+    pub leaves: PathBuf,
+}
+
+impl MerkleRoot {
+    pub fn parse(self) -> Result<String> {
+        // Read the leaves.
+        let leaves = read_leaves(&self.leaves)?;
+        // Compute the merkle root.
+        Ok(compute_root(&leaves)?.to_string())
+    }
+}
+
+/// Generates a merkle membership proof for a leaf in a set of leaves.
+#[derive(Debug, Parser)]
+pub struct MerkleProof {
+    /// The path to a file of hex-encoded 32-byte leaves, one per line.
+    #[clap(long)]
+    pub leaves: PathBuf,
+    /// The index of the leaf to prove membership of.
+    #[clap(long)]
+    pub index: usize,
+}
+
+impl MerkleProof {
+    pub fn parse(self) -> Result<String> {
+        // Read the leaves.
+        let leaves = read_leaves(&self.leaves)?;
+        // Generate the merkle proof.
+        let (root, proof) = generate_proof(&leaves, self.index)?;
+        Ok(format!("root: {root}\nproof: {}", encode_hex(&proof.to_bytes_le()?)))
+    }
+}
+
+/// Verifies a merkle membership proof for a leaf.
+#[derive(Debug, Parser)]
+pub struct MerkleVerify {
+    /// The merkle root.
+    #[clap(long)]
+    pub root: String,
+    /// The hex-encoded 32-byte leaf.
+    #[clap(long)]
+    pub leaf: String,
+    /// The hex-encoded merkle proof, as output by `merkle-proof`.
+    #[clap(long)]
+    pub proof: String,
+}
+
+impl MerkleVerify {
+    pub fn parse(self) -> Result<String> {
+        // Parse the root, leaf, and proof.
+        let root = Field::<CurrentNetwork>::from_str(&self.root)?;
+        let leaf = decode_leaf(&self.leaf)?;
+        let proof = MerklePath::<CurrentNetwork, MERKLE_TREE_DEPTH>::from_bytes_le(&decode_hex(&self.proof)?)?;
+
+        // Verify the merkle proof.
+        match verify_proof(&root, &leaf, &proof) {
+            true => Ok("✅ The merkle proof is valid".to_string()),
+            false => bail!("The merkle proof is invalid"),
+        }
+    }
+}
+
+/// Benchmarks the construction of a merkle tree with random leaves, and the generation and verification of its proofs.
+#[derive(Debug, Parser)]
+pub struct BenchMerkle {
+    /// The number of random leaves.
+    #[clap(long)]
+    pub leaves: usize,
+    /// The height of the merkle tree [options: 8, 16, 20, 24, 32, 64].
+    #[clap(long, default_value = "32")]
+    pub height: u8,
+    /// The number of proofs to generate and verify.
+    #[clap(long, default_value = "100")]
+    pub proofs: usize,
+}
+
+impl BenchMerkle {
+    pub fn parse(self) -> Result<String> {
+        ensure!(self.leaves > 0, "The number of leaves must be greater than zero");
+        ensure!(self.proofs > 0, "The number of proofs must be greater than zero");
+        // The height is a compile-time parameter of the merkle tree, so only a fixed set is supported.
+        let report = match self.height {
+            8 => bench_merkle::<8>(self.leaves, self.proofs)?,
+            16 => bench_merkle::<16>(self.leaves, self.proofs)?,
+            20 => bench_merkle::<20>(self.leaves, self.proofs)?,
+            24 => bench_merkle::<24>(self.leaves, self.proofs)?,
+            32 => bench_merkle::<32>(self.leaves, self.proofs)?,
+            64 => bench_merkle::<64>(self.leaves, self.proofs)?,
+            height => bail!("Unsupported merkle tree height {height} (options: 8, 16, 20, 24, 32, 64)"),
+        };
+        Ok(report.to_string())
+    }
+}
+
+/// The timings of a merkle tree benchmark.
+pub(crate) struct MerkleBenchReport {
+    num_leaves: usize,
+    height: u8,
+    num_proofs: usize,
+    construction: Duration,
+    proof_generation: Duration,
+    proof_verification: Duration,
+}
+
+impl std::fmt::Display for MerkleBenchReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let num_proofs = self.num_proofs as u32;
+        writeln!(f, "Merkle tree with {} leaves (height {})", self.num_leaves, self.height)?;
+        writeln!(f, "  construction:       {:?}", self.construction)?;
+        writeln!(f, "  proof generation:   {:?} per proof", self.proof_generation / num_proofs)?;
+        write!(f, "  proof verification: {:?} per proof", self.proof_verification / num_proofs)
+    }
+}
+
+/// Benchmarks a BHP merkle tree of the given depth over the given number of random leaves.
+pub(crate) fn bench_merkle<const DEPTH: u8>(num_leaves: usize, num_proofs: usize) -> Result<MerkleBenchReport> {
+    // Ensure the leaves fit in the merkle tree.
+    let capacity = 1usize.checked_shl(DEPTH as u32).unwrap_or(usize::MAX);
+    ensure!(num_leaves <= capacity, "A merkle tree of height {DEPTH} holds at most {capacity} leaves");
+    // Sample the random leaves.
+    let rng = &mut rand::thread_rng();
+    let leaves = (0..num_leaves).map(|_| to_bits_le(&rng.gen::<[u8; 32]>())).collect::<Vec<_>>();
+
+    // Construct the merkle tree.
+    let timer = Instant::now();
+    let tree = CurrentNetwork::merkle_tree_bhp::<DEPTH>(&leaves)?;
+    let construction = timer.elapsed();
+
+    // Generate and verify the proofs for randomly-sampled leaves.
+    let (mut proof_generation, mut proof_verification) = (Duration::ZERO, Duration::ZERO);
+    for _ in 0..num_proofs {
+        let index = rng.gen_range(0..num_leaves);
+
+        let timer = Instant::now();
+        let proof = tree.prove(index, &leaves[index])?;
+        proof_generation += timer.elapsed();
+
+        let timer = Instant::now();
+        let is_valid = CurrentNetwork::verify_merkle_path_bhp(&proof, tree.root(), &leaves[index]);
+        proof_verification += timer.elapsed();
+        ensure!(is_valid, "The merkle proof for leaf {index} is invalid");
+    }
+
+    Ok(MerkleBenchReport { num_leaves, height: DEPTH, num_proofs, construction, proof_generation, proof_verification })
+}
+
+/// Computes the root of the BHP merkle tree over the given leaves, and a proof for the leaf at the given index.
+pub(crate) fn generate_proof(
+    leaves: &[[u8; 32]],
+    index: usize,
+) -> Result<(Field<CurrentNetwork>, MerklePath<CurrentNetwork, MERKLE_TREE_DEPTH>)> {
+    ensure!(index < leaves.len(), "The leaf index {index} is out of bounds ({} leaves)", leaves.len());
+    let leaves = leaves.iter().map(|leaf| to_bits_le(leaf)).collect::<Vec<_>>();
+    let tree = CurrentNetwork::merkle_tree_bhp::<MERKLE_TREE_DEPTH>(&leaves)?;
+    Ok((*tree.root(), tree.prove(index, &leaves[index])?))
+}
+
+/// Returns `true` if the given proof is a valid proof of membership of the leaf under the given root.
+pub(crate) fn verify_proof(
+    root: &Field<CurrentNetwork>,
+    leaf: &[u8; 32],
+    proof: &MerklePath<CurrentNetwork, MERKLE_TREE_DEPTH>,
+) -> bool {
+    CurrentNetwork::verify_merkle_path_bhp(proof, root, &to_bits_le(leaf))
+}
+
+/// Computes the root of the BHP merkle tree over the given leaves.
+pub(crate) fn compute_root(leaves: &[[u8; 32]]) -> Result<Field<CurrentNetwork>> {
+    let leaves = leaves.iter().map(|leaf| to_bits_le(leaf)).collect::<Vec<_>>();
+    Ok(*CurrentNetwork::merkle_tree_bhp::<MERKLE_TREE_DEPTH>(&leaves)?.root())
+}
+
+/// Reads the hex-encoded 32-byte leaves from the given file, ignoring blank lines.
+pub(crate) fn read_leaves(path: &Path) -> Result<Vec<[u8; 32]>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) => bail!("Failed to read the leaves file '{}' - {error}", path.display()),
+    };
+    let leaves =
+        contents.lines().map(str::trim).filter(|line| !line.is_empty()).map(decode_leaf).collect::<Result<Vec<_>>>()?;
+    ensure!(!leaves.is_empty(), "The leaves file '{}' is empty", path.display());
+    Ok(leaves)
+}
+
+/// Decodes a hex-encoded 32-byte leaf, with an optional `0x` prefix.
+pub(crate) fn decode_leaf(leaf: &str) -> Result<[u8; 32]> {
+    match decode_hex(leaf).ok().and_then(|bytes| <[u8; 32]>::try_from(bytes).ok()) {
+        Some(bytes) => Ok(bytes),
+        None => bail!("The leaf '{leaf}' is not a hex-encoded 32-byte value"),
+    }
+}
+
+/// Decodes the given hex string, with an optional `0x` prefix.
+fn decode_hex(hex: &str) -> Result<Vec<u8>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    ensure!(hex.len() % 2 == 0 && hex.is_ascii(), "The value is not a valid hex string");
+    let mut bytes = Vec::with_capacity(hex.len() / 2);
+    for i in (0..hex.len()).step_by(2) {
+        match u8::from_str_radix(&hex[i..i + 2], 16) {
+            Ok(byte) => bytes.push(byte),
+            Err(_) => bail!("The value is not a valid hex string"),
+        }
+    }
+    Ok(bytes)
+}
+
+/// Encodes the given bytes as a hex string.
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Returns the little-endian bits of the given bytes.
+fn to_bits_le(bytes: &[u8]) -> Vec<bool> {
+    bytes.iter().flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_leaf() {
+        let leaf = decode_leaf(&"ab".repeat(32)).unwrap();
+        assert_eq!(leaf, [0xab; 32]);
+        assert_eq!(decode_leaf(&format!("0x{}", "01".repeat(32))).unwrap(), [0x01; 32]);
+
+        // Ensure malformed leaves are rejected.
+        assert!(decode_leaf("abcd").is_err());
+        assert!(decode_leaf(&"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn test_generate_and_verify_proof() {
+        let leaves = [[0u8; 32], [1u8; 32], [2u8; 32]];
+
+        // Generate a proof for the second leaf.
+        let (root, proof) = generate_proof(&leaves, 1).unwrap();
+        assert_eq!(root, compute_root(&leaves).unwrap());
+
+        // Ensure the proof survives a round trip.
+        let proof = MerklePath::<CurrentNetwork, MERKLE_TREE_DEPTH>::from_bytes_le(
+            &decode_hex(&encode_hex(&proof.to_bytes_le().unwrap())).unwrap(),
+        )
+        .unwrap();
+
+        // Ensure the proof only verifies for its leaf.
+        assert!(verify_proof(&root, &leaves[1], &proof));
+        assert!(!verify_proof(&root, &leaves[0], &proof));
+
+        // Ensure an out-of-bounds index is rejected.
+        assert!(generate_proof(&leaves, 3).is_err());
+    }
+
+    #[test]
+    fn test_bench_merkle() {
+        let report = bench_merkle::<8>(10, 5).unwrap();
+        assert_eq!(report.num_leaves, 10);
+        assert_eq!(report.num_proofs, 5);
+
+        // Ensure the number of leaves is bounded by the height.
+        assert!(bench_merkle::<8>(257, 1).is_err());
+    }
+
+    #[test]
+    fn test_compute_root() {
+        let leaves = [[0u8; 32], [1u8; 32]];
+        // The root is deterministic.
+        assert_eq!(compute_root(&leaves).unwrap(), compute_root(&leaves).unwrap());
+        // The root depends on the order of the leaves.
+        assert_ne!(compute_root(&leaves).unwrap(), compute_root(&[[1u8; 32], [0u8; 32]]).unwrap());
+    }
+}
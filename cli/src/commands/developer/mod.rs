@@ -21,6 +21,9 @@ pub use deploy::*;
 mod execute;
 pub use execute::*;
 
+mod merkle;
+pub use merkle::*;
+
 mod scan;
 pub use scan::*;
 
@@ -57,12 +60,20 @@ type CurrentNetwork = snarkvm::prelude::Testnet3;
 /// Commands to manage Aleo accounts.
 #[derive(Debug, Parser)]
 pub enum Developer {
+    /// Benchmark the construction of a merkle tree and its proofs.
+    BenchMerkle(BenchMerkle),
     /// Decrypt a ciphertext.
     Decrypt(Decrypt),
     /// Deploy a program.
     Deploy(Deploy),
     /// Execute a program function.
     Execute(Execute),
+    /// Compute the merkle root of a set of leaves.
+    MerkleRoot(MerkleRoot),
+    /// Generate a merkle membership proof for a leaf.
+    MerkleProof(MerkleProof),
+    /// Verify a merkle membership proof for a leaf.
+    MerkleVerify(MerkleVerify),
     /// Scan the node for records.
     Scan(Scan),
     /// Execute the `credits.aleo/transfer_private` function.
@@ -72,9 +83,13 @@ pub enum Developer {
 impl Developer {
     pub fn parse(self) -> Result<String> {
         match self {
+            Self::BenchMerkle(bench_merkle) => bench_merkle.parse(),
             Self::Decrypt(decrypt) => decrypt.parse(),
             Self::Deploy(deploy) => deploy.parse(),
             Self::Execute(execute) => execute.parse(),
+            Self::MerkleRoot(merkle_root) => merkle_root.parse(),
+            Self::MerkleProof(merkle_proof) => merkle_proof.parse(),
+            Self::MerkleVerify(merkle_verify) => merkle_verify.parse(),
             Self::Scan(scan) => scan.parse(),
             Self::TransferPrivate(transfer_private) => transfer_private.parse(),
         }
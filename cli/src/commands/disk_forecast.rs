@@ -0,0 +1,115 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_node_rest::StorageStats;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use std::{thread, time::Duration};
+use time::OffsetDateTime;
+
+/// The number of seconds in a day.
+const SECS_PER_DAY: f64 = 86_400.0;
+
+/// Estimates the growth rate of the ledger storage of a running node, and projects when its disk will be full.
+#[derive(Debug, Parser)]
+pub struct DiskForecast {
+    /// Specify the REST endpoint of the node
+    #[clap(long, default_value = "http://127.0.0.1:3033")]
+    pub rpc: String,
+    /// Specify the number of seconds to sample the ledger storage for
+    #[clap(long, default_value = "300")]
+    pub window: u64,
+}
+
+impl DiskForecast {
+    /// Samples the ledger storage over the window, and projects the disk usage.
+    pub fn parse(self) -> Result<String> {
+        if self.window == 0 {
+            bail!("The '--window' must be greater than 0");
+        }
+
+        let endpoint = format!("{}/testnet3/storageInfo", self.rpc.trim_end_matches('/'));
+        let fetch = || -> Result<StorageStats> { Ok(ureq::get(&endpoint).call()?.into_json()?) };
+
+        // Sample the ledger storage at the start and the end of the window.
+        let first = fetch()?;
+        println!("Sampling the ledger storage for {} seconds...", self.window);
+        thread::sleep(Duration::from_secs(self.window));
+        let last = fetch()?;
+
+        // Compute the growth rate.
+        let growth = last.ledger_size.saturating_sub(first.ledger_size);
+        let bytes_per_day = growth as f64 * SECS_PER_DAY / self.window as f64;
+        let rate = format!("📈 The ledger grows by {}/day", format_bytes(bytes_per_day as u64));
+        let Some(available_space) = last.available_space else {
+            return Ok(format!("{rate} (the free disk space is unknown)"));
+        };
+        let rate = format!("{rate} ({} of free disk space left)", format_bytes(available_space));
+
+        // Project when the disk will be full.
+        match days_until_full(bytes_per_day, available_space) {
+            Some(days) => {
+                // Note: the float-to-integer cast saturates, and the addition is checked for far-off dates.
+                let date = OffsetDateTime::now_utc()
+                    .checked_add(time::Duration::seconds((days * SECS_PER_DAY) as i64))
+                    .map(|date| date.date().to_string())
+                    .unwrap_or_else(|| "an unrepresentable date".to_string());
+                Ok(format!("{rate}\n📅 The disk will be full in approximately {days:.1} days, on {date}"))
+            }
+            None => Ok(format!("{rate}\n📅 The ledger did not grow during the window")),
+        }
+    }
+}
+
+/// Returns the number of days until the given free space is used up at the given rate, if the ledger grows.
+fn days_until_full(bytes_per_day: f64, available_space: u64) -> Option<f64> {
+    match bytes_per_day > 0.0 {
+        true => Some(available_space as f64 / bytes_per_day),
+        false => None,
+    }
+}
+
+/// Returns the given number of bytes in a human-readable format.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{bytes} B"),
+        _ => format!("{size:.2} {}", UNITS[unit]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_until_full() {
+        assert_eq!(days_until_full(10.0, 100), Some(10.0));
+        assert_eq!(days_until_full(0.0, 100), None);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.00 GiB");
+    }
+}
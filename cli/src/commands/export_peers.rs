@@ -0,0 +1,77 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, bail, Result};
+use clap::Parser;
+use serde_json::{json, Value};
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Exports the connected peers of a running node, and their capabilities, to a JSON file.
+#[derive(Debug, Parser)]
+pub struct ExportPeers {
+    /// Specify the REST endpoint of the node
+    #[clap(long, default_value = "http://127.0.0.1:3033")]
+    pub rpc: String,
+    /// Specify the path of the JSON file to write the peers to
+    #[clap(long, default_value = "peers.json")]
+    pub out: PathBuf,
+}
+
+impl ExportPeers {
+    /// Fetches the connected peers of the node, and writes them to the output file.
+    pub fn parse(self) -> Result<String> {
+        let endpoint = format!("{}/testnet3/peers/all/details", self.rpc.trim_end_matches('/'));
+        let peers: Value = match ureq::get(&endpoint).call() {
+            Ok(response) => response.into_json()?,
+            Err(error) => bail!("Failed to fetch the peers from '{}' - {error}", self.rpc),
+        };
+
+        let exported_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let (num_peers, export) = Self::to_export(&self.rpc, exported_at, peers)?;
+        std::fs::write(&self.out, serde_json::to_string_pretty(&export)?)
+            .map_err(|error| anyhow!("Failed to write the peers to '{}' - {error}", self.out.display()))?;
+
+        Ok(format!("✅ Exported {num_peers} peers to '{}'", self.out.display()))
+    }
+
+    /// Returns the number of peers, and the export of the given peers, labeled with the node they were
+    /// fetched from and the time of the export, so that exports from several nodes can be aggregated.
+    fn to_export(rpc: &str, exported_at: u64, peers: Value) -> Result<(usize, Value)> {
+        let Some(num_peers) = peers.as_array().map(|peers| peers.len()) else {
+            bail!("The node at '{rpc}' returned an invalid list of peers");
+        };
+        Ok((num_peers, json!({ "rpc": rpc, "exported_at": exported_at, "peers": peers })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_export() {
+        let peers = json!([{ "ip": "127.0.0.1:4133", "node_type": "Validator", "version": 13 }]);
+        let (num_peers, export) = ExportPeers::to_export("http://127.0.0.1:3033", 1_700_000_000, peers).unwrap();
+        assert_eq!(num_peers, 1);
+        assert_eq!(export["rpc"], "http://127.0.0.1:3033");
+        assert_eq!(export["exported_at"], 1_700_000_000);
+        assert_eq!(export["peers"][0]["ip"], "127.0.0.1:4133");
+
+        // Ensure a response that is not a list of peers is rejected.
+        assert!(ExportPeers::to_export("http://127.0.0.1:3033", 0, json!({ "error": "" })).is_err());
+    }
+}
@@ -15,18 +15,51 @@
 mod account;
 pub use account::*;
 
+mod balance;
+pub use balance::*;
+
 mod clean;
 pub use clean::*;
 
+mod compare;
+pub use compare::*;
+
+mod dev_accounts;
+pub use dev_accounts::*;
+
 mod developer;
 pub use developer::*;
 
+mod disk_forecast;
+pub use disk_forecast::*;
+
+mod export_peers;
+pub use export_peers::*;
+
+mod monitor;
+pub use monitor::*;
+
+mod probe;
+pub use probe::*;
+
+mod simulate;
+pub use simulate::*;
+
 mod start;
 pub use start::*;
 
 mod update;
 pub use update::*;
 
+mod validate_blocks;
+pub use validate_blocks::*;
+
+mod validator_report;
+pub use validator_report::*;
+
+mod wait_for_height;
+pub use wait_for_height::*;
+
 use anstyle::{AnsiColor, Color, Style};
 use anyhow::Result;
 use clap::{builder::Styles, Parser};
@@ -53,14 +86,36 @@ pub struct CLI {
 pub enum Command {
     #[clap(subcommand)]
     Account(Account),
+    #[clap(name = "balance")]
+    Balance(Balance),
     #[clap(name = "clean")]
     Clean(Clean),
+    #[clap(name = "compare")]
+    Compare(Compare),
+    #[clap(name = "dev-accounts")]
+    DevAccounts(DevAccounts),
     #[clap(subcommand)]
     Developer(Developer),
+    #[clap(name = "disk-forecast")]
+    DiskForecast(DiskForecast),
+    #[clap(name = "export-peers")]
+    ExportPeers(ExportPeers),
+    #[clap(name = "monitor")]
+    Monitor(Monitor),
+    #[clap(name = "probe")]
+    Probe(Probe),
+    #[clap(name = "simulate")]
+    Simulate(Simulate),
     #[clap(name = "start")]
     Start(Box<Start>),
     #[clap(name = "update")]
     Update(Update),
+    #[clap(name = "validate-blocks")]
+    ValidateBlocks(ValidateBlocks),
+    #[clap(name = "validator-report")]
+    ValidatorReport(ValidatorReport),
+    #[clap(name = "wait-for-height")]
+    WaitForHeight(WaitForHeight),
 }
 
 impl Command {
@@ -68,10 +123,21 @@ impl Command {
     pub fn parse(self) -> Result<String> {
         match self {
             Self::Account(command) => command.parse(),
+            Self::Balance(command) => command.parse(),
             Self::Clean(command) => command.parse(),
+            Self::Compare(command) => command.parse(),
+            Self::DevAccounts(command) => command.parse(),
             Self::Developer(command) => command.parse(),
+            Self::DiskForecast(command) => command.parse(),
+            Self::ExportPeers(command) => command.parse(),
+            Self::Monitor(command) => command.parse(),
+            Self::Probe(command) => command.parse(),
+            Self::Simulate(command) => command.parse(),
             Self::Start(command) => command.parse(),
             Self::Update(command) => command.parse(),
+            Self::ValidateBlocks(command) => command.parse(),
+            Self::ValidatorReport(command) => command.parse(),
+            Self::WaitForHeight(command) => command.parse(),
         }
     }
 }
@@ -0,0 +1,146 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use colored::Colorize;
+use serde_json::Value;
+use std::{process::Command, thread, time::Duration};
+
+/// Monitors the health of a running node, alerting when a threshold is violated.
+#[derive(Debug, Parser)]
+pub struct Monitor {
+    /// Specify the REST endpoint of the node to monitor
+    #[clap(long, default_value = "http://127.0.0.1:3033")]
+    pub rpc: String,
+    /// Specify the polling interval in seconds
+    #[clap(long, default_value = "10")]
+    pub interval: u64,
+    /// Specify the minimum number of connected peers
+    #[clap(long)]
+    pub min_peers: Option<usize>,
+    /// Specify the maximum number of blocks the node may lag behind its peers
+    #[clap(long)]
+    pub max_lag: Option<u32>,
+    /// Specify a shell command to run on alert, instead of exiting (the alert is passed in `SNARKOS_ALERT`)
+    #[clap(long)]
+    pub on_alert: Option<String>,
+}
+
+impl Monitor {
+    /// Polls the node until a threshold is violated (or forever, if an alert hook is given).
+    pub fn parse(self) -> Result<String> {
+        if self.interval == 0 {
+            bail!("The '--interval' must be greater than 0");
+        }
+
+        let endpoint = format!("{}/testnet3/networkInfo", self.rpc.trim_end_matches('/'));
+        loop {
+            // Fetch the network info, treating an unreachable node as an alert.
+            let alerts = match ureq::get(&endpoint).call() {
+                Ok(response) => {
+                    let info: Value = response.into_json()?;
+                    println!("{}", Self::dashboard(&info));
+                    self.check_thresholds(&info)
+                }
+                Err(error) => vec![format!("The node at '{}' is unreachable - {error}", self.rpc)],
+            };
+
+            for alert in alerts {
+                eprintln!("🚨 {}", alert.red());
+                match &self.on_alert {
+                    Some(hook) => Self::run_hook(hook, &alert),
+                    None => bail!("{alert}"),
+                }
+            }
+
+            thread::sleep(Duration::from_secs(self.interval));
+        }
+    }
+
+    /// Returns a one-line summary of the given network info.
+    fn dashboard(info: &Value) -> String {
+        format!(
+            "height {} / {} | peers {} (validators {}, provers {}, clients {}) | synced {} | uptime {}s",
+            info["latest_height"],
+            info["greatest_peer_height"],
+            info["peers"]["connected"],
+            info["peers"]["validators"],
+            info["peers"]["provers"],
+            info["peers"]["clients"],
+            info["is_synced"],
+            info["uptime_secs"],
+        )
+    }
+
+    /// Returns the alerts for every threshold violated by the given network info.
+    fn check_thresholds(&self, info: &Value) -> Vec<String> {
+        let mut alerts = Vec::new();
+        if let Some(min_peers) = self.min_peers {
+            let num_peers = info["peers"]["connected"].as_u64().unwrap_or_default() as usize;
+            if num_peers < min_peers {
+                alerts.push(format!("The node has {num_peers} connected peers (minimum is {min_peers})"));
+            }
+        }
+        if let Some(max_lag) = self.max_lag {
+            let latest_height = info["latest_height"].as_u64().unwrap_or_default();
+            let greatest_peer_height = info["greatest_peer_height"].as_u64().unwrap_or_default();
+            let lag = greatest_peer_height.saturating_sub(latest_height);
+            if lag > max_lag as u64 {
+                alerts.push(format!("The node is {lag} blocks behind its peers (maximum is {max_lag})"));
+            }
+        }
+        alerts
+    }
+
+    /// Runs the given alert hook, logging (but not propagating) any failure.
+    fn run_hook(hook: &str, alert: &str) {
+        match Command::new("sh").arg("-c").arg(hook).env("SNARKOS_ALERT", alert).status() {
+            Ok(status) if status.success() => (),
+            Ok(status) => eprintln!("The alert hook exited with {status}"),
+            Err(error) => eprintln!("Failed to run the alert hook - {error}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn monitor(min_peers: Option<usize>, max_lag: Option<u32>) -> Monitor {
+        Monitor { rpc: "http://127.0.0.1:3033".to_string(), interval: 1, min_peers, max_lag, on_alert: None }
+    }
+
+    #[test]
+    fn test_check_thresholds() {
+        let info = json!({ "peers": { "connected": 2 }, "latest_height": 90, "greatest_peer_height": 100 });
+
+        // Without thresholds, nothing is reported.
+        assert!(monitor(None, None).check_thresholds(&info).is_empty());
+        // Within the thresholds, nothing is reported.
+        assert!(monitor(Some(2), Some(10)).check_thresholds(&info).is_empty());
+        // Each violated threshold is reported.
+        assert_eq!(monitor(Some(3), None).check_thresholds(&info).len(), 1);
+        assert_eq!(monitor(None, Some(9)).check_thresholds(&info).len(), 1);
+        assert_eq!(monitor(Some(3), Some(9)).check_thresholds(&info).len(), 2);
+    }
+
+    #[test]
+    fn test_check_thresholds_ahead_of_peers() {
+        // A node ahead of its peers is not lagging.
+        let info = json!({ "peers": { "connected": 2 }, "latest_height": 100, "greatest_peer_height": 0 });
+        assert!(monitor(None, Some(0)).check_thresholds(&info).is_empty());
+    }
+}
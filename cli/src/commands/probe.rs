@@ -0,0 +1,164 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_account::Account;
+use snarkos_node::router::messages::{ChallengeRequest, ChallengeResponse, Message, MessageCodec, NodeType};
+use snarkvm::{
+    ledger::narwhal::Data,
+    prelude::{block::Block, FromBytes, Network, Testnet3},
+};
+
+use anyhow::{bail, Result};
+use bytes::BytesMut;
+use clap::Parser;
+use rand::Rng;
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpStream},
+    time::Duration,
+};
+use tokio_util::codec::{Decoder, Encoder};
+
+type CurrentNetwork = Testnet3;
+
+/// The maximum number of messages to read after the handshake, while waiting for the peer's `Ping`.
+const MAX_POST_HANDSHAKE_MESSAGES: usize = 10;
+
+/// Performs a handshake with a peer and reports what it advertises, without joining the network.
+#[derive(Debug, Parser)]
+pub struct Probe {
+    /// Specify the address of the peer to probe
+    pub peer: SocketAddr,
+    /// Specify the number of seconds to wait for each step of the handshake
+    #[clap(long, default_value = "10")]
+    pub timeout: u64,
+}
+
+impl Probe {
+    /// Probes the peer, returning its advertised node type, version, genesis, and height.
+    pub fn parse(self) -> Result<String> {
+        let timeout = Duration::from_secs(self.timeout);
+        let mut stream = match TcpStream::connect_timeout(&self.peer, timeout) {
+            Ok(stream) => stream,
+            Err(error) => bail!("Failed to connect to '{}' - {error}", self.peer),
+        };
+        stream.set_read_timeout(Some(timeout))?;
+        stream.set_write_timeout(Some(timeout))?;
+
+        Self::probe::<CurrentNetwork>(&mut stream, self.peer)
+    }
+
+    /// Performs the handshake as the connection initiator, then waits for the peer's first `Ping`.
+    fn probe<N: Network>(stream: &mut TcpStream, peer: SocketAddr) -> Result<String> {
+        let rng = &mut rand::thread_rng();
+        // Sample an ephemeral account for the handshake.
+        let account = Account::<N>::new(rng)?;
+        let genesis = Block::<N>::from_bytes_le(N::genesis_bytes())?;
+        // Frame the messages with the codec of the router, which limits the size of the handshake messages.
+        let mut codec = MessageCodec::<N>::handshake();
+        let mut buffer = BytesMut::new();
+
+        // Send the challenge request.
+        let our_nonce = rng.gen();
+        let our_request = ChallengeRequest::new(Message::<N>::VERSION, NodeType::Client, account.address(), our_nonce);
+        write_message(stream, &mut codec, Message::ChallengeRequest(our_request))?;
+
+        // Receive the peer's challenge response, followed by its challenge request.
+        let peer_response = match read_message(stream, &mut codec, &mut buffer)? {
+            Message::ChallengeResponse(response) => response,
+            Message::Disconnect(disconnect) => bail!("'{peer}' refused the handshake ({:?})", disconnect.reason),
+            message => bail!("'{peer}' sent '{}' instead of 'ChallengeResponse'", message.name()),
+        };
+        let peer_request = match read_message(stream, &mut codec, &mut buffer)? {
+            Message::ChallengeRequest(request) => request,
+            message => bail!("'{peer}' sent '{}' instead of 'ChallengeRequest'", message.name()),
+        };
+
+        // Verify the peer's challenge response.
+        let genesis_matches = peer_response.genesis_header == *genesis.header();
+        let signature = peer_response.signature.deserialize_blocking()?;
+        if !signature.verify_bytes(&peer_request.address, &our_nonce.to_le_bytes()) {
+            bail!("'{peer}' sent an invalid signature (it may be a private or development network)");
+        }
+
+        // Send our challenge response.
+        let Ok(our_signature) = account.sign_bytes(&peer_request.nonce.to_le_bytes(), rng) else {
+            bail!("Failed to sign the challenge request nonce from '{peer}'");
+        };
+        let our_response = ChallengeResponse {
+            genesis_header: *genesis.header(),
+            signature: Data::Object(our_signature),
+            secret_proof: None,
+        };
+        write_message(stream, &mut codec, Message::ChallengeResponse(our_response))?;
+
+        // Wait for the peer's first `Ping`, which advertises its latest block height.
+        // Note: After the handshake, the messages may be as large as in the network.
+        let mut codec = MessageCodec::<N>::default();
+        let mut height = None;
+        for _ in 0..MAX_POST_HANDSHAKE_MESSAGES {
+            match read_message(stream, &mut codec, &mut buffer) {
+                Ok(Message::Ping(ping)) => {
+                    height = ping.block_locators.map(|locators| locators.latest_locator_height());
+                    break;
+                }
+                Ok(Message::Disconnect(disconnect)) => bail!("'{peer}' disconnected ({:?})", disconnect.reason),
+                Ok(_) => continue,
+                // The peer may not send a `Ping` (e.g. if it is a prover), so stop waiting.
+                Err(_) => break,
+            }
+        }
+
+        let genesis = match genesis_matches {
+            true => genesis.hash().to_string(),
+            false => format!("mismatch (expected {})", genesis.hash()),
+        };
+        let height = height.map(|height| height.to_string()).unwrap_or_else(|| "unknown".to_string());
+        let mut report = format!("✅ Completed the handshake with '{peer}'\n");
+        report += &format!("  node type: {}\n", peer_request.node_type);
+        report += &format!("  address:   {}\n", peer_request.address);
+        report += &format!("  version:   {}\n", peer_request.version);
+        report += &format!("  genesis:   {genesis}\n");
+        report += &format!("  height:    {height}");
+        Ok(report)
+    }
+}
+
+/// Writes the given message to the stream, framed by the given codec.
+fn write_message<N: Network>(stream: &mut TcpStream, codec: &mut MessageCodec<N>, message: Message<N>) -> Result<()> {
+    let mut bytes = BytesMut::new();
+    codec.encode(message, &mut bytes)?;
+    stream.write_all(&bytes)?;
+    Ok(stream.flush()?)
+}
+
+/// Reads the next message from the stream, framed by the given codec.
+/// The buffer holds the bytes read from the stream that belong to the following messages.
+fn read_message<N: Network>(
+    stream: &mut TcpStream,
+    codec: &mut MessageCodec<N>,
+    buffer: &mut BytesMut,
+) -> Result<Message<N>> {
+    let mut chunk = [0u8; 16 * 1024];
+    loop {
+        // Note: The codec rejects an oversized message by its length prefix, before reading its bytes.
+        if let Some(message) = codec.decode(buffer)? {
+            return Ok(message);
+        }
+        match stream.read(&mut chunk)? {
+            0 => bail!("The peer closed the connection"),
+            num_bytes => buffer.extend_from_slice(&chunk[..num_bytes]),
+        }
+    }
+}
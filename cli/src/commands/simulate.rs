@@ -0,0 +1,263 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_node::router::{
+    messages::{BlockResponse, Message, UnconfirmedTransaction},
+    MessageRecorder,
+    RecordedMessage,
+};
+use snarkvm::prelude::{block::Block, store::helpers::memory::ConsensusMemory, FromBytes, Ledger, Network, Testnet3};
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+type CurrentNetwork = Testnet3;
+
+/// The seed of the RNG used to check blocks and transactions, so that simulations are reproducible.
+const SIMULATION_RNG_SEED: u64 = 1234567890u64;
+
+/// Replays a recorded trace of block and transaction arrivals against an in-memory ledger,
+/// without networking, and reports the throughput, latency, and memory usage.
+#[derive(Debug, Parser)]
+pub struct Simulate {
+    /// Specify the path to the trace (a message recording, as written by 'snarkos start --record-messages')
+    #[clap(long)]
+    pub trace: PathBuf,
+    /// Specify the path to the genesis block of the trace (default: the genesis block of the network)
+    #[clap(long)]
+    pub genesis: Option<PathBuf>,
+    /// If the flag is set, the trace is replayed at its recorded pace, instead of as fast as possible
+    #[clap(long)]
+    pub realtime: bool,
+}
+
+impl Simulate {
+    /// Runs the simulation, and returns its report.
+    pub fn parse(self) -> Result<String> {
+        // Load the trace.
+        let trace = MessageRecorder::load::<CurrentNetwork, _>(&self.trace)?;
+        // Load the genesis block.
+        let genesis = match &self.genesis {
+            Some(path) => match std::fs::read(path) {
+                Ok(bytes) => Block::read_le(&bytes[..])?,
+                Err(error) => bail!("Failed to read the genesis block '{}' - {error}", path.display()),
+            },
+            None => Block::read_le(CurrentNetwork::genesis_bytes())?,
+        };
+
+        let report = Self::simulate(genesis, trace, self.realtime)?;
+        Ok(report.to_string())
+    }
+
+    /// Replays the given trace in order, on an in-memory ledger initialized from the given genesis block.
+    fn simulate<N: Network>(
+        genesis: Block<N>,
+        trace: Vec<RecordedMessage<N>>,
+        realtime: bool,
+    ) -> Result<SimulationReport> {
+        let (Some(first), Some(last)) = (trace.first(), trace.last()) else { bail!("The trace is empty") };
+        let trace_span = Duration::from_millis(last.timestamp.saturating_sub(first.timestamp));
+        let first_timestamp = first.timestamp;
+
+        // Initialize an in-memory ledger from the genesis block.
+        let ledger = Ledger::<N, ConsensusMemory<N>>::load(genesis, None)?;
+        let rng = &mut ChaChaRng::seed_from_u64(SIMULATION_RNG_SEED);
+
+        let mut report = SimulationReport { num_messages: trace.len(), trace_span, ..Default::default() };
+        let start = Instant::now();
+        for RecordedMessage { timestamp, message, .. } in trace {
+            // Wait until the message is due, if the trace is replayed at its recorded pace.
+            if realtime {
+                let due = Duration::from_millis(timestamp.saturating_sub(first_timestamp));
+                std::thread::sleep(due.saturating_sub(start.elapsed()));
+            }
+
+            match message {
+                Message::BlockResponse(BlockResponse { blocks, .. }) => {
+                    let blocks = match blocks.deserialize_blocking() {
+                        Ok(blocks) => blocks.0,
+                        Err(_) => {
+                            report.blocks.rejected += 1;
+                            continue;
+                        }
+                    };
+                    for block in blocks {
+                        // Skip the blocks that are already in the ledger, e.g. from overlapping responses.
+                        if block.height() <= ledger.latest_height() {
+                            report.blocks.duplicates += 1;
+                            continue;
+                        }
+                        // Check the block, and advance to it on success.
+                        let timer = Instant::now();
+                        let result =
+                            ledger.check_next_block(&block, rng).and_then(|_| ledger.advance_to_next_block(&block));
+                        report.blocks.record(result.is_ok(), timer.elapsed());
+                    }
+                }
+                Message::UnconfirmedTransaction(UnconfirmedTransaction { transaction, .. }) => {
+                    let transaction = match transaction.deserialize_blocking() {
+                        Ok(transaction) => transaction,
+                        Err(_) => {
+                            report.transactions.rejected += 1;
+                            continue;
+                        }
+                    };
+                    // Check the transaction against the current state of the ledger.
+                    let timer = Instant::now();
+                    let result = ledger.check_transaction_basic(&transaction, None, rng);
+                    report.transactions.record(result.is_ok(), timer.elapsed());
+                }
+                // The other messages do not change the ledger, so they are not simulated.
+                _ => report.num_ignored += 1,
+            }
+        }
+        report.elapsed = start.elapsed();
+        report.final_height = ledger.latest_height();
+        report.peak_memory = peak_memory_in_bytes();
+        Ok(report)
+    }
+}
+
+/// The outcomes and latencies of one kind of simulated arrival.
+#[derive(Debug, Default)]
+struct Arrivals {
+    /// The number of arrivals that were accepted.
+    accepted: usize,
+    /// The number of arrivals that were rejected.
+    rejected: usize,
+    /// The number of arrivals that were skipped, as they were already processed.
+    duplicates: usize,
+    /// The processing time of each accepted or rejected arrival.
+    latencies: Vec<Duration>,
+}
+
+impl Arrivals {
+    /// Records the outcome and processing time of an arrival.
+    fn record(&mut self, accepted: bool, latency: Duration) {
+        match accepted {
+            true => self.accepted += 1,
+            false => self.rejected += 1,
+        }
+        self.latencies.push(latency);
+    }
+
+    /// Returns the given percentile of the latencies, using the nearest-rank method.
+    fn percentile(&self, percentile: usize) -> Duration {
+        let mut latencies = self.latencies.clone();
+        latencies.sort_unstable();
+        match latencies.len() {
+            0 => Duration::ZERO,
+            len => latencies[(len - 1) * percentile.min(100) / 100],
+        }
+    }
+
+    /// Returns the number of accepted arrivals per second, over the given duration.
+    fn throughput(&self, elapsed: Duration) -> f64 {
+        match elapsed.is_zero() {
+            true => 0.0,
+            false => self.accepted as f64 / elapsed.as_secs_f64(),
+        }
+    }
+
+    /// Returns the summary line of the arrivals.
+    fn summary(&self, name: &str, elapsed: Duration) -> String {
+        format!(
+            "{name}: {} accepted, {} rejected, {} duplicates | {:.2}/s | latency p50 {:?}, p99 {:?}, max {:?}",
+            self.accepted,
+            self.rejected,
+            self.duplicates,
+            self.throughput(elapsed),
+            self.percentile(50),
+            self.percentile(99),
+            self.percentile(100),
+        )
+    }
+}
+
+/// The results of a simulation.
+#[derive(Debug, Default)]
+struct SimulationReport {
+    /// The number of messages in the trace.
+    num_messages: usize,
+    /// The number of messages that do not change the ledger, and were not simulated.
+    num_ignored: usize,
+    /// The time between the first and last message of the trace.
+    trace_span: Duration,
+    /// The time taken to replay the trace.
+    elapsed: Duration,
+    /// The latest block height after the simulation.
+    final_height: u32,
+    /// The peak memory usage of the process, if it is available on this platform.
+    peak_memory: Option<u64>,
+    /// The simulated block arrivals.
+    blocks: Arrivals,
+    /// The simulated transaction arrivals.
+    transactions: Arrivals,
+}
+
+impl std::fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Simulated {} messages ({} ignored)", self.num_messages, self.num_ignored)?;
+        writeln!(f, "Trace span: {:?} | Replay time: {:?}", self.trace_span, self.elapsed)?;
+        writeln!(f, "{}", self.blocks.summary("Blocks", self.elapsed))?;
+        writeln!(f, "{}", self.transactions.summary("Transactions", self.elapsed))?;
+        writeln!(f, "Final height: {}", self.final_height)?;
+        match self.peak_memory {
+            Some(bytes) => write!(f, "Peak memory: {:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+            None => write!(f, "Peak memory: unavailable"),
+        }
+    }
+}
+
+/// Returns the peak resident memory of the process in bytes, if it is available on this platform.
+fn peak_memory_in_bytes() -> Option<u64> {
+    // Note: On Linux, the peak resident set size is reported as 'VmHWM' (in kB) in the process status.
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kilobytes = line.trim_start_matches("VmHWM:").trim().trim_end_matches("kB").trim().parse::<u64>().ok()?;
+    Some(kilobytes * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulate_empty() {
+        let genesis = Block::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        assert!(Simulate::simulate::<CurrentNetwork>(genesis, vec![], false).is_err());
+    }
+
+    #[test]
+    fn test_arrivals_percentile() {
+        let mut arrivals = Arrivals::default();
+        assert_eq!(arrivals.percentile(50), Duration::ZERO);
+
+        for millis in [40, 10, 30, 20, 50] {
+            arrivals.record(millis != 50, Duration::from_millis(millis));
+        }
+        assert_eq!(arrivals.accepted, 4);
+        assert_eq!(arrivals.rejected, 1);
+        assert_eq!(arrivals.percentile(0), Duration::from_millis(10));
+        assert_eq!(arrivals.percentile(50), Duration::from_millis(30));
+        assert_eq!(arrivals.percentile(100), Duration::from_millis(50));
+        assert_eq!(arrivals.throughput(Duration::from_secs(2)), 2.0);
+    }
+}
@@ -12,9 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::helpers::{LogFormat, NetworkProfile, Syslog};
 use snarkos_account::Account;
 use snarkos_display::Display;
-use snarkos_node::{bft::MEMORY_POOL_PORT, router::messages::NodeType, Node};
+use snarkos_node::{
+    bft::{helpers::ProductionGrace, ledger_service::DEFAULT_VERIFICATION_CACHE_SIZE, MEMORY_POOL_PORT},
+    cdn::CdnVerification,
+    rest::{CorsOrigins, RestConfig},
+    router::{messages::NodeType, ConnectionLimits, ReconnectBackoff, RouterConfig, PING_SLEEP_IN_SECS},
+    sync::{BlockStream, BlockSyncConfig, MinorityForkPolicy},
+    Node,
+    NodeConfig,
+    TaskFailurePolicies,
+    DEFAULT_SHUTDOWN_TIMEOUT_IN_SECS,
+};
 use snarkvm::{
     console::{
         account::{Address, PrivateKey},
@@ -24,27 +35,52 @@ use snarkvm::{
     ledger::{
         block::Block,
         committee::{Committee, MIN_VALIDATOR_STAKE},
-        store::{helpers::memory::ConsensusMemory, ConsensusStore},
+        store::{
+            helpers::{memory::ConsensusMemory, rocksdb::ConsensusDB},
+            ConsensusStorage,
+            ConsensusStore,
+        },
     },
     prelude::{FromBytes, ToBits, ToBytes},
     synthesizer::VM,
     utilities::to_bytes_le,
 };
 
-use anyhow::{bail, ensure, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use clap::Parser;
 use colored::Colorize;
 use core::str::FromStr;
+use crossterm::tty::IsTty;
 use rand::SeedableRng;
 use rand_chacha::ChaChaRng;
-use std::{net::SocketAddr, path::PathBuf};
-use tokio::runtime::{self, Runtime};
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+use tokio::{
+    runtime::{self, Runtime},
+    sync::mpsc::Receiver,
+};
 
 /// The recommended minimum number of 'open files' limit for a validator.
 /// Validators should be able to handle at least 1000 concurrent connections, each requiring 2 sockets.
 #[cfg(target_family = "unix")]
 const RECOMMENDED_MIN_NOFILES_LIMIT: u64 = 2048;
 
+/// The ID of the network that the node runs on, and the other commands target, by default.
+pub(crate) const DEFAULT_NETWORK_ID: u16 = <Testnet3 as Network>::ID;
+/// The IDs and names of the networks that the node is able to run on.
+const SUPPORTED_NETWORKS: [(u16, &str); 1] = [(<Testnet3 as Network>::ID, <Testnet3 as Network>::NAME)];
+
+/// The number of seconds to wait for the initial connections to the '--peers', before checking for isolation.
+const STARTUP_CONNECT_TIMEOUT_IN_SECS: u64 = 10;
+
+/// The environment variable to read the REST admin token from, if '--admin-token' is not set.
+const ADMIN_TOKEN_ENV_VAR: &str = "SNARKOS_ADMIN_TOKEN";
+/// The well-known default credentials, which must not protect a REST server that is reachable from the network.
+const DEFAULT_CREDENTIALS: [&str; 8] = ["root", "pass", "password", "admin", "changeme", "secret", "snarkos", "token"];
+
 /// The development mode RNG seed.
 const DEVELOPMENT_MODE_RNG_SEED: u64 = 1234567890u64;
 /// The development mode number of genesis committee members.
@@ -53,9 +89,18 @@ const DEVELOPMENT_MODE_NUM_GENESIS_COMMITTEE_MEMBERS: u16 = 4;
 /// Starts the snarkOS node.
 #[derive(Clone, Debug, Parser)]
 pub struct Start {
+    /// Specify the path to a TOML file of options (options given on the command line take precedence)
+    #[clap(long = "config")]
+    pub config: Option<PathBuf>,
     /// Specify the network ID of this node
-    #[clap(default_value = "3", long = "network")]
+    #[clap(default_value_t = DEFAULT_NETWORK_ID, long = "network")]
     pub network: u16,
+    /// Specify the path to a network profile (a JSON file with the network ID, genesis, peers, and ports)
+    #[clap(long = "network-profile")]
+    pub network_profile: Option<PathBuf>,
+    /// The loaded network profile.
+    #[clap(skip)]
+    profile: Option<NetworkProfile>,
 
     /// Specify this node as a validator
     #[clap(long = "validator")]
@@ -77,15 +122,81 @@ pub struct Start {
     /// Specify the IP address and port for the node server
     #[clap(default_value = "0.0.0.0:4133", long = "node")]
     pub node: SocketAddr,
+    /// Specify an additional IPv6 address and port for the node server, to listen on both IPv4 and IPv6
+    #[clap(long = "node-ipv6")]
+    pub node_ipv6: Option<SocketAddr>,
+    /// Specify whether the IPv6 node server only accepts IPv6 connections (IPV6_V6ONLY) [options: true, false]
+    /// If not set, the OS setting is used, unless '--node-ipv6' is set, in which case it defaults to true
+    #[clap(long = "ipv6-only")]
+    pub ipv6_only: Option<bool>,
     /// Specify the IP address and port for the BFT
     #[clap(long = "bft")]
     pub bft: Option<SocketAddr>,
-    /// Specify the IP address and port of the peer(s) to connect to
+    /// Specify the IP address and port of the peer(s) to connect to, each optionally with a priority from 0 to 255
+    /// as '<ip>:<port>@<priority>' (default: 0). The node prefers the peers of the highest priority, and only falls
+    /// back to the peers of a lower priority while the former fail to connect
     #[clap(default_value = "", long = "peers")]
     pub peers: String,
     /// Specify the IP address and port of the validator(s) to connect to
     #[clap(default_value = "", long = "validators")]
     pub validators: String,
+    /// Specify a shared secret that peers must prove knowledge of in the handshake (for private networks).
+    /// Note: This only covers the router; the BFT gateway of a validator does not check the secret
+    #[clap(long = "network-secret")]
+    pub network_secret: Option<String>,
+    /// Specify the maximum number of blocks a peer may advertise above the median peer height during sync
+    #[clap(long = "height-sanity-threshold")]
+    pub height_sanity_threshold: Option<u32>,
+    /// Specify the maximum number of blocks that may be in flight (requested, but not yet applied) during sync
+    #[clap(long = "sync-window")]
+    pub sync_window: Option<usize>,
+    /// Specify the number of seconds after which an unanswered block request times out during sync
+    #[clap(long = "block-request-timeout")]
+    pub block_request_timeout: Option<u64>,
+    /// Specify the maximum number of connected validators
+    #[clap(long = "max-validator-peers")]
+    pub max_validator_peers: Option<usize>,
+    /// Specify the maximum number of connected provers
+    #[clap(long = "max-prover-peers")]
+    pub max_prover_peers: Option<usize>,
+    /// Specify the maximum number of connected clients
+    #[clap(long = "max-client-peers")]
+    pub max_client_peers: Option<usize>,
+    /// Specify the maximum number of peers connected by inbound connections (default: '--max-peers')
+    #[clap(long = "max-inbound")]
+    pub max_inbound: Option<usize>,
+    /// Specify the maximum number of peers connected by outbound connections (default: '--max-peers')
+    #[clap(long = "max-outbound")]
+    pub max_outbound: Option<usize>,
+    /// Specify the maximum number of both inbound and outbound peer connections
+    #[clap(long = "max-peers")]
+    pub max_peers: Option<usize>,
+    /// If the flag is set, the node refuses inbound connections from non-validators while it is syncing
+    #[clap(long = "reject-while-syncing")]
+    pub reject_while_syncing: bool,
+    /// Specify the number of seconds without reading a message from, or while writing one to, a peer
+    /// after which the connection is considered dead and dropped (must exceed the 20 second ping interval)
+    #[clap(long = "peer-io-timeout")]
+    pub peer_io_timeout: Option<u64>,
+    /// Specify the maximum number of block requests served to each peer per minute (excess requests are dropped)
+    #[clap(long = "serve-rate-limit")]
+    pub serve_rate_limit: Option<usize>,
+    /// Specify the maximum size in bytes of a message from a peer (default: 128 MiB)
+    /// A peer that sends a larger message is disconnected and restricted
+    #[clap(long = "max-message-size")]
+    pub max_message_size: Option<usize>,
+    /// Specify the delay in seconds before reconnecting to a disconnected trusted peer
+    #[clap(default_value = "25", long = "reconnect-initial")]
+    pub reconnect_initial: u64,
+    /// Specify the factor by which the reconnection delay grows after each failed attempt
+    #[clap(default_value = "2", long = "reconnect-multiplier")]
+    pub reconnect_multiplier: u32,
+    /// Specify the maximum delay in seconds in between reconnection attempts to a trusted peer
+    #[clap(default_value = "25", long = "reconnect-max")]
+    pub reconnect_max: u64,
+    /// Specify the maximum number of consecutive attempts to reconnect to a trusted peer (0 retries indefinitely)
+    #[clap(default_value = "0", long = "connect-retries")]
+    pub connect_retries: u32,
 
     /// Specify the IP address and port for the REST server
     #[clap(default_value = "0.0.0.0:3033", long = "rest")]
@@ -93,16 +204,138 @@ pub struct Start {
     /// If the flag is set, the node will not initialize the REST server
     #[clap(long)]
     pub norest: bool,
+    /// Specify the maximum number of requests per second the REST server accepts from each IP
+    /// (excess requests receive '429 Too Many Requests')
+    #[clap(long = "rest-rate-limit", alias = "rpc-rate-limit")]
+    pub rest_rate_limit: Option<u32>,
+    /// Specify the maximum number of requests in a batch to the REST 'POST /testnet3/batch' endpoint (default: 50)
+    #[clap(long = "rest-max-batch", alias = "rpc-max-batch")]
+    pub rest_max_batch: Option<usize>,
+    /// Specify the origins allowed to make cross-origin requests to the REST server, as a comma-separated list
+    /// (e.g. 'https://example.com'), or '*' for any origin (default: any origin)
+    #[clap(long = "rest-cors", alias = "rpc-cors")]
+    pub rest_cors: Option<String>,
+    /// Specify the path to the TLS certificate chain (PEM) of the REST server, which then serves HTTPS
+    /// (on SIGHUP, the certificate and key are reloaded from their files, e.g. after a rotation)
+    #[clap(long = "rest-tls-cert", alias = "rpc-tls-cert")]
+    pub rest_tls_cert: Option<PathBuf>,
+    /// Specify the path to the TLS private key (PEM) of the REST server
+    #[clap(long = "rest-tls-key", alias = "rpc-tls-key")]
+    pub rest_tls_key: Option<PathBuf>,
+    /// Specify the IP address and port to serve the Prometheus metrics at (requires the 'metrics' feature)
+    #[clap(long = "metrics")]
+    pub metrics: Option<SocketAddr>,
+    /// Specify the path to the file to periodically append the key metrics to (requires the 'metrics' feature)
+    /// The file is rotated daily, keeping the number of days given by '--log-keep'
+    #[clap(long = "metrics-file")]
+    pub metrics_file: Option<PathBuf>,
+    /// Specify the interval in seconds at which to append the key metrics to the metrics file
+    #[clap(default_value = "60", long = "metrics-interval")]
+    pub metrics_interval: u64,
+    /// Specify the format of the metrics file: 'csv' or 'line-protocol'
+    #[clap(default_value = "csv", long = "metrics-format")]
+    pub metrics_format: String,
+    /// Specify the IP address and port to serve a minimal block explorer for development (requires the REST server)
+    /// The explorer page queries the REST server from the browser, so a restricted '--rest-cors' must allow its
+    /// origin (e.g. 'http://localhost:3000')
+    #[clap(long = "explorer")]
+    pub explorer: Option<SocketAddr>,
+    /// Specify the token required to access the REST admin endpoints (default: only reachable from localhost)
+    /// If not set, the token is read from the `SNARKOS_ADMIN_TOKEN` environment variable
+    #[clap(long = "admin-token")]
+    pub admin_token: Option<String>,
+    /// Specify a static token to accept for the JWT-protected REST endpoints, as 'Authorization: Bearer <token>'
+    /// The one-time JWT token printed on startup remains valid
+    #[clap(long = "rest-token", alias = "rpc-token", conflicts_with = "rest_token_file")]
+    pub rest_token: Option<String>,
+    /// Specify the path to a file containing the static token to accept for the JWT-protected REST endpoints
+    #[clap(long = "rest-token-file", alias = "rpc-token-file")]
+    pub rest_token_file: Option<PathBuf>,
 
     /// If the flag is set, the node will not render the display
     #[clap(long)]
     pub nodisplay: bool,
+    /// If the flag is set, the node aborts startup on any startup warning
+    #[clap(long = "fast-fail")]
+    pub fast_fail: bool,
+    /// If the flag is set, the node starts even if its REST server is reachable from the network,
+    /// while it accepts a well-known default credential (e.g. 'root' or 'pass') as a token
+    #[clap(long = "rest-allow-default-credentials", alias = "rpc-allow-default-credentials")]
+    pub rest_allow_default_credentials: bool,
     /// Specify the verbosity of the node [options: 0, 1, 2, 3, 4]
     #[clap(default_value = "1", long = "verbosity")]
     pub verbosity: u8,
-    /// Specify the path to the file where logs will be stored
-    #[clap(default_value_os_t = std::env::temp_dir().join("snarkos.log"), long = "logfile")]
+    /// Specify the path to the file where logs will be stored (rotated daily, with the date appended to old logs)
+    #[clap(default_value_os_t = std::env::temp_dir().join("snarkos.log"), long = "logfile", alias = "log-file")]
     pub logfile: PathBuf,
+    /// Specify the format of the logs [options: text, json]
+    #[clap(default_value = "text", long = "log-format")]
+    pub log_format: String,
+    /// Specify the address of a syslog server to also send the logs to (RFC 5424)
+    /// e.g. 'udp://10.0.0.1:514', 'tcp://10.0.0.1:601', or '10.0.0.1:514' for UDP
+    #[clap(long = "syslog")]
+    pub syslog: Option<String>,
+    /// Specify the directory in which to store the logs, as 'snarkos.log' (overrides '--logfile')
+    #[clap(long = "log-dir", conflicts_with = "logfile")]
+    pub log_dir: Option<PathBuf>,
+    /// Specify the number of days of rotated log files to keep (older log files are removed on startup)
+    #[clap(default_value = "7", long = "log-keep", alias = "log-retention-days")]
+    pub log_keep: u16,
+    /// Specify the path to a file in which unclean shutdowns (and their reasons) are tracked across restarts
+    #[clap(long = "crash-info")]
+    pub crash_info: Option<PathBuf>,
+    /// Specify the action when a background task fails [options: restart, shutdown, ignore]
+    /// A task class may be configured separately, e.g. 'ignore,sync=restart' [classes: sync, puzzle, transaction-pool]
+    #[clap(default_value = "ignore", long = "on-task-failure")]
+    pub on_task_failure: String,
+    /// Specify the action when the majority of peers are on a different chain [options: alert, halt]
+    /// With 'halt', the node stops syncing and proposing batches while the majority of its trusted peers
+    /// (its committee peers, or the '--peers' of a client) are on a different chain, and resumes once they agree
+    #[clap(default_value = "alert", long = "on-minority-fork")]
+    pub on_minority_fork: String,
+    /// Specify the number of seconds a validator must be synced and connected to '--min-peers' validators
+    /// after starting, before it begins proposing batches
+    #[clap(long = "production-grace")]
+    pub production_grace: Option<u64>,
+    /// Specify the minimum number of connected validators during the '--production-grace' period
+    #[clap(default_value = "0", long = "min-peers")]
+    pub min_peers: usize,
+    /// If the flag is set, the node records a histogram of its block validation timings for profiling
+    /// (exposed on the REST 'validationHistogram' endpoint, and as metrics)
+    #[clap(long = "validation-histogram")]
+    pub validation_histogram: bool,
+    /// Specify the number of successful transaction and solution verifications to cache, so that a transmission
+    /// checked again before the next block (e.g. when it is proposed in a batch) is not re-verified (0 disables it)
+    #[clap(default_value_t = DEFAULT_VERIFICATION_CACHE_SIZE, long = "verification-cache")]
+    pub verification_cache: usize,
+    /// Specify the path to a file (or named pipe) to append each newly-applied block to, for indexers
+    /// Each block is written as its length (u32, little-endian) followed by its bytes
+    #[clap(long = "block-stream")]
+    pub block_stream: Option<PathBuf>,
+    /// Specify the number of seconds the node may take to shut down, after which the process exits regardless
+    #[clap(default_value_t = DEFAULT_SHUTDOWN_TIMEOUT_IN_SECS, long = "shutdown-timeout")]
+    pub shutdown_timeout: u64,
+    /// Specify the path to a file where all inbound peer messages will be recorded
+    #[clap(long = "record-messages")]
+    pub record_messages: Option<PathBuf>,
+    /// Specify the path to a message recording to replay into the node on startup
+    #[clap(long = "replay-messages")]
+    pub replay_messages: Option<PathBuf>,
+    /// If the flag is set, the node only relays unconfirmed solutions and transactions once it is synced
+    #[clap(long = "relay-after-sync")]
+    pub relay_after_sync: bool,
+    /// If the flag is set, the node pauses production and relay once most peers are on a newer protocol version
+    #[clap(long = "upgrade-awareness")]
+    pub upgrade_awareness: bool,
+    /// If the flag is set, peers that send a block which fails verification are banned, instead of only disconnected
+    #[clap(long = "ban-on-invalid-block")]
+    pub ban_on_invalid_block: bool,
+    /// Specify the maximum number of running router tasks, beyond which a (likely) task leak is reported
+    #[clap(long = "max-tasks")]
+    pub max_tasks: Option<usize>,
+    /// If the flag is set, tasks beyond '--max-tasks' are refused, instead of only being reported
+    #[clap(long = "refuse-excess-tasks")]
+    pub refuse_excess_tasks: bool,
 
     /// Enables the node to prefetch initial blocks from a CDN
     #[clap(default_value = "https://s3.us-west-1.amazonaws.com/testnet3.blocks/phase3", long = "cdn")]
@@ -110,6 +343,15 @@ pub struct Start {
     /// If the flag is set, the node will not prefresh from a CDN
     #[clap(long)]
     pub nocdn: bool,
+    /// Specify the verification of blocks imported from the CDN [options: none, headers, full]
+    /// 'headers' checks that each block extends the ledger, and 'full' fully verifies each block
+    #[clap(default_value = "none", long = "verify-on-import")]
+    pub verify_on_import: String,
+
+    /// Specify the ledger storage backend [options: rocksdb, memory]
+    /// The memory backend is ephemeral: the ledger is discarded when the node shuts down
+    #[clap(default_value = "rocksdb", long = "storage")]
+    pub storage: String,
 
     /// Enables development mode, specify a unique ID for this node
     #[clap(long)]
@@ -121,25 +363,44 @@ pub struct Start {
 
 impl Start {
     /// Starts the snarkOS node.
-    pub fn parse(self) -> Result<String> {
+    pub fn parse(mut self) -> Result<String> {
+        // Apply the network profile, if one is given.
+        self.parse_network_profile()?;
+        // Ensure the network and the storage backend are supported, before starting the node.
+        self.check_network_and_storage()?;
+        // Ensure the node type flags do not conflict, before starting the node.
+        self.parse_node_type()?;
+        // Ensure the private key is valid, before starting the node.
+        // Note: Each network in `SUPPORTED_NETWORKS` must be dispatched here.
+        match self.network {
+            Testnet3::ID => self.check_private_key::<Testnet3>()?,
+            network => unreachable!("The network ID '{network}' was checked to be supported"),
+        }
+
         // Initialize the logger.
-        let log_receiver = crate::helpers::initialize_logger(self.verbosity, self.nodisplay, self.logfile.clone());
+        let log_format = LogFormat::from_str(&self.log_format)?;
+        let syslog = self.syslog.as_deref().map(Syslog::new).transpose()?;
+        let logfile = match &self.log_dir {
+            Some(log_dir) => log_dir.join("snarkos.log"),
+            None => self.logfile.clone(),
+        };
+        let log_receiver = crate::helpers::initialize_logger(
+            self.verbosity,
+            self.nodisplay,
+            logfile,
+            self.log_keep,
+            log_format,
+            syslog,
+        );
         // Initialize the runtime.
         Self::runtime().block_on(async move {
             // Clone the configurations.
             let mut cli = self.clone();
-            // Parse the network.
+            // Start the node on the network.
+            // Note: Each network in `SUPPORTED_NETWORKS` must be dispatched here.
             match cli.network {
-                3 => {
-                    // Parse the node from the configurations.
-                    let node = cli.parse_node::<Testnet3>().await.expect("Failed to parse the node");
-                    // If the display is enabled, render the display.
-                    if !cli.nodisplay {
-                        // Initialize the display.
-                        Display::start(node, log_receiver).expect("Failed to initialize the display");
-                    }
-                }
-                _ => panic!("Invalid network ID specified"),
+                Testnet3::ID => cli.start_network::<Testnet3>(log_receiver).await,
+                network => unreachable!("The network ID '{network}' was checked to be supported"),
             };
             // Note: Do not move this. The pending await must be here otherwise
             // other snarkOS commands will not exit.
@@ -151,22 +412,112 @@ impl Start {
 }
 
 impl Start {
+    /// Ensures the network ID and the storage backend are supported.
+    fn check_network_and_storage(&self) -> Result<()> {
+        if !SUPPORTED_NETWORKS.iter().any(|(id, _)| *id == self.network) {
+            let supported = SUPPORTED_NETWORKS.iter().map(|(id, name)| format!("{id} ({name})")).collect::<Vec<_>>();
+            bail!("Unsupported network ID '{}' (supported: {})", self.network, supported.join(", "));
+        }
+        if !["rocksdb", "memory"].contains(&self.storage.as_str()) {
+            bail!("Invalid storage backend '{}' (expected 'rocksdb' or 'memory')", self.storage);
+        }
+        Ok(())
+    }
+
+    /// Ensures the private key of the node is given and well-formed.
+    /// Note: In development mode, the private key is derived when the node starts, and is not checked here.
+    fn check_private_key<N: Network>(&self) -> Result<()> {
+        if self.dev.is_none() {
+            self.parse_private_key::<N>()?;
+        }
+        Ok(())
+    }
+
+    /// Starts the node on the given network, with the configured ledger storage backend.
+    async fn start_network<N: Network>(&mut self, log_receiver: Receiver<Vec<u8>>) {
+        match self.storage.as_str() {
+            "rocksdb" => self.start_node::<N, ConsensusDB<N>>(log_receiver).await,
+            "memory" => self.start_node::<N, ConsensusMemory<N>>(log_receiver).await,
+            storage => unreachable!("The storage backend '{storage}' was checked to be supported"),
+        }
+    }
+
+    /// Starts the node on the given ledger storage, and renders the display if it is enabled.
+    async fn start_node<N: Network, C: ConsensusStorage<N>>(&mut self, log_receiver: Receiver<Vec<u8>>) {
+        // Parse the node from the configurations.
+        let node = self.parse_node::<N, C>().await.expect("Failed to parse the node");
+        // If the display is enabled, render the display.
+        if !self.nodisplay {
+            // Initialize the display.
+            Display::start(node, log_receiver).expect("Failed to initialize the display");
+        }
+    }
+
+    /// Loads the network profile, if one is given, and applies its settings.
+    /// The profile overrides the network ID and ports, and its peers are added to those given by flag.
+    fn parse_network_profile(&mut self) -> Result<()> {
+        let Some(path) = &self.network_profile else { return Ok(()) };
+        let profile = NetworkProfile::load(path)?;
+
+        if let Some(network) = profile.network {
+            self.network = network;
+        }
+        if let Some(port) = profile.node_port {
+            self.node.set_port(port);
+        }
+        if let Some(port) = profile.rest_port {
+            self.rest.set_port(port);
+        }
+        // Add the peers and validators of the profile.
+        for ip in &profile.peers {
+            self.peers = match self.peers.is_empty() {
+                true => ip.to_string(),
+                false => format!("{},{ip}", self.peers),
+            };
+        }
+        for ip in &profile.validators {
+            self.validators = match self.validators.is_empty() {
+                true => ip.to_string(),
+                false => format!("{},{ip}", self.validators),
+            };
+        }
+
+        self.profile = Some(profile);
+        Ok(())
+    }
+
     /// Returns the initial peer(s) to connect to, from the given configurations.
     fn parse_trusted_peers(&self) -> Result<Vec<SocketAddr>> {
-        match self.peers.is_empty() {
-            true => Ok(vec![]),
-            false => Ok(self
-                .peers
-                .split(',')
-                .flat_map(|ip| match ip.parse::<SocketAddr>() {
-                    Ok(ip) => Some(ip),
-                    Err(e) => {
-                        eprintln!("The IP supplied to --peers ('{ip}') is malformed: {e}");
-                        None
-                    }
-                })
-                .collect()),
+        Ok(self.parse_peers_with_priorities()?.into_iter().map(|(ip, _)| ip).collect())
+    }
+
+    /// Returns the priorities of the initial peer(s) that are annotated with one.
+    fn parse_peer_priorities(&self) -> Result<indexmap::IndexMap<SocketAddr, u8>> {
+        let peers = self.parse_peers_with_priorities()?;
+        Ok(peers.into_iter().filter_map(|(ip, priority)| Some((ip, priority?))).collect())
+    }
+
+    /// Returns the initial peer(s) to connect to, along with their priority, if they are annotated with one
+    /// as '<ip>:<port>@<priority>'. A malformed IP is skipped, while a malformed priority is an error.
+    fn parse_peers_with_priorities(&self) -> Result<Vec<(SocketAddr, Option<u8>)>> {
+        if self.peers.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut peers = Vec::new();
+        for entry in self.peers.split(',') {
+            let (ip, priority) = match entry.rsplit_once('@') {
+                Some((ip, priority)) => match priority.parse::<u8>() {
+                    Ok(priority) => (ip, Some(priority)),
+                    Err(e) => bail!("The priority supplied to --peers ('{entry}') is malformed (expected 0-255): {e}"),
+                },
+                None => (entry, None),
+            };
+            match ip.parse::<SocketAddr>() {
+                Ok(ip) => peers.push((ip, priority)),
+                Err(e) => eprintln!("The IP supplied to --peers ('{ip}') is malformed: {e}"),
+            }
         }
+        Ok(peers)
     }
 
     /// Returns the initial validator(s) to connect to, from the given configurations.
@@ -187,6 +538,105 @@ impl Start {
         }
     }
 
+    /// Returns the token required to access the REST admin endpoints, if one is set.
+    /// The '--admin-token' flag takes precedence over the `SNARKOS_ADMIN_TOKEN` environment variable.
+    fn parse_admin_token(&self) -> Option<String> {
+        self.parse_admin_token_with(std::env::var(ADMIN_TOKEN_ENV_VAR).ok())
+    }
+
+    /// Returns the token required to access the REST admin endpoints, if one is set,
+    /// given the value of the `SNARKOS_ADMIN_TOKEN` environment variable, if it is set.
+    fn parse_admin_token_with(&self, env_admin_token: Option<String>) -> Option<String> {
+        let (admin_token, source) = match (&self.admin_token, env_admin_token) {
+            (Some(admin_token), _) => (Some(admin_token.clone()), "the '--admin-token' flag".to_string()),
+            (None, Some(admin_token)) if !admin_token.is_empty() => {
+                (Some(admin_token), format!("the '{ADMIN_TOKEN_ENV_VAR}' environment variable"))
+            }
+            (None, _) => (None, "the default (none)".to_string()),
+        };
+        // Report the source of the admin token, without printing the token itself.
+        if self.verbosity >= 2 {
+            println!("🔑 Using the REST admin token from {source}\n");
+        }
+        admin_token
+    }
+
+    /// Returns the static token to accept for the JWT-protected REST endpoints, if one is set.
+    /// The token file may end with a newline, which is not part of the token.
+    fn parse_rest_token(&self) -> Result<Option<String>> {
+        match (&self.rest_token, &self.rest_token_file) {
+            (Some(rest_token), _) => Ok(Some(rest_token.clone())),
+            (None, Some(path)) => {
+                let rest_token = std::fs::read_to_string(path)
+                    .map_err(|e| anyhow!("Failed to read the REST token file '{}' - {e}", path.display()))?;
+                Ok(Some(rest_token.trim_end_matches(['\r', '\n']).to_string()))
+            }
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Ensures the REST server does not accept a well-known default credential (e.g. 'root' or 'pass') as one of the
+    /// given static tokens, while it is reachable from the network, unless '--rest-allow-default-credentials' is set.
+    fn check_default_credentials(&self, rest_ip: Option<SocketAddr>, static_tokens: &[(&str, String)]) -> Result<()> {
+        let Some(rest_ip) = rest_ip.filter(|rest_ip| !rest_ip.ip().is_loopback()) else { return Ok(()) };
+        for (name, token) in static_tokens {
+            if !DEFAULT_CREDENTIALS.contains(&token.to_lowercase().as_str()) {
+                continue;
+            }
+            let message = format!(
+                "The REST server at '{rest_ip}' is reachable from the network, while {name} is a well-known \
+                 default credential ('{token}')"
+            );
+            match self.rest_allow_default_credentials {
+                true => eprintln!("{}\n", format!("⚠️  {message}.").yellow().bold()),
+                false => bail!(
+                    "{message}. Set a strong token, bind the REST server to localhost (e.g. '--rest 127.0.0.1:3033'), \
+                     or pass '--rest-allow-default-credentials' to start anyway"
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a warning if the block explorer is served, while the '--rest-cors' origins do not include any
+    /// origin on the port of the explorer, as the browser then blocks the queries of the explorer page.
+    fn explorer_cors_warning(&self) -> Option<String> {
+        let (explorer_ip, origins) = (self.explorer?, self.rest_cors.as_deref()?);
+        let suffix = format!(":{}", explorer_ip.port());
+        let origins = origins.split(',').map(str::trim).collect::<Vec<_>>();
+        if origins.contains(&"*") || origins.iter().any(|origin| origin.ends_with(&suffix)) {
+            return None;
+        }
+        Some(format!(
+            "⚠️  The '--rest-cors' origins do not allow the block explorer at '{explorer_ip}' to query the REST \
+             server.\n⚠️  Add the origin of the explorer (e.g. 'http://localhost{suffix}') to '--rest-cors'."
+        ))
+    }
+
+    /// Returns a warning if the REST server is reachable from the network over plaintext HTTP while it accepts
+    /// the given static tokens, as anyone on the network path can then read the tokens from the requests.
+    fn rest_credentials_warning(&self, rest_ip: Option<SocketAddr>, static_tokens: &[&str]) -> Option<String> {
+        let rest_ip = rest_ip?;
+        // Note: A TLS certificate is only accepted along with its key, in which case the REST server serves HTTPS.
+        if static_tokens.is_empty() || rest_ip.ip().is_loopback() || self.rest_tls_cert.is_some() {
+            return None;
+        }
+        Some(
+            [
+                format!(
+                    "⚠️  The REST server at '{rest_ip}' is reachable from the network over plaintext HTTP, \
+                     while it accepts {}.",
+                    static_tokens.join(" and ")
+                ),
+                "⚠️  Anyone on the network path can read the tokens from the requests.".to_owned(),
+                "⚠️  Bind the REST server to localhost (e.g. '--rest 127.0.0.1:3033'), \
+                 or serve HTTPS with '--rest-tls-cert' and '--rest-tls-key'."
+                    .to_owned(),
+            ]
+            .join("\n"),
+        )
+    }
+
     /// Returns the CDN to prefetch initial blocks from, from the given configurations.
     fn parse_cdn(&self) -> Option<String> {
         // Determine if the node type is not declared.
@@ -212,9 +662,18 @@ impl Start {
         match self.dev {
             None => match (&self.private_key, &self.private_key_file) {
                 // Parse the private key directly.
-                (Some(private_key), None) => Account::from_str(private_key.trim()),
+                // Note: The private key is not included in the error, as it is a secret.
+                (Some(private_key), None) => Account::from_str(private_key.trim())
+                    .map_err(|error| anyhow!("Invalid Aleo private key for '--private-key': {error}")),
                 // Parse the private key from a file.
-                (None, Some(path)) => Account::from_str(std::fs::read_to_string(path)?.trim()),
+                (None, Some(path)) => {
+                    let private_key = std::fs::read_to_string(path).map_err(|error| {
+                        anyhow!("Failed to read '--private-key-file' ({}): {error}", path.display())
+                    })?;
+                    Account::from_str(private_key.trim()).map_err(|error| {
+                        anyhow!("Invalid Aleo private key in '--private-key-file' ({}): {error}", path.display())
+                    })
+                }
                 // Ensure the private key is provided to the CLI, except for clients or nodes in development mode.
                 (None, None) => match self.client {
                     true => Account::new(&mut rand::thread_rng()),
@@ -351,29 +810,49 @@ impl Start {
                 eprintln!("The '--dev-num-validators' flag is ignored because '--dev' is not set");
             }
 
-            Block::from_bytes_le(N::genesis_bytes())
+            // Load the genesis block of the network profile, if one is given.
+            let genesis = match &self.profile {
+                Some(profile) => profile.load_genesis::<N>()?,
+                None => None,
+            };
+            let genesis = match genesis {
+                Some(genesis) => genesis,
+                None => Block::from_bytes_le(N::genesis_bytes())?,
+            };
+            // Ensure the genesis block matches the network profile.
+            if let Some(profile) = &self.profile {
+                profile.check_genesis(&genesis)?;
+            }
+            Ok(genesis)
         }
     }
 
     /// Returns the node type, from the given configurations.
-    const fn parse_node_type(&self) -> NodeType {
-        if self.validator {
-            NodeType::Validator
-        } else if self.prover {
-            NodeType::Prover
-        } else {
-            NodeType::Client
+    /// Returns an error if more than one node type is specified.
+    fn parse_node_type(&self) -> Result<NodeType> {
+        match (self.validator, self.prover, self.client) {
+            (true, true, true) => bail!("Cannot specify all of '--validator', '--prover', and '--client'"),
+            (true, true, false) => bail!("Cannot specify both '--prover' and '--validator'"),
+            (true, false, true) => bail!("Cannot specify both '--client' and '--validator'"),
+            (false, true, true) => bail!("Cannot specify both '--client' and '--prover'"),
+            (true, false, false) => Ok(NodeType::Validator),
+            (false, true, false) => Ok(NodeType::Prover),
+            (false, false, _) => Ok(NodeType::Client),
         }
     }
 
     /// Returns the node type corresponding to the given configurations.
     #[rustfmt::skip]
-    async fn parse_node<N: Network>(&mut self) -> Result<Node<N>> {
-        // Print the welcome.
-        println!("{}", crate::helpers::welcome_message());
+    async fn parse_node<N: Network, C: ConsensusStorage<N>>(&mut self) -> Result<Node<N, C>> {
+        // Print the welcome, unless the output is not a terminal (e.g. in container logs).
+        if std::io::stdout().is_tty() {
+            println!("{}", crate::helpers::welcome_message());
+        }
 
         // Parse the trusted peers to connect to.
         let mut trusted_peers = self.parse_trusted_peers()?;
+        // Parse the priorities of the trusted peers, to prefer the ones of a higher priority.
+        let peer_priorities = self.parse_peer_priorities()?;
         // Parse the trusted validators to connect to.
         let mut trusted_validators = self.parse_trusted_validators()?;
         // Parse the development configurations.
@@ -381,13 +860,74 @@ impl Start {
 
         // Parse the CDN.
         let cdn = self.parse_cdn();
+        // Parse the verification of blocks imported from the CDN.
+        let cdn_verification = CdnVerification::from_str(&self.verify_on_import)?;
 
         // Parse the genesis block.
         let genesis = self.parse_genesis::<N>()?;
         // Parse the private key of the node.
         let account = self.parse_private_key::<N>()?;
         // Parse the node type.
-        let node_type = self.parse_node_type();
+        let node_type = self.parse_node_type()?;
+
+        // Parse the network secret.
+        let network_secret = match self.network_secret.as_deref().map(str::trim) {
+            Some("") => bail!("The '--network-secret' argument must not be empty"),
+            secret => secret.map(str::to_string),
+        };
+
+        // Ensure the height sanity threshold is nonzero.
+        if self.height_sanity_threshold == Some(0) {
+            bail!("The '--height-sanity-threshold' argument must be greater than zero");
+        }
+
+        // Ensure the sync window is nonzero.
+        if self.sync_window == Some(0) {
+            bail!("The '--sync-window' argument must be greater than zero");
+        }
+
+        // Ensure the block request timeout is nonzero.
+        if self.block_request_timeout == Some(0) {
+            bail!("The '--block-request-timeout' argument must be greater than zero");
+        }
+
+        // Ensure the peer I/O timeout exceeds the ping interval, as an idle but healthy peer is only heard from
+        // once per ping interval, and would otherwise be dropped.
+        if let Some(peer_io_timeout) = self.peer_io_timeout {
+            ensure!(
+                peer_io_timeout > PING_SLEEP_IN_SECS,
+                "The '--peer-io-timeout' argument must exceed the {PING_SLEEP_IN_SECS} second ping interval"
+            );
+        }
+
+        // Ensure the additional node server address is an IPv6 address.
+        if let Some(node_ipv6) = self.node_ipv6 {
+            ensure!(node_ipv6.is_ipv6(), "The '--node-ipv6' argument must be an IPv6 address (found {node_ipv6})");
+        }
+
+        // Parse the per-type and per-direction connection limits.
+        let connection_limits = ConnectionLimits {
+            max_validators: self.max_validator_peers,
+            max_provers: self.max_prover_peers,
+            max_clients: self.max_client_peers,
+            max_inbound: self.max_inbound.or(self.max_peers),
+            max_outbound: self.max_outbound.or(self.max_peers),
+            reject_while_syncing: self.reject_while_syncing,
+            io_timeout_secs: self.peer_io_timeout,
+            extra_listener: self.node_ipv6,
+            // Note: A separate IPv6 listener must not also accept IPv4, or it would clash with the IPv4 listener.
+            ipv6_only: self.ipv6_only.or(self.node_ipv6.map(|_| true)),
+            serve_rate_limit: self.serve_rate_limit,
+            max_message_size: self.max_message_size,
+        };
+
+        // Parse the reconnection backoff.
+        let reconnect_backoff = ReconnectBackoff::new(
+            Duration::from_secs(self.reconnect_initial),
+            self.reconnect_multiplier,
+            Duration::from_secs(self.reconnect_max),
+        )?
+        .with_max_attempts(self.connect_retries);
 
         // Parse the REST IP.
         let rest_ip = match self.norest {
@@ -420,21 +960,211 @@ impl Start {
             }
         }
 
+        // If a metrics address or file is given, initialize the metrics, serving them at the address if given.
+        if self.metrics.is_some() || self.metrics_file.is_some() {
+            #[cfg(feature = "metrics")]
+            snarkos_node::metrics::initialize(self.metrics);
+            #[cfg(not(feature = "metrics"))]
+            bail!("Cannot record metrics, as snarkOS was built without the 'metrics' feature");
+        }
+        // If a metrics file is given, periodically append the key metrics to it.
+        #[cfg(feature = "metrics")]
+        if let Some(metrics_file) = &self.metrics_file {
+            ensure!(self.metrics_interval > 0, "The '--metrics-interval' argument must be at least 1 second");
+            let format = crate::helpers::MetricsFormat::from_str(&self.metrics_format)?;
+            let interval = Duration::from_secs(self.metrics_interval);
+            crate::helpers::spawn_metrics_file(metrics_file.clone(), self.log_keep, format, interval)?;
+        }
+        // Parse the REST configuration, where each limit is only applied if it is given.
+        let mut rest_config = RestConfig {
+            // Limit the requests per second from each IP.
+            rate_limit: self.rest_rate_limit,
+            // Limit the number of requests in each batch.
+            max_batch_size: self.rest_max_batch,
+            // Only allow cross-origin requests to the REST server from the given origins.
+            cors_origins: self.rest_cors.as_deref().map(CorsOrigins::from_str).transpose()?,
+            // Serve the REST endpoints over HTTPS, with the given TLS certificate and key.
+            tls: match (&self.rest_tls_cert, &self.rest_tls_key) {
+                (Some(cert_path), Some(key_path)) => Some((cert_path.clone(), key_path.clone())),
+                (Some(_), None) => bail!("The '--rest-tls-cert' argument requires '--rest-tls-key'"),
+                (None, Some(_)) => bail!("The '--rest-tls-key' argument requires '--rest-tls-cert'"),
+                (None, None) => None,
+            },
+            ..Default::default()
+        };
+        // If an admin token is given, require it for the REST admin endpoints.
+        let mut static_tokens = vec![];
+        if let Some(admin_token) = self.parse_admin_token() {
+            static_tokens.push(("the admin token", admin_token.clone()));
+            rest_config.admin_token = Some(admin_token);
+        }
+        // If a REST token is given, also accept it for the JWT-protected REST endpoints.
+        if let Some(rest_token) = self.parse_rest_token()? {
+            static_tokens.push(("the '--rest-token'", rest_token.clone()));
+            rest_config.rest_token = Some(rest_token);
+        }
+        // If the ledger is stored on disk, report its storage on the REST storage endpoint.
+        if self.storage == "rocksdb" {
+            rest_config.storage_path = Some(aleo_std::aleo_ledger_dir(N::ID, self.dev));
+        }
+        // Ensure the REST configuration is well-formed, before the node starts.
+        rest_config.check()?;
+        // Refuse to expose a REST server that accepts a well-known default credential to the network.
+        self.check_default_credentials(rest_ip, &static_tokens)?;
+        // Warn if the static tokens would be sent in plaintext to a REST server that is reachable from the network.
+        let token_names = static_tokens.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        if let Some(warning) = self.rest_credentials_warning(rest_ip, &token_names) {
+            crate::helpers::warn_or_fail(warning, self.fast_fail)?;
+        }
+        // If crash tracking is enabled, record this run, and report any unclean shutdown of the previous run.
+        if let Some(path) = &self.crash_info {
+            self.initialize_crash_info(path)?;
+        }
+        // Parse the action taken when a background task of the node fails.
+        let task_failure_policies = TaskFailurePolicies::from_str(&self.on_task_failure)?;
+        // Parse the action taken when the node detects it is on a minority fork.
+        let minority_fork_policy = MinorityForkPolicy::from_str(&self.on_minority_fork)?;
+        // If a production grace period is given, delay proposing batches until the validator has warmed up.
+        let production_grace = match self.production_grace {
+            Some(secs) => ProductionGrace { duration: Duration::from_secs(secs), min_peers: self.min_peers },
+            None => ProductionGrace::default(),
+        };
+        // If a block stream is given, append each newly-applied block to it.
+        let block_stream = self.block_stream.clone().map(BlockStream::new).transpose()?;
+
         // If the node is a validator, check if the open files limit is lower than recommended.
         #[cfg(target_family = "unix")]
         if node_type.is_validator() {
-            crate::helpers::check_open_files_limit(RECOMMENDED_MIN_NOFILES_LIMIT);
+            crate::helpers::check_open_files_limit(RECOMMENDED_MIN_NOFILES_LIMIT, self.fast_fail)?;
         }
         // Check if the machine meets the minimum requirements for a validator.
-        crate::helpers::check_validator_machine(node_type);
+        crate::helpers::check_validator_machine(node_type, self.fast_fail)?;
+
+        // Ensure the block explorer is able to query the REST server.
+        if self.explorer.is_some() {
+            ensure!(rest_ip.is_some(), "The '--explorer' flag requires the REST server");
+            ensure!(!node_type.is_prover(), "The '--explorer' flag is not available for provers");
+        }
+        if let Some(warning) = self.explorer_cors_warning() {
+            crate::helpers::warn_or_fail(warning, self.fast_fail)?;
+        }
+
+        // Ensure the limit on the number of running router tasks is well-formed, before the node spawns any task.
+        match self.max_tasks {
+            Some(0) => bail!("The '--max-tasks' argument must be greater than zero"),
+            None if self.refuse_excess_tasks => bail!("The '--refuse-excess-tasks' flag requires '--max-tasks'"),
+            _ => (),
+        }
+
+        // Initialize the node configuration.
+        let config = NodeConfig {
+            router: RouterConfig {
+                network_secret,
+                connection_limits,
+                reconnect_backoff,
+                peer_priorities,
+                // If set, limit the number of running router tasks.
+                max_tasks: self.max_tasks,
+                refuse_excess_tasks: self.refuse_excess_tasks,
+            },
+            sync: BlockSyncConfig {
+                height_sanity_threshold: self.height_sanity_threshold,
+                sync_window: self.sync_window,
+                block_request_timeout: self.block_request_timeout,
+                minority_fork_policy,
+                // If validation profiling is enabled, record the timings of each block validation phase.
+                validation_histogram: self.validation_histogram.then(Default::default),
+                block_stream,
+            },
+            rest: rest_config,
+            cdn_verification,
+            crash_info_path: self.crash_info.clone(),
+            task_failure_policies,
+            shutdown_timeout: Some(Duration::from_secs(self.shutdown_timeout)),
+            production_grace,
+            verification_cache_size: Some(self.verification_cache),
+        };
 
         // Initialize the node.
         let bft_ip = if self.dev.is_some() { self.bft } else { None };
-        match node_type {
-            NodeType::Validator => Node::new_validator(self.node, rest_ip, bft_ip, account, &trusted_peers, &trusted_validators, genesis, cdn, self.dev).await,
-            NodeType::Prover => Node::new_prover(self.node, account, &trusted_peers, genesis, self.dev).await,
-            NodeType::Client => Node::new_client(self.node, rest_ip, account, &trusted_peers, genesis, cdn, self.dev).await,
+        let node = match node_type {
+            NodeType::Validator => Node::new_validator(self.node, rest_ip, bft_ip, account, &trusted_peers, &trusted_validators, genesis, cdn, self.dev, config).await,
+            NodeType::Prover => Node::new_prover(self.node, account, &trusted_peers, genesis, self.dev, config).await,
+            NodeType::Client => Node::new_client(self.node, rest_ip, account, &trusted_peers, genesis, cdn, self.dev, config).await,
+        }?;
+
+        // If set, serve the block explorer, which queries the REST server.
+        if let (Some(explorer_ip), Some(rest_ip)) = (self.explorer, rest_ip) {
+            println!("🔭 Starting the block explorer at {}.\n", explorer_ip.to_string().bold());
+            snarkos_node_rest::start_explorer(explorer_ip, rest_ip);
+        }
+        // If set, pause the relay of unconfirmed solutions and transactions while the node is syncing.
+        if self.relay_after_sync {
+            node.relay_after_sync();
+        }
+        // If set, pause production and relay if the network upgrades past this node.
+        if self.upgrade_awareness {
+            node.upgrade_awareness();
+        }
+        // If set, ban the peers that send a block which fails verification.
+        if self.ban_on_invalid_block {
+            node.ban_on_invalid_block();
+        }
+        // If message recording is enabled, record all inbound messages.
+        if let Some(path) = &self.record_messages {
+            node.record_messages(path)?;
         }
+        // If a message recording is given, replay it into the node.
+        if let Some(path) = &self.replay_messages {
+            let num_handled = node.replay_messages(path).await?;
+            println!("🔁 Replayed {num_handled} recorded messages from '{}'.\n", path.display());
+        }
+        // If peers are given, ensure the node is not isolated once the initial connection attempts are done.
+        if !self.peers.is_empty() {
+            self.check_isolation(&node).await?;
+        }
+
+        Ok(node)
+    }
+
+    /// Enables crash tracking in the file at the given path, and records panics as crash reasons.
+    fn initialize_crash_info(&self, path: &Path) -> Result<()> {
+        let crash_info = snarkos_node_rest::initialize_crash_info(path)?;
+        if crash_info.num_unclean_shutdowns > 0 {
+            let reason = crash_info.last_reason.as_deref().unwrap_or_default();
+            let message = format!(
+                "⚠️  This node has shut down uncleanly {} time(s) - the last reason was: {reason}",
+                crash_info.num_unclean_shutdowns
+            );
+            eprintln!("{}\n", message.yellow().bold());
+        }
+
+        // Record the panic message, before running the default panic hook.
+        let default_hook = std::panic::take_hook();
+        let path = path.to_path_buf();
+        std::panic::set_hook(Box::new(move |panic_info| {
+            snarkos_node_rest::record_crash_reason(&path, format!("panic: {panic_info}"));
+            default_hook(panic_info);
+        }));
+        Ok(())
+    }
+
+    /// Waits for the initial connection attempts to the trusted peers, and warns (or fails, if '--fast-fail' is set)
+    /// if none of them succeeded, and there are no other peers to connect to.
+    async fn check_isolation<N: Network, C: ConsensusStorage<N>>(&self, node: &Node<N, C>) -> Result<()> {
+        let deadline = Instant::now() + Duration::from_secs(STARTUP_CONNECT_TIMEOUT_IN_SECS);
+        while node.is_isolated() {
+            if Instant::now() >= deadline {
+                let message = format!(
+                    "⚠️  None of the peers given to '--peers' ({}) could be connected to, and there are no other \
+                     peers to discover. The node is isolated - please check the peer addresses.",
+                    self.peers
+                );
+                return crate::helpers::warn_or_fail(message, self.fast_fail);
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+        Ok(())
     }
 
     /// Returns a runtime for the node.
@@ -564,6 +1294,27 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_parse_peer_priorities() {
+        let peers = "1.2.3.4:5@10,6.7.8.9:0,[::1]:4130@0";
+        let config = Start::try_parse_from(["snarkos", "--peers", peers].iter()).unwrap();
+        assert_eq!(config.parse_trusted_peers().unwrap(), vec![
+            SocketAddr::from_str("1.2.3.4:5").unwrap(),
+            SocketAddr::from_str("6.7.8.9:0").unwrap(),
+            SocketAddr::from_str("[::1]:4130").unwrap()
+        ]);
+        let priorities = config.parse_peer_priorities().unwrap();
+        assert_eq!(priorities.len(), 2);
+        assert_eq!(priorities[&SocketAddr::from_str("1.2.3.4:5").unwrap()], 10);
+        assert_eq!(priorities[&SocketAddr::from_str("[::1]:4130").unwrap()], 0);
+
+        // Ensure malformed priorities are rejected.
+        for peers in ["1.2.3.4:5@", "1.2.3.4:5@high", "1.2.3.4:5@256", "1.2.3.4:5@-1"] {
+            let config = Start::try_parse_from(["snarkos", "--peers", peers].iter()).unwrap();
+            assert!(config.parse_trusted_peers().is_err());
+        }
+    }
+
     #[test]
     fn test_parse_trusted_validators() {
         let config = Start::try_parse_from(["snarkos", "--validators", ""].iter()).unwrap();
@@ -582,6 +1333,152 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_check_network_and_storage() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert!(config.check_network_and_storage().is_ok());
+
+        // Ensure an unsupported network ID is rejected, instead of panicking on startup.
+        let config = Start::try_parse_from(["snarkos", "--network", "2"].iter()).unwrap();
+        assert!(config.check_network_and_storage().is_err());
+
+        // Ensure an invalid storage backend is rejected.
+        let config = Start::try_parse_from(["snarkos", "--storage", "sled"].iter()).unwrap();
+        assert!(config.check_network_and_storage().is_err());
+    }
+
+    #[test]
+    fn test_check_private_key() {
+        let private_key = PrivateKey::<CurrentNetwork>::new(&mut rand::thread_rng()).unwrap().to_string();
+        let config = Start::try_parse_from(["snarkos", "--validator", "--private-key", &private_key].iter()).unwrap();
+        assert!(config.check_private_key::<CurrentNetwork>().is_ok());
+
+        // Ensure a malformed private key is rejected, naming the flag.
+        let config = Start::try_parse_from(["snarkos", "--validator", "--private-key", "APrivateKey1"].iter()).unwrap();
+        let error = config.check_private_key::<CurrentNetwork>().unwrap_err().to_string();
+        assert!(error.starts_with("Invalid Aleo private key for '--private-key'"));
+
+        // Ensure a missing private key is rejected for non-clients.
+        let config = Start::try_parse_from(["snarkos", "--prover"].iter()).unwrap();
+        assert!(config.check_private_key::<CurrentNetwork>().is_err());
+        let config = Start::try_parse_from(["snarkos", "--client"].iter()).unwrap();
+        assert!(config.check_private_key::<CurrentNetwork>().is_ok());
+    }
+
+    #[test]
+    fn test_parse_node_type() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert_eq!(config.parse_node_type().unwrap(), NodeType::Client);
+        let config = Start::try_parse_from(["snarkos", "--validator"].iter()).unwrap();
+        assert_eq!(config.parse_node_type().unwrap(), NodeType::Validator);
+        let config = Start::try_parse_from(["snarkos", "--prover"].iter()).unwrap();
+        assert_eq!(config.parse_node_type().unwrap(), NodeType::Prover);
+
+        // Ensure conflicting node types are rejected, naming the conflicting flags.
+        let config = Start::try_parse_from(["snarkos", "--prover", "--validator"].iter()).unwrap();
+        let error = config.parse_node_type().unwrap_err().to_string();
+        assert_eq!(error, "Cannot specify both '--prover' and '--validator'");
+        let config = Start::try_parse_from(["snarkos", "--client", "--prover"].iter()).unwrap();
+        assert!(config.parse_node_type().is_err());
+    }
+
+    #[test]
+    fn test_parse_admin_token() {
+        let from_env = || Some("from-env".to_string());
+
+        // The flag takes precedence over the environment variable.
+        let config = Start::try_parse_from(["snarkos", "--client", "--admin-token", "from-flag"].iter()).unwrap();
+        assert_eq!(config.parse_admin_token_with(from_env()), Some("from-flag".to_string()));
+        // The environment variable is used if the flag is not set.
+        let config = Start::try_parse_from(["snarkos", "--client"].iter()).unwrap();
+        assert_eq!(config.parse_admin_token_with(from_env()), Some("from-env".to_string()));
+
+        // An empty environment variable is ignored.
+        assert_eq!(config.parse_admin_token_with(Some(String::new())), None);
+        assert_eq!(config.parse_admin_token_with(None), None);
+    }
+
+    #[test]
+    fn test_parse_rest_token() {
+        let config = Start::try_parse_from(["snarkos", "--client", "--rest-token", "from-flag"].iter()).unwrap();
+        assert_eq!(config.parse_rest_token().unwrap(), Some("from-flag".to_string()));
+        let config = Start::try_parse_from(["snarkos", "--client"].iter()).unwrap();
+        assert_eq!(config.parse_rest_token().unwrap(), None);
+
+        // The token file may end with a newline.
+        let path = std::env::temp_dir().join(format!("snarkos-test-rest-token-{}", std::process::id()));
+        std::fs::write(&path, "from-file\n").unwrap();
+        let config = Start::try_parse_from(["snarkos", "--client", "--rest-token-file", path.to_str().unwrap()].iter());
+        assert_eq!(config.unwrap().parse_rest_token().unwrap(), Some("from-file".to_string()));
+        std::fs::remove_file(&path).unwrap();
+
+        // A missing token file is an error.
+        let config = Start::try_parse_from(["snarkos", "--client", "--rest-token-file", path.to_str().unwrap()].iter());
+        assert!(config.unwrap().parse_rest_token().is_err());
+        // The token and the token file are mutually exclusive.
+        let args = ["snarkos", "--client", "--rest-token", "a", "--rest-token-file", "b"];
+        assert!(Start::try_parse_from(args.iter()).is_err());
+    }
+
+    #[test]
+    fn test_check_default_credentials() {
+        let public_ip = Some(SocketAddr::from_str("0.0.0.0:3033").unwrap());
+        let local_ip = Some(SocketAddr::from_str("127.0.0.1:3033").unwrap());
+        let default_token = [("the admin token", "Pass".to_string())];
+        let strong_token = [("the admin token", "3f9a1c7e5b2d4a60".to_string())];
+
+        // Ensure a publicly reachable REST server with a default credential is refused.
+        let config = Start::try_parse_from(["snarkos", "--client"].iter()).unwrap();
+        let error = config.check_default_credentials(public_ip, &default_token).unwrap_err().to_string();
+        assert!(error.contains("well-known default credential ('Pass')"));
+        // Ensure a strong token, a local REST server, or no REST server is accepted.
+        assert!(config.check_default_credentials(public_ip, &strong_token).is_ok());
+        assert!(config.check_default_credentials(local_ip, &default_token).is_ok());
+        assert!(config.check_default_credentials(None, &default_token).is_ok());
+
+        // Ensure the refusal can be overridden.
+        let args = ["snarkos", "--client", "--rest-allow-default-credentials"];
+        let config = Start::try_parse_from(args.iter()).unwrap();
+        assert!(config.check_default_credentials(public_ip, &default_token).is_ok());
+    }
+
+    #[test]
+    fn test_explorer_cors_warning() {
+        let parse = |args: &[&str]| Start::try_parse_from(["snarkos", "--client"].iter().chain(args)).unwrap();
+
+        // Ensure the origins that do not allow the explorer are warned about.
+        let config = parse(&["--explorer", "0.0.0.0:3000", "--rest-cors", "https://example.com"]);
+        assert!(config.explorer_cors_warning().unwrap().contains("'0.0.0.0:3000'"));
+        // Ensure no warning is given if the explorer is allowed, any origin is allowed, or either flag is absent.
+        let origins = "https://example.com, http://localhost:3000";
+        let config = parse(&["--explorer", "0.0.0.0:3000", "--rest-cors", origins]);
+        assert!(config.explorer_cors_warning().is_none());
+        assert!(parse(&["--explorer", "0.0.0.0:3000", "--rest-cors", "*"]).explorer_cors_warning().is_none());
+        assert!(parse(&["--explorer", "0.0.0.0:3000"]).explorer_cors_warning().is_none());
+        assert!(parse(&["--rest-cors", "https://example.com"]).explorer_cors_warning().is_none());
+    }
+
+    #[test]
+    fn test_rest_credentials_warning() {
+        let config = Start::try_parse_from(["snarkos", "--client"].iter()).unwrap();
+        let public_ip = Some(SocketAddr::from_str("0.0.0.0:3033").unwrap());
+        let local_ip = Some(SocketAddr::from_str("127.0.0.1:3033").unwrap());
+
+        // Ensure a publicly reachable plaintext REST server with a static token is warned about.
+        let warning = config.rest_credentials_warning(public_ip, &["the admin token"]).unwrap();
+        assert!(warning.contains("'0.0.0.0:3033'"));
+        assert!(warning.contains("the admin token"));
+        // Ensure no warning is given without a static token, on localhost, or without the REST server.
+        assert!(config.rest_credentials_warning(public_ip, &[]).is_none());
+        assert!(config.rest_credentials_warning(local_ip, &["the admin token"]).is_none());
+        assert!(config.rest_credentials_warning(None, &["the admin token"]).is_none());
+
+        // Ensure no warning is given if the REST server serves HTTPS.
+        let args = ["snarkos", "--client", "--rest-tls-cert", "cert.pem", "--rest-tls-key", "key.pem"];
+        let config = Start::try_parse_from(args.iter()).unwrap();
+        assert!(config.rest_credentials_warning(public_ip, &["the admin token"]).is_none());
+    }
+
     #[test]
     fn test_parse_cdn() {
         // Validator (Prod)
@@ -771,6 +1668,8 @@ mod tests {
             "IP1,IP2,IP3",
             "--rest",
             "127.0.0.1:3033",
+            "--storage",
+            "memory",
         ];
         let cli = CLI::parse_from(arg_vec);
 
@@ -784,6 +1683,7 @@ mod tests {
             assert_eq!(start.network, 3);
             assert_eq!(start.peers, "IP1,IP2,IP3");
             assert_eq!(start.validators, "IP1,IP2,IP3");
+            assert_eq!(start.storage, "memory");
         } else {
             panic!("Unexpected result of clap parsing!");
         }
@@ -0,0 +1,98 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{block::Block, store::helpers::memory::ConsensusMemory, Ledger, Network, Testnet3};
+
+use anyhow::{bail, ensure, Result};
+use clap::Parser;
+use colored::Colorize;
+use std::path::PathBuf;
+
+type CurrentNetwork = Testnet3;
+
+/// Validates a block file against an in-memory ledger, without persisting anything.
+#[derive(Debug, Parser)]
+pub struct ValidateBlocks {
+    /// Specify the path to the block file (a bincode-encoded list of blocks, as served by the CDN)
+    pub file: PathBuf,
+}
+
+impl ValidateBlocks {
+    /// Validates the blocks in the given file.
+    pub fn parse(self) -> Result<String> {
+        // Read the block file.
+        let bytes = match std::fs::read(&self.file) {
+            Ok(bytes) => bytes,
+            Err(error) => bail!("Failed to read the block file '{}' - {error}", self.file.display()),
+        };
+        // Deserialize the blocks.
+        let blocks: Vec<Block<CurrentNetwork>> = match bincode::deserialize(&bytes) {
+            Ok(blocks) => blocks,
+            Err(error) => bail!("Failed to deserialize the block file '{}' - {error}", self.file.display()),
+        };
+
+        Self::validate(blocks)
+    }
+
+    /// Validates the given blocks in order, starting from the genesis block.
+    fn validate<N: Network>(blocks: Vec<Block<N>>) -> Result<String> {
+        // Ensure the block file starts at the genesis block.
+        let mut blocks = blocks.into_iter();
+        let genesis = match blocks.next() {
+            Some(genesis) => genesis,
+            None => bail!("The block file is empty"),
+        };
+        ensure!(genesis.height() == 0, "The block file must start at genesis (found block {})", genesis.height());
+
+        // Initialize an in-memory ledger from the genesis block.
+        let ledger = Ledger::<N, ConsensusMemory<N>>::load(genesis, None)?;
+
+        let mut num_valid = 1;
+        let mut failure = None;
+        for block in blocks.by_ref() {
+            // Check the block against the in-memory ledger, and advance on success.
+            let result = ledger
+                .check_next_block(&block, &mut rand::thread_rng())
+                .and_then(|_| ledger.advance_to_next_block(&block));
+            match result {
+                Ok(()) => num_valid += 1,
+                Err(error) => {
+                    failure = Some(format!("Block {} ({}) is invalid - {error}", block.height(), block.hash()));
+                    // The remaining blocks can not be validated without this block.
+                    break;
+                }
+            }
+        }
+        // Count the blocks that were not validated.
+        let num_unchecked = blocks.count();
+
+        match failure {
+            None => Ok(format!("✅ Validated {num_valid} blocks")),
+            Some(failure) => bail!(
+                "Validated {num_valid} blocks before a failure ({num_unchecked} remaining blocks were not checked)\n{}",
+                failure.dimmed()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_empty() {
+        assert!(ValidateBlocks::validate::<CurrentNetwork>(vec![]).is_err());
+    }
+}
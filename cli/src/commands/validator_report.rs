@@ -0,0 +1,153 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{
+    block::{Authority, Block, Ratify},
+    Address,
+    Network,
+    Testnet3,
+};
+
+use anyhow::{bail, ensure, Result};
+use clap::Parser;
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    str::FromStr,
+};
+
+type CurrentNetwork = Testnet3;
+
+/// The header row of the validator report.
+const CSV_HEADER: &str = "height,timestamp,reward,fees";
+
+/// Writes the block production history of a validator to a CSV file, reading the ledger without modifying it.
+#[derive(Debug, Parser)]
+pub struct ValidatorReport {
+    /// Specify the address of the validator
+    #[clap(long)]
+    pub address: String,
+    /// Specify the first block height to scan
+    #[clap(long, default_value = "0")]
+    pub from: u32,
+    /// Specify the last block height to scan (default: the latest block height)
+    #[clap(long)]
+    pub to: Option<u32>,
+    /// Specify the path to the output CSV file
+    #[clap(long)]
+    pub out: PathBuf,
+    /// Enables development mode, specify the unique ID of the node whose ledger to read
+    #[clap(long)]
+    pub dev: Option<u16>,
+}
+
+impl ValidatorReport {
+    /// Scans the ledger and writes the validator report.
+    pub fn parse(self) -> Result<String> {
+        let address = match Address::<CurrentNetwork>::from_str(&self.address) {
+            Ok(address) => address,
+            Err(error) => bail!("Invalid validator address '{}' - {error}", self.address),
+        };
+
+        // Open the ledger storage.
+        let store = crate::helpers::open_ledger_store::<CurrentNetwork>(self.dev)?;
+        let block_store = store.block_store();
+        let Some(latest_height) = block_store.max_height() else {
+            bail!("The ledger is empty");
+        };
+
+        // Determine the range of blocks to scan.
+        let end_height = self.to.unwrap_or(latest_height);
+        ensure!(end_height <= latest_height, "The '--to' height exceeds the latest height ({latest_height})");
+        ensure!(self.from <= end_height, "The given range is invalid (from = {}, to = {end_height})", self.from);
+        let total_blocks = end_height - self.from + 1;
+
+        let mut writer = BufWriter::new(File::create(&self.out)?);
+        writeln!(writer, "{CSV_HEADER}")?;
+
+        let mut num_produced = 0;
+        for height in self.from..=end_height {
+            // Load the block.
+            let Some(block_hash) = block_store.get_block_hash(height)? else {
+                bail!("Missing the block hash for height {height}");
+            };
+            let Some(block) = block_store.get_block(&block_hash)? else {
+                bail!("Missing block {height} ({block_hash})");
+            };
+            // Write a row for each block produced by the validator.
+            if Self::producer(&block) == address {
+                writeln!(writer, "{}", Self::to_csv_row(&block)?)?;
+                num_produced += 1;
+            }
+
+            // Log the progress.
+            let num_scanned = height - self.from + 1;
+            if num_scanned % 1000 == 0 || num_scanned == total_blocks {
+                let percentage_complete = num_scanned as f64 * 100.0 / total_blocks as f64;
+                print!("\rScanning {total_blocks} blocks ({percentage_complete:.2}% complete)...");
+                std::io::stdout().flush()?;
+            }
+        }
+        writer.flush()?;
+        println!();
+
+        Ok(format!("✅ Wrote {num_produced} produced blocks to '{}'", self.out.display()))
+    }
+
+    /// Returns the address of the validator that produced the given block.
+    fn producer<N: Network>(block: &Block<N>) -> Address<N> {
+        match block.authority() {
+            Authority::Beacon(signature) => signature.to_address(),
+            Authority::Quorum(subdag) => subdag.leader_certificate().author(),
+        }
+    }
+
+    /// Returns the CSV row for the given block.
+    fn to_csv_row<N: Network>(block: &Block<N>) -> Result<String> {
+        // Sum the block rewards in the block.
+        let reward: u64 = block
+            .ratifications()
+            .iter()
+            .map(|ratify| match ratify {
+                Ratify::BlockReward(amount) => *amount,
+                _ => 0,
+            })
+            .sum();
+        // Sum the fees of the transactions in the block.
+        let mut fees = 0u64;
+        for transaction in block.transactions().iter() {
+            fees = fees.saturating_add(*transaction.fee_amount()?);
+        }
+        Ok(csv_row(block.height(), block.timestamp(), reward, fees))
+    }
+}
+
+/// Returns a CSV row with the given block height, timestamp, reward, and fees.
+fn csv_row(height: u32, timestamp: i64, reward: u64, fees: u64) -> String {
+    format!("{height},{timestamp},{reward},{fees}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_row() {
+        let row = csv_row(10, 1_700_000_000, 23_782_343, 1_000);
+        assert_eq!(row, "10,1700000000,23782343,1000");
+        // Ensure the row has one value per header column.
+        assert_eq!(row.split(',').count(), CSV_HEADER.split(',').count());
+    }
+}
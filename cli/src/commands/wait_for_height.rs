@@ -0,0 +1,75 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use std::{
+    io::Write,
+    str::FromStr,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Waits until a running node reaches the given block height.
+#[derive(Debug, Parser)]
+pub struct WaitForHeight {
+    /// Specify the REST endpoint of the node
+    #[clap(long, default_value = "http://127.0.0.1:3033")]
+    pub rpc: String,
+    /// Specify the block height to wait for
+    #[clap(long)]
+    pub height: u32,
+    /// Specify the number of seconds to wait before giving up (default: wait forever)
+    #[clap(long)]
+    pub timeout: Option<u64>,
+    /// Specify the polling interval in seconds
+    #[clap(long, default_value = "5")]
+    pub interval: u64,
+}
+
+impl WaitForHeight {
+    /// Polls the node until it reaches the target height, or the timeout expires.
+    pub fn parse(self) -> Result<String> {
+        if self.interval == 0 {
+            bail!("The '--interval' must be greater than 0");
+        }
+
+        let endpoint = format!("{}/testnet3/block/height/latest", self.rpc.trim_end_matches('/'));
+        let deadline = self.timeout.map(|timeout| Instant::now() + Duration::from_secs(timeout));
+        loop {
+            // Fetch the latest height, tolerating an unreachable node (it may still be starting up).
+            match ureq::get(&endpoint).call() {
+                Ok(response) => {
+                    let latest_height = u32::from_str(response.into_string()?.trim())?;
+                    if latest_height >= self.height {
+                        println!();
+                        return Ok(format!("✅ The node reached block {latest_height}"));
+                    }
+                    print!("\rWaiting for block {} (currently at block {latest_height})...", self.height);
+                }
+                Err(error) => print!("\rWaiting for block {} (the node is unreachable - {error})...", self.height),
+            }
+            std::io::stdout().flush()?;
+
+            // Ensure the timeout has not expired.
+            if let Some(deadline) = deadline {
+                if Instant::now() >= deadline {
+                    println!();
+                    bail!("Timed out waiting for block {}", self.height);
+                }
+            }
+            thread::sleep(Duration::from_secs(self.interval));
+        }
+    }
+}
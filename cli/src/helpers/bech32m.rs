@@ -56,6 +56,22 @@ pub fn has_vanity_string(s: &str, vanity: &str) -> bool {
     data.starts_with(vanity) || data.ends_with(vanity)
 }
 
+/// Check if a given prefix string exists at the start of the data part of a bech32m string.
+///
+/// The bech32m string must have the following format:
+/// ```ignore
+///     <HRP>1<prefix string><data>
+/// ```
+/// For example, the prefix string `abc` matches `aleo1abc...`, but not `aleo1xabc...`.
+pub fn has_prefix_string(s: &str, prefix: &str) -> bool {
+    match s.split_once('1') {
+        // Check if the prefix string exists at the start of the data part.
+        Some((hrp, data)) => !hrp.is_empty() && data.starts_with(prefix),
+        // The bech32m string is invalid.
+        None => false,
+    }
+}
+
 #[test]
 fn test_is_in_bech32m_charset() {
     assert!(is_in_bech32m_charset("qpzry9x8gf2tvdw0s3jn54khce6mua7l1qpzry9x8gf2tvdw0s3jn54khce6mua7l1"));
@@ -70,3 +86,11 @@ fn test_has_vanity_string() {
     assert!(has_vanity_string("myhrp11234myvanitystring", "myvanitystring"));
     assert!(!has_vanity_string("myhrp1anotherstring1234", "myvanitystring"));
 }
+
+#[test]
+fn test_has_prefix_string() {
+    assert!(has_prefix_string("myhrp1myprefix1234", "myprefix"));
+    assert!(!has_prefix_string("myhrp11234myprefix", "myprefix"));
+    assert!(!has_prefix_string("myhrp1anotherstring", "myprefix"));
+    assert!(!has_prefix_string("1myprefix", "myprefix"));
+}
@@ -0,0 +1,218 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::commands::Start;
+
+use anyhow::{bail, Result};
+use clap::{parser::ValueSource, ArgAction, CommandFactory};
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+/// The name of the option that specifies the config file of the `start` command.
+const CONFIG_OPTION: &str = "config";
+
+/// Expands the `--config <path>` option of the `start` command into the options in the given TOML file,
+/// which maps option names (e.g. `network`, `node`, `private_key`) to values.
+/// The file options are inserted before the command-line options, and options given explicitly on the
+/// command line are skipped, so that they take precedence over the file.
+pub fn expand_config_args(args: impl IntoIterator<Item = OsString>) -> Result<Vec<OsString>> {
+    let mut args = args.into_iter().collect::<Vec<_>>();
+
+    // Find the `start` subcommand, skipping over the value of the top-level verbosity option.
+    let mut index = 1;
+    while index < args.len() {
+        match args[index].to_str() {
+            Some("start") => break,
+            Some("-v") | Some("--verbosity") => index += 2,
+            _ => index += 1,
+        }
+    }
+    if index >= args.len() {
+        return Ok(args);
+    }
+    let start_index = index;
+
+    // Parse the command-line options of the `start` command, to find the config file and the explicit options.
+    // Note: The errors are ignored here (e.g. a missing value), and reported by the final parse of the arguments.
+    let command = Start::command().ignore_errors(true);
+    let command_args = std::iter::once(args[start_index].clone()).chain(args[start_index + 1..].iter().cloned());
+    let Ok(matches) = command.clone().try_get_matches_from(command_args) else {
+        return Ok(args);
+    };
+    // Retrieve the path to the config file, if one is given.
+    let Some(path) = matches.get_one::<PathBuf>(CONFIG_OPTION).cloned() else {
+        return Ok(args);
+    };
+
+    // Skip the options that are given explicitly on the command line (by their long or short name, or an alias).
+    let explicit = command
+        .get_arguments()
+        .filter(|arg| matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine))
+        .filter_map(|arg| arg.get_long().map(str::to_string))
+        .collect::<Vec<_>>();
+    let config_args = load_config_args(&path)?
+        .into_iter()
+        .filter(|(name, _)| !explicit.contains(name))
+        .flat_map(|(_, args)| args)
+        .map(OsString::from);
+
+    // Insert the file options right after the `start` subcommand.
+    args.splice(start_index + 1..start_index + 1, config_args);
+    Ok(args)
+}
+
+/// Loads the TOML file at the given path, and returns each option name with its command-line arguments.
+fn load_config_args(path: &Path) -> Result<Vec<(String, Vec<String>)>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) => bail!("Failed to read the config file '{}' - {error}", path.display()),
+    };
+    let table = match toml::from_str::<toml::value::Table>(&contents) {
+        Ok(table) => table,
+        Err(error) => bail!("Failed to parse the config file '{}' - {error}", path.display()),
+    };
+
+    let command = Start::command();
+    let mut options = Vec::with_capacity(table.len());
+    for (key, value) in table {
+        // Ensure the key is a known option, accepting both `snake_case` and `kebab-case` names.
+        let name = key.replace('_', "-");
+        let Some(arg) = command.get_arguments().find(|arg| arg.get_long() == Some(name.as_str())) else {
+            bail!("Unknown option '{key}' in the config file '{}'", path.display());
+        };
+        if name == CONFIG_OPTION {
+            bail!("The config file '{}' must not set the '{key}' option", path.display());
+        }
+
+        // Convert the value into command-line arguments.
+        let flag = format!("--{name}");
+        let args = match (arg.get_action(), value) {
+            (ArgAction::SetTrue, toml::Value::Boolean(true)) => vec![flag],
+            (ArgAction::SetTrue, toml::Value::Boolean(false)) => vec![],
+            (ArgAction::SetTrue, _) => bail!("The option '{key}' in the config file must be a boolean"),
+            (_, toml::Value::String(value)) => vec![flag, value],
+            (_, toml::Value::Integer(value)) => vec![flag, value.to_string()],
+            (_, toml::Value::Float(value)) => vec![flag, value.to_string()],
+            (_, toml::Value::Array(values)) => {
+                let values = values
+                    .into_iter()
+                    .map(|value| match value {
+                        toml::Value::String(value) => Ok(value),
+                        value => bail!("The option '{key}' in the config file must be a list of strings ({value})"),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                vec![flag, values.join(",")]
+            }
+            (_, value) => bail!("The option '{key}' in the config file has an unsupported value ({value})"),
+        };
+        options.push((name, args));
+    }
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    fn write_config(name: &str, contents: &str) -> String {
+        let path = std::env::temp_dir().join(format!("snarkos-config-{}-{name}.toml", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_expand_config_args() {
+        let path = write_config(
+            "expand",
+            r#"
+                network = 3
+                node = "0.0.0.0:4140"
+                nodisplay = true
+                nocdn = false
+                peers = ["1.2.3.4:4133", "5.6.7.8:4133"]
+            "#,
+        );
+
+        // The file options are inserted after the subcommand, and explicit options take precedence.
+        let expanded =
+            expand_config_args(args(&["snarkos", "start", "--config", &path, "--node", "0.0.0.0:4141"])).unwrap();
+        assert_eq!(
+            expanded,
+            args(&[
+                "snarkos",
+                "start",
+                "--network",
+                "3",
+                "--nodisplay",
+                "--peers",
+                "1.2.3.4:4133,5.6.7.8:4133",
+                "--config",
+                &path,
+                "--node",
+                "0.0.0.0:4141",
+            ])
+        );
+        // The expanded arguments parse, alongside '--dev'.
+        let config = format!("--config={path}");
+        let expanded = expand_config_args(args(&["snarkos", "start", &config, "--dev", "0"])).unwrap();
+        let start = Start::try_parse_from(&expanded[1..]).unwrap();
+        assert_eq!(start.node.port(), 4140);
+        assert_eq!(start.dev, Some(0));
+
+        // Other commands, and commands without a config file, are left unchanged.
+        let unchanged = args(&["snarkos", "-v", "start", "clean"]);
+        assert_eq!(expand_config_args(unchanged.clone()).unwrap(), unchanged);
+        let unchanged = args(&["snarkos", "start", "--dev", "0"]);
+        assert_eq!(expand_config_args(unchanged.clone()).unwrap(), unchanged);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_expand_config_args_with_alias() {
+        let path = write_config("alias", "rest_max_batch = 10");
+
+        // Ensure an option given by its alias on the command line takes precedence over the file.
+        let expanded = expand_config_args(args(&["snarkos", "start", "--config", &path, "--rpc-max-batch", "20"]));
+        let expanded = expanded.unwrap();
+        assert_eq!(expanded, args(&["snarkos", "start", "--config", &path, "--rpc-max-batch", "20"]));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_expand_config_args_fails() {
+        // A missing file fails.
+        assert!(expand_config_args(args(&["snarkos", "start", "--config", "/nonexistent/snarkos.toml"])).is_err());
+        // A missing path is left to the final parse, which fails.
+        let unchanged = args(&["snarkos", "start", "--config"]);
+        assert_eq!(expand_config_args(unchanged.clone()).unwrap(), unchanged);
+        assert!(Start::try_parse_from(&unchanged[1..]).is_err());
+
+        // Malformed TOML, unknown options, and mistyped values fail.
+        let configs = [("malformed", "network = "), ("unknown", "rpc_username = \"x\""), ("mistyped", "nodisplay = 1")];
+        for (name, contents) in configs {
+            let path = write_config(name, contents);
+            assert!(expand_config_args(args(&["snarkos", "start", "--config", &path])).is_err());
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+}
@@ -12,17 +12,58 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::helpers::LogWriter;
+use crate::helpers::{LogWriter, RotatingFile, Syslog};
 
+use anyhow::{bail, Result};
 use crossterm::tty::IsTty;
-use std::{fs::File, io, path::Path};
+use std::{io, path::PathBuf, str::FromStr, sync::Arc};
 use tokio::sync::mpsc;
+use tracing::Subscriber;
 use tracing_subscriber::{
+    fmt::{self, MakeWriter},
     layer::{Layer, SubscriberExt},
+    registry::LookupSpan,
     util::SubscriberInitExt,
     EnvFilter,
 };
 
+/// The format of the log events.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines.
+    #[default]
+    Text,
+    /// One JSON object per line, with the timestamp, level, target, and message (and fields) of the event.
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(format: &str) -> Result<Self> {
+        match format {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => bail!("Invalid log format '{format}' (expected 'text' or 'json')"),
+        }
+    }
+}
+
+/// Returns the given log layer, formatting its events in the given format.
+fn with_format<S, W>(
+    layer: fmt::Layer<S, fmt::format::DefaultFields, fmt::format::Format, W>,
+    format: LogFormat,
+) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => layer.boxed(),
+        LogFormat::Json => layer.json().flatten_event(true).with_ansi(false).with_target(true).boxed(),
+    }
+}
+
 /// Initializes the logger.
 ///
 /// ```ignore
@@ -34,7 +75,19 @@ use tracing_subscriber::{
 /// 5 => info, debug, trace, snarkos_node_router=trace
 /// 6 => info, debug, trace, snarkos_node_tcp=trace
 /// ```
-pub fn initialize_logger<P: AsRef<Path>>(verbosity: u8, nodisplay: bool, logfile: P) -> mpsc::Receiver<Vec<u8>> {
+///
+/// The logs are also written to the given logfile, without colors, which is rotated daily,
+/// keeping the logs of the given number of previous days, and to the given syslog server, if one is set.
+/// The log files older than the number of days to keep are removed on startup.
+/// The verbosity applies in either log format.
+pub fn initialize_logger(
+    verbosity: u8,
+    nodisplay: bool,
+    logfile: PathBuf,
+    log_keep_days: u16,
+    log_format: LogFormat,
+    syslog: Option<Syslog>,
+) -> mpsc::Receiver<Vec<u8>> {
     match verbosity {
         0 => std::env::set_var("RUST_LOG", "info"),
         1 => std::env::set_var("RUST_LOG", "debug"),
@@ -42,7 +95,7 @@ pub fn initialize_logger<P: AsRef<Path>>(verbosity: u8, nodisplay: bool, logfile
     };
 
     // Filter out undesirable logs. (unfortunately EnvFilter cannot be cloned)
-    let [filter, filter2] = std::array::from_fn(|_| {
+    let [filter, filter2, filter3] = std::array::from_fn(|_| {
         let filter = EnvFilter::from_default_env()
             .add_directive("mio=off".parse().unwrap())
             .add_directive("tokio_util=off".parse().unwrap())
@@ -85,14 +138,16 @@ pub fn initialize_logger<P: AsRef<Path>>(verbosity: u8, nodisplay: bool, logfile
     });
 
     // Create the directories tree for a logfile if it doesn't exist.
-    let logfile_dir = logfile.as_ref().parent().expect("Root directory passed as a logfile");
+    let logfile_dir = logfile.parent().expect("Root directory passed as a logfile");
     if !logfile_dir.exists() {
         std::fs::create_dir_all(logfile_dir)
             .expect("Failed to create a directories: '{logfile_dir}', please check if user has permissions");
     }
     // Create a file to write logs to.
     let logfile =
-        File::options().append(true).create(true).open(logfile).expect("Failed to open the file for writing logs");
+        Arc::new(RotatingFile::open(logfile, log_keep_days).expect("Failed to open the file for writing logs"));
+    // Remove the log files that expired since the last run.
+    let num_pruned = logfile.prune();
 
     // Initialize the log channel.
     let (log_sender, log_receiver) = mpsc::channel(1024);
@@ -107,22 +162,37 @@ pub fn initialize_logger<P: AsRef<Path>>(verbosity: u8, nodisplay: bool, logfile
     let _ = tracing_subscriber::registry()
         .with(
             // Add layer using LogWriter for stdout / terminal
-            tracing_subscriber::fmt::Layer::default()
-                .with_ansi(log_sender.is_none() && io::stdout().is_tty())
-                .with_writer(move || LogWriter::new(&log_sender))
-                .with_target(verbosity > 2)
-                .with_filter(filter),
+            with_format(
+                fmt::Layer::default()
+                    .with_ansi(log_sender.is_none() && io::stdout().is_tty())
+                    .with_writer(move || LogWriter::new(&log_sender))
+                    .with_target(verbosity > 2),
+                log_format,
+            )
+            .with_filter(filter),
         )
         .with(
             // Add layer redirecting logs to the file
-            tracing_subscriber::fmt::Layer::default()
-                .with_ansi(false)
-                .with_writer(logfile)
-                .with_target(verbosity > 2)
-                .with_filter(filter2),
+            with_format(
+                fmt::Layer::default().with_ansi(false).with_writer(logfile).with_target(verbosity > 2),
+                log_format,
+            )
+            .with_filter(filter2),
         )
+        .with(syslog.map(|syslog| {
+            // Add layer sending logs to the syslog server
+            with_format(
+                fmt::Layer::default().with_ansi(false).with_writer(syslog).with_target(verbosity > 2),
+                log_format,
+            )
+            .with_filter(filter3)
+        }))
         .try_init();
 
+    if num_pruned > 0 {
+        tracing::info!("Removed {num_pruned} log files older than {log_keep_days} days");
+    }
+
     log_receiver
 }
 
@@ -151,3 +221,16 @@ pub fn welcome_message() -> String {
     output += &"👋 Welcome to Aleo! We thank you for running a node and supporting privacy.\n".bold();
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_format() {
+        assert_eq!(LogFormat::from_str("text").unwrap(), LogFormat::Text);
+        assert_eq!(LogFormat::from_str("json").unwrap(), LogFormat::Json);
+        assert!(LogFormat::from_str("JSON").is_err());
+        assert!(LogFormat::from_str("").is_err());
+    }
+}
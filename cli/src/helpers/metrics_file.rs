@@ -0,0 +1,137 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::RotatingFile;
+use snarkos_node::metrics;
+
+use anyhow::{bail, Result};
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The key metrics written to the metrics file, as pairs of the field name and the metric name.
+const FIELDS: [(&str, &str); 5] = [
+    ("height", metrics::blocks::HEIGHT),
+    ("peers", metrics::peers::CONNECTED),
+    ("mempool", metrics::consensus::MEMPOOL_SIZE),
+    ("bytes_in", metrics::router::BYTES_RECEIVED),
+    ("bytes_out", metrics::router::BYTES_SENT),
+];
+
+/// The format of the lines written to the metrics file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MetricsFormat {
+    /// Comma-separated values, with a header naming the fields, and the UNIX timestamp in seconds.
+    Csv,
+    /// The InfluxDB line protocol, with the UNIX timestamp in nanoseconds.
+    LineProtocol,
+}
+
+impl FromStr for MetricsFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(format: &str) -> Result<Self> {
+        match format {
+            "csv" => Ok(Self::Csv),
+            "line-protocol" | "influx" => Ok(Self::LineProtocol),
+            _ => bail!("Invalid metrics format '{format}' (expected 'csv' or 'line-protocol')"),
+        }
+    }
+}
+
+impl MetricsFormat {
+    /// Returns the header to start each metrics file with, if the format has one.
+    fn header(&self) -> Option<String> {
+        match self {
+            Self::Csv => {
+                let names = FIELDS.iter().map(|(field, _)| *field).collect::<Vec<_>>();
+                Some(format!("timestamp,{}\n", names.join(",")))
+            }
+            Self::LineProtocol => None,
+        }
+    }
+
+    /// Returns the line of the key metrics in the given snapshot, taken at the given time since the UNIX epoch.
+    /// Note: A metric that was not recorded yet is written as zero.
+    fn line(&self, timestamp: Duration, snapshot: &HashMap<String, f64>) -> String {
+        let values = FIELDS.iter().map(|(field, name)| (*field, snapshot.get(*name).copied().unwrap_or_default()));
+        match self {
+            Self::Csv => {
+                let values = values.map(|(_, value)| value.to_string()).collect::<Vec<_>>();
+                format!("{},{}\n", timestamp.as_secs(), values.join(","))
+            }
+            Self::LineProtocol => {
+                let fields = values.map(|(field, value)| format!("{field}={value}")).collect::<Vec<_>>();
+                format!("snarkos {} {}\n", fields.join(","), timestamp.as_nanos())
+            }
+        }
+    }
+}
+
+/// Spawns a task that appends the key metrics to the file at the given path, at every interval.
+/// The file is rotated daily like the log files, keeping the given number of previous days.
+pub fn spawn_metrics_file(path: PathBuf, keep_days: u16, format: MetricsFormat, interval: Duration) -> Result<()> {
+    let mut file = RotatingFile::open(path.clone(), keep_days)?;
+    if let Some(header) = format.header() {
+        file = file.with_header(header);
+    }
+    // Remove the rotated metrics files left behind while the node was not running.
+    file.prune();
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+            let line = format.line(timestamp, &metrics::snapshot());
+            if let Err(error) = (&file).write_all(line.as_bytes()) {
+                tracing::warn!("Failed to write the metrics to '{}' - {error}", path.display());
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_format_line() {
+        let snapshot = HashMap::from([
+            (metrics::blocks::HEIGHT.to_string(), 42.0),
+            (metrics::peers::CONNECTED.to_string(), 5.0),
+            (metrics::router::BYTES_RECEIVED.to_string(), 1024.0),
+            (metrics::router::BYTES_SENT.to_string(), 2048.0),
+        ]);
+        let timestamp = Duration::from_secs(1_700_000_000);
+
+        let csv = MetricsFormat::from_str("csv").unwrap();
+        assert_eq!(csv.header().unwrap(), "timestamp,height,peers,mempool,bytes_in,bytes_out\n");
+        assert_eq!(csv.line(timestamp, &snapshot), "1700000000,42,5,0,1024,2048\n");
+
+        let line_protocol = MetricsFormat::from_str("line-protocol").unwrap();
+        assert!(line_protocol.header().is_none());
+        assert_eq!(
+            line_protocol.line(timestamp, &snapshot),
+            "snarkos height=42,peers=5,mempool=0,bytes_in=1024,bytes_out=2048 1700000000000000000\n"
+        );
+
+        assert!(MetricsFormat::from_str("json").is_err());
+    }
+}
@@ -15,24 +15,74 @@
 mod bech32m;
 pub use bech32m::*;
 
+mod config_file;
+pub use config_file::*;
+
 mod log_writer;
 use log_writer::*;
 
 pub mod logger;
 pub use logger::*;
 
+#[cfg(feature = "metrics")]
+mod metrics_file;
+#[cfg(feature = "metrics")]
+pub use metrics_file::*;
+
+mod network_profile;
+pub use network_profile::*;
+
+mod rotating_file;
+pub use rotating_file::*;
+
+mod syslog;
+pub use syslog::*;
+
 pub mod updater;
 pub use updater::*;
 
 use snarkos_node::router::messages::NodeType;
+use snarkvm::prelude::{
+    store::{helpers::rocksdb::ConsensusDB, ConsensusStore},
+    Network,
+};
 
+use anyhow::{anyhow, bail, Result};
 use colored::*;
 #[cfg(target_family = "unix")]
 use nix::sys::resource::{getrlimit, Resource};
 
+/// Prints the given startup warning, or returns it as an error if `fast_fail` is set.
+pub(crate) fn warn_or_fail(warning: String, fast_fail: bool) -> Result<()> {
+    match fast_fail {
+        true => bail!("{warning}\n(aborting, as '--fast-fail' is set)"),
+        false => {
+            eprintln!("{}\n", warning.yellow().bold());
+            Ok(())
+        }
+    }
+}
+
+/// Opens the existing ledger of the given node, for the commands that read the ledger.
+/// Note: The ledger storage can only be opened read-write, so it cannot be read while a node is running on it.
+pub(crate) fn open_ledger_store<N: Network>(dev: Option<u16>) -> Result<ConsensusStore<N, ConsensusDB<N>>> {
+    let path = aleo_std::aleo_ledger_dir(N::ID, dev);
+    // Ensure the ledger exists, as opening a missing ledger would create an empty one.
+    if !path.exists() {
+        bail!("There is no ledger at '{}'", path.display());
+    }
+    ConsensusStore::open(dev).map_err(|error| match error.to_string().to_lowercase().contains("lock") {
+        true => anyhow!(
+            "The ledger at '{}' is in use by a running node - stop the node to read its ledger ({error})",
+            path.display()
+        ),
+        false => error,
+    })
+}
+
 /// Check if process's open files limit is above minimum and warn if not.
 #[cfg(target_family = "unix")]
-pub fn check_open_files_limit(minimum: u64) {
+pub fn check_open_files_limit(minimum: u64, fast_fail: bool) -> Result<()> {
     // Acquire current limits.
     match getrlimit(Resource::RLIMIT_NOFILE) {
         Ok((soft_limit, _)) => {
@@ -44,10 +94,8 @@ pub fn check_open_files_limit(minimum: u64) {
                     format!("⚠️  To ensure correct behavior of the node, please raise it to at least {minimum}."),
                     "⚠️  See the `ulimit` command and `/etc/security/limits.conf` for more details.".to_owned(),
                 ]
-                .join("\n")
-                .yellow()
-                .bold();
-                eprintln!("{warning}\n");
+                .join("\n");
+                warn_or_fail(warning, fast_fail)?;
             }
         }
         Err(err) => {
@@ -57,12 +105,11 @@ pub fn check_open_files_limit(minimum: u64) {
                 format!("⚠️  To ensure correct behavior of the node, please ensure it is at least {minimum}."),
                 "⚠️  See the `ulimit` command and `/etc/security/limits.conf` for more details.".to_owned(),
             ]
-            .join("\n")
-            .yellow()
-            .bold();
-            eprintln!("{warning}\n");
+            .join("\n");
+            warn_or_fail(warning, fast_fail)?;
         }
     };
+    Ok(())
 }
 
 /// Returns the RAM memory in GiB.
@@ -76,30 +123,31 @@ pub(crate) fn detect_ram_memory() -> Result<u64, sys_info::Error> {
 /// Note: Some of the checks in this method are overly-permissive, in order to ensure
 /// future hardware architecture changes do not prevent validators from running a node.
 #[rustfmt::skip]
-pub(crate) fn check_validator_machine(node_type: NodeType) {
+pub(crate) fn check_validator_machine(node_type: NodeType, fast_fail: bool) -> Result<()> {
     // If the node is a validator, ensure it meets the minimum requirements.
     if node_type.is_validator() {
         // Ensure the system is a Linux-based system.
         // Note: While macOS is not officially supported, we allow it for development purposes.
         if !cfg!(target_os = "linux") && !cfg!(target_os = "macos") {
-            let message = "⚠️  The operating system of this machine is not supported for a validator (Ubuntu required)\n".to_string();
-            println!("{}", message.yellow().bold());
+            let message = "⚠️  The operating system of this machine is not supported for a validator (Ubuntu required)".to_string();
+            warn_or_fail(message, fast_fail)?;
         }
         // Retrieve the number of cores.
         let num_cores = num_cpus::get();
         // Enforce the minimum number of cores.
         let min_num_cores = 32;
         if num_cores < min_num_cores {
-            let message = format!("⚠️  The number of cores ({num_cores} cores) on this machine is insufficient for a validator (minimum {min_num_cores} cores)\n");
-            println!("{}", message.yellow().bold());
+            let message = format!("⚠️  The number of cores ({num_cores} cores) on this machine is insufficient for a validator (minimum {min_num_cores} cores)");
+            warn_or_fail(message, fast_fail)?;
         }
         // Enforce the minimum amount of RAM.
         if let Ok(ram) = crate::helpers::detect_ram_memory() {
             let min_ram = 60;
             if ram < min_ram {
-                let message = format!("⚠️  The amount of RAM ({ram} GiB) on this machine is insufficient for a validator (minimum {min_ram} GiB)\n");
-                println!("{}", message.yellow().bold());
+                let message = format!("⚠️  The amount of RAM ({ram} GiB) on this machine is insufficient for a validator (minimum {min_ram} GiB)");
+                warn_or_fail(message, fast_fail)?;
             }
         }
     }
+    Ok(())
 }
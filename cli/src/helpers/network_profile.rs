@@ -0,0 +1,109 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{block::Block, FromBytes, Network};
+
+use anyhow::{bail, Result};
+use serde::Deserialize;
+use std::{net::SocketAddr, path::PathBuf};
+
+/// The network-specific settings needed to join a (custom) network, as loaded from a profile file.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkProfile {
+    /// The network ID.
+    pub network: Option<u16>,
+    /// The path to the genesis block file, relative to the profile file.
+    pub genesis: Option<PathBuf>,
+    /// The expected hash of the genesis block.
+    pub genesis_hash: Option<String>,
+    /// The peers to connect to.
+    #[serde(default)]
+    pub peers: Vec<SocketAddr>,
+    /// The validators to connect to.
+    #[serde(default)]
+    pub validators: Vec<SocketAddr>,
+    /// The port for the node server.
+    pub node_port: Option<u16>,
+    /// The port for the REST server.
+    pub rest_port: Option<u16>,
+}
+
+impl NetworkProfile {
+    /// Loads the network profile from the given JSON file.
+    pub fn load(path: &PathBuf) -> Result<Self> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) => bail!("Failed to read the network profile '{}' - {error}", path.display()),
+        };
+        let mut profile: Self = match serde_json::from_str(&contents) {
+            Ok(profile) => profile,
+            Err(error) => bail!("Failed to parse the network profile '{}' - {error}", path.display()),
+        };
+        // Resolve the genesis block file relative to the profile file.
+        if let (Some(genesis), Some(directory)) = (&profile.genesis, path.parent()) {
+            profile.genesis = Some(directory.join(genesis));
+        }
+        Ok(profile)
+    }
+
+    /// Returns the genesis block of the profile, if one is given.
+    pub fn load_genesis<N: Network>(&self) -> Result<Option<Block<N>>> {
+        let Some(path) = &self.genesis else { return Ok(None) };
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(error) => bail!("Failed to read the genesis block '{}' - {error}", path.display()),
+        };
+        Ok(Some(Block::from_bytes_le(&bytes)?))
+    }
+
+    /// Ensures the given genesis block matches the genesis hash of the profile, if one is given.
+    pub fn check_genesis<N: Network>(&self, genesis: &Block<N>) -> Result<()> {
+        match &self.genesis_hash {
+            Some(expected) if *expected != genesis.hash().to_string() => {
+                bail!("The genesis block {} does not match the network profile ({expected})", genesis.hash())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_network_profile() {
+        let directory = std::env::temp_dir().join(format!("snarkos-test-network-profile-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("profile.json");
+        std::fs::write(
+            &path,
+            r#"{ "network": 3, "genesis": "genesis.block", "peers": ["127.0.0.1:4130"], "node_port": 4140 }"#,
+        )
+        .unwrap();
+
+        let profile = NetworkProfile::load(&path).unwrap();
+        assert_eq!(profile.network, Some(3));
+        assert_eq!(profile.genesis, Some(directory.join("genesis.block")));
+        assert_eq!(profile.peers, vec!["127.0.0.1:4130".parse().unwrap()]);
+        assert!(profile.validators.is_empty());
+        assert_eq!(profile.node_port, Some(4140));
+        assert_eq!(profile.rest_port, None);
+
+        // Ensure unknown settings are rejected.
+        std::fs::write(&path, r#"{ "network": 3, "unknown": true }"#).unwrap();
+        assert!(NetworkProfile::load(&path).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use parking_lot::Mutex;
+use std::{
+    fs::File,
+    io,
+    path::{Path, PathBuf},
+};
+use time::{Date, Duration, OffsetDateTime};
+
+/// A log file that is rotated daily, keeping the logs of the given number of previous days.
+/// The logs of the current day are written to the given path, and the logs of a previous day
+/// are moved to the path suffixed with the date, e.g. `snarkos.log.2024-01-31`.
+pub struct RotatingFile {
+    /// The path of the log file of the current day.
+    path: PathBuf,
+    /// The number of days of rotated log files to keep.
+    keep_days: u16,
+    /// The open log file, and the date it holds the logs of.
+    file: Mutex<(File, Date)>,
+    /// The header to write at the start of each new file, if any.
+    header: Option<String>,
+}
+
+impl RotatingFile {
+    /// Opens the log file at the given path for appending, creating it if it does not exist.
+    pub fn open(path: PathBuf, keep_days: u16) -> io::Result<Self> {
+        let file = Self::open_file(&path)?;
+        // If the existing log file holds the logs of a previous day, it is rotated on the next write.
+        let date = match file.metadata().and_then(|metadata| metadata.modified()) {
+            Ok(modified) => OffsetDateTime::from(modified).date(),
+            Err(_) => today(),
+        };
+        Ok(Self { path, keep_days, file: Mutex::new((file, date)), header: None })
+    }
+
+    /// Sets the header to write at the start of each new file, e.g. the column names of a CSV file.
+    pub fn with_header(mut self, header: String) -> Self {
+        self.header = Some(header);
+        self
+    }
+
+    /// Opens the file at the given path for appending.
+    fn open_file(path: &Path) -> io::Result<File> {
+        File::options().append(true).create(true).open(path)
+    }
+
+    /// Rotates the log file if it holds the logs of a day before the given date.
+    fn rotate_if_needed(&self, file: &mut (File, Date), today: Date) -> io::Result<()> {
+        if file.1 >= today {
+            return Ok(());
+        }
+        // Move the logs of the previous day aside, and start a new log file.
+        std::fs::rename(&self.path, self.rotated_path(file.1))?;
+        *file = (Self::open_file(&self.path)?, today);
+        // Remove the rotated log files that are older than the number of days to keep.
+        self.remove_expired(today);
+        Ok(())
+    }
+
+    /// Removes the rotated log files that are older than the number of days to keep,
+    /// e.g. those left behind while the node was not running, and returns the number of removed files.
+    pub fn prune(&self) -> usize {
+        self.remove_expired(today())
+    }
+
+    /// Returns the path of the rotated log file of the given date.
+    fn rotated_path(&self, date: Date) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{date}"));
+        PathBuf::from(path)
+    }
+
+    /// Removes the rotated log files of the days before the ones to keep, as of the given date,
+    /// and returns the number of removed files.
+    fn remove_expired(&self, today: Date) -> usize {
+        let Some(file_name) = self.path.file_name() else { return 0 };
+        let Some(cutoff) = today.checked_sub(Duration::days(i64::from(self.keep_days))) else { return 0 };
+        // Note: A relative path without a parent directory is in the current directory.
+        let directory = match self.path.parent() {
+            Some(directory) if !directory.as_os_str().is_empty() => directory,
+            _ => Path::new("."),
+        };
+        let Ok(entries) = std::fs::read_dir(directory) else { return 0 };
+        let prefix = format!("{}.", file_name.to_string_lossy());
+        let cutoff = cutoff.to_string();
+        let mut num_removed = 0;
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            // Note: The dates are formatted as 'YYYY-MM-DD', so they are ordered as strings.
+            if let Some(date) = name.strip_prefix(&prefix) {
+                if date.len() == cutoff.len() && date < cutoff.as_str() && std::fs::remove_file(entry.path()).is_ok() {
+                    num_removed += 1;
+                }
+            }
+        }
+        num_removed
+    }
+}
+
+impl io::Write for &RotatingFile {
+    /// Writes the given buffer into the log file of the current day.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut file = self.file.lock();
+        self.rotate_if_needed(&mut file, today())?;
+        // If the file is new, start it with the header.
+        if let Some(header) = &self.header {
+            if file.0.metadata()?.len() == 0 {
+                file.0.write_all(header.as_bytes())?;
+            }
+        }
+        file.0.write(buf)
+    }
+
+    /// Flushes the log file.
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.lock().0.flush()
+    }
+}
+
+/// Returns the current date, in UTC.
+fn today() -> Date {
+    OffsetDateTime::now_utc().date()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_rotating_file() {
+        let directory = std::env::temp_dir().join(format!("snarkos-test-logs-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("snarkos.log");
+
+        let log = RotatingFile::open(path.clone(), 2).unwrap();
+        let day = |days: i64| today() + Duration::days(days);
+        (&log).write_all(b"today\n").unwrap();
+
+        // Ensure the logs of each previous day are moved aside.
+        for days in 1..=4 {
+            let mut file = log.file.lock();
+            log.rotate_if_needed(&mut file, day(days)).unwrap();
+            file.0.write_all(format!("day {days}\n").as_bytes()).unwrap();
+        }
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "day 4\n");
+        assert_eq!(std::fs::read_to_string(log.rotated_path(day(3))).unwrap(), "day 3\n");
+        assert_eq!(std::fs::read_to_string(log.rotated_path(day(2))).unwrap(), "day 2\n");
+        // Ensure only the rotated log files of the days to keep remain.
+        assert!(!log.rotated_path(day(1)).exists());
+        assert!(!log.rotated_path(day(0)).exists());
+
+        // Ensure pruning removes the rotated log files older than the days to keep, as of today.
+        assert_eq!(log.prune(), 0);
+        std::fs::write(log.rotated_path(day(-3)), "expired\n").unwrap();
+        assert_eq!(log.prune(), 1);
+        assert!(!log.rotated_path(day(-3)).exists());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{bail, Result};
+use parking_lot::Mutex;
+use std::{
+    io::{self, Write},
+    net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket},
+    time::{Duration, Instant},
+};
+use time::OffsetDateTime;
+use tracing::{Level, Metadata};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// The syslog facility of the node logs (`daemon`).
+const FACILITY: u8 = 3;
+/// The name of the application in the syslog messages.
+const APP_NAME: &str = "snarkos";
+/// The maximum duration to wait when connecting to a TCP syslog server.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+/// The duration to wait after failing to reach a TCP syslog server, before connecting again.
+/// Note: In the meantime, the logs are dropped, so an unreachable server does not stall the node.
+const RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
+/// The transport to the syslog server.
+enum Transport {
+    /// Each message is sent in a UDP datagram.
+    Udp(UdpSocket),
+    /// The messages are sent over a TCP connection, which is reopened on failure.
+    Tcp(Mutex<TcpState>),
+}
+
+/// The state of the TCP connection to the syslog server.
+#[derive(Default)]
+struct TcpState {
+    /// The connection, if it is open.
+    stream: Option<TcpStream>,
+    /// The time of the last failure to reach the server, if any.
+    last_failure: Option<Instant>,
+}
+
+/// A remote syslog server, to which the logs are sent as RFC 5424 messages.
+pub struct Syslog {
+    /// The address of the syslog server.
+    addr: SocketAddr,
+    /// The transport to the syslog server.
+    transport: Transport,
+    /// The hostname of this machine, as reported in the messages.
+    hostname: String,
+}
+
+impl Syslog {
+    /// Initializes the syslog client for the given address, i.e. `udp://host:port`, `tcp://host:port`,
+    /// or `host:port` for UDP. The TCP connection is opened when the first message is sent.
+    pub fn new(addr: &str) -> Result<Self> {
+        let (is_tcp, host) = match addr.split_once("://") {
+            Some(("udp", host)) => (false, host),
+            Some(("tcp", host)) => (true, host),
+            Some((scheme, _)) => bail!("Invalid syslog protocol '{scheme}' (expected 'udp' or 'tcp')"),
+            None => (false, addr),
+        };
+        let Some(socket_addr) = host.to_socket_addrs().ok().and_then(|mut addrs| addrs.next()) else {
+            bail!("Invalid syslog address '{addr}' (expected e.g. 'udp://127.0.0.1:514')");
+        };
+        let transport = match is_tcp {
+            true => Transport::Tcp(Default::default()),
+            false => {
+                let bind_addr: SocketAddr = match socket_addr.is_ipv4() {
+                    true => ([0u8; 4], 0).into(),
+                    false => ([0u16; 8], 0).into(),
+                };
+                Transport::Udp(UdpSocket::bind(bind_addr)?)
+            }
+        };
+        let hostname = sys_info::hostname().unwrap_or_else(|_| "-".to_string());
+        Ok(Self { addr: socket_addr, transport, hostname })
+    }
+
+    /// Sends the given log line to the syslog server, with the given severity.
+    /// If the server can not be reached, the log line is dropped.
+    fn send(&self, severity: u8, line: &[u8]) {
+        let message = self.format(severity, OffsetDateTime::now_utc(), line);
+        match &self.transport {
+            Transport::Udp(socket) => {
+                let _ = socket.send_to(message.as_bytes(), self.addr);
+            }
+            Transport::Tcp(state) => {
+                let mut state = state.lock();
+                // If the server was recently unreachable, drop the message.
+                if state.stream.is_none() && state.last_failure.map_or(false, |t| t.elapsed() < RECONNECT_DELAY) {
+                    return;
+                }
+                if state.stream.is_none() {
+                    state.stream = TcpStream::connect_timeout(&self.addr, CONNECT_TIMEOUT).ok();
+                }
+                // Frame the message with its length (RFC 6587 octet counting).
+                let framed = format!("{} {message}", message.len());
+                let result = match &mut state.stream {
+                    Some(stream) => stream.write_all(framed.as_bytes()),
+                    None => Err(io::ErrorKind::NotConnected.into()),
+                };
+                if result.is_err() {
+                    *state = TcpState { stream: None, last_failure: Some(Instant::now()) };
+                }
+            }
+        }
+    }
+
+    /// Returns the RFC 5424 message for the given severity, timestamp, and log line.
+    fn format(&self, severity: u8, timestamp: OffsetDateTime, line: &[u8]) -> String {
+        let priority = FACILITY * 8 + severity;
+        let timestamp = format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}Z",
+            timestamp.year(),
+            u8::from(timestamp.month()),
+            timestamp.day(),
+            timestamp.hour(),
+            timestamp.minute(),
+            timestamp.second(),
+            timestamp.microsecond()
+        );
+        let line = String::from_utf8_lossy(line);
+        format!("<{priority}>1 {timestamp} {} {APP_NAME} {} - - {}", self.hostname, std::process::id(), line.trim_end())
+    }
+}
+
+/// Returns the syslog severity for the given log level.
+fn severity(level: &Level) -> u8 {
+    match *level {
+        // Error.
+        Level::ERROR => 3,
+        // Warning.
+        Level::WARN => 4,
+        // Informational.
+        Level::INFO => 6,
+        // Debug (for both the debug and trace levels).
+        _ => 7,
+    }
+}
+
+/// A writer for a single log event, which is sent to the syslog server when the writer is dropped.
+pub struct SyslogWriter<'a> {
+    /// The syslog server.
+    syslog: &'a Syslog,
+    /// The syslog severity of the log event.
+    severity: u8,
+    /// The formatted log event.
+    buffer: Vec<u8>,
+}
+
+impl Write for SyslogWriter<'_> {
+    /// Writes the given buffer into the log event.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    /// Flushes the log writer (no-op).
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for SyslogWriter<'_> {
+    /// Sends the log event to the syslog server.
+    fn drop(&mut self) {
+        if !self.buffer.is_empty() {
+            self.syslog.send(self.severity, &self.buffer);
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for Syslog {
+    type Writer = SyslogWriter<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        SyslogWriter { syslog: self, severity: severity(&Level::INFO), buffer: Vec::new() }
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        SyslogWriter { syslog: self, severity: severity(meta.level()), buffer: Vec::new() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_syslog_format() {
+        let syslog = Syslog::new("udp://127.0.0.1:514").unwrap();
+        let timestamp = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let message = syslog.format(severity(&Level::WARN), timestamp, b"WARN Peer is unresponsive\n");
+        let expected = format!(
+            "<28>1 2023-11-14T22:13:20.000000Z {} snarkos {} - - WARN Peer is unresponsive",
+            syslog.hostname,
+            std::process::id()
+        );
+        assert_eq!(message, expected);
+    }
+
+    #[test]
+    fn test_syslog_address() {
+        assert!(Syslog::new("127.0.0.1:514").is_ok());
+        assert!(Syslog::new("tcp://127.0.0.1:6514").is_ok());
+        assert!(Syslog::new("http://127.0.0.1:514").is_err());
+        assert!(Syslog::new("udp://127.0.0.1").is_err());
+    }
+
+    #[test]
+    fn test_syslog_udp() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let syslog = Syslog::new(&server.local_addr().unwrap().to_string()).unwrap();
+        syslog.send(severity(&Level::ERROR), b"ERROR Failed to advance");
+
+        let mut buffer = [0u8; 1024];
+        let (length, _) = server.recv_from(&mut buffer).unwrap();
+        let message = String::from_utf8_lossy(&buffer[..length]);
+        assert!(message.starts_with("<27>1 "));
+        assert!(message.ends_with(" - - ERROR Failed to advance"));
+    }
+}
@@ -24,7 +24,7 @@ if [[ $clear_logs == "y" ]]; then
 
   for ((validator_index = 0; validator_index < total_validators; validator_index++)); do
     # Run 'snarkos clean' for each validator in the background
-    snarkos clean --dev $validator_index &
+    snarkos clean --dev $validator_index --force &
 
     # Store the process ID of the background task
     clean_processes+=($!)
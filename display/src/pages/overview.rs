@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use snarkos_node::Node;
-use snarkvm::prelude::Network;
+use snarkvm::prelude::{store::ConsensusStorage, Network};
 
 use tui::{
     backend::Backend,
@@ -27,7 +27,12 @@ use tui::{
 pub(crate) struct Overview;
 
 impl Overview {
-    pub(crate) fn draw<B: Backend, N: Network>(&self, f: &mut Frame<B>, area: Rect, _node: &Node<N>) {
+    pub(crate) fn draw<B: Backend, N: Network, C: ConsensusStorage<N>>(
+        &self,
+        f: &mut Frame<B>,
+        area: Rect,
+        _node: &Node<N, C>,
+    ) {
         // Initialize the layout of the page.
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -22,23 +22,65 @@ use snarkvm::{
         store::ConsensusStorage,
         Ledger,
     },
-    prelude::{bail, Field, Network, Result},
+    prelude::{bail, Field, Network, Result, ToBytes},
 };
 
 use indexmap::IndexMap;
-use std::{fmt, ops::Range, sync::Arc};
+use lru::LruCache;
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use std::{fmt, num::NonZeroUsize, ops::Range, sync::Arc};
+
+/// The default number of successful transmission verifications to cache.
+pub const DEFAULT_VERIFICATION_CACHE_SIZE: usize = 4096;
+
+/// A key of the verification cache, as the SHA-256 digest of the verified transmission bytes,
+/// and the latest block hash at the time of verification.
+///
+/// Note: The transmission ID does not commit to every byte (e.g. a transaction ID does not commit to its proof),
+/// so the digest of the bytes is used instead. A verification also depends on the ledger state
+/// (e.g. unspent inputs, or the epoch challenge), so it is only reused until the ledger advances.
+type VerificationKey<N> = ([u8; 32], <N as Network>::BlockHash);
 
 /// A core ledger service.
 pub struct CoreLedgerService<N: Network, C: ConsensusStorage<N>> {
     ledger: Ledger<N, C>,
     coinbase_verifying_key: Arc<CoinbaseVerifyingKey<N>>,
+    /// The cache of successful transmission verifications, if it is enabled.
+    verified: Option<Mutex<LruCache<VerificationKey<N>, ()>>>,
 }
 
 impl<N: Network, C: ConsensusStorage<N>> CoreLedgerService<N, C> {
-    /// Initializes a new core ledger service.
-    pub fn new(ledger: Ledger<N, C>) -> Self {
+    /// Initializes a new core ledger service, which caches up to the given number of successful transmission
+    /// verifications, so that a transmission that is checked again (e.g. when it is proposed in a batch)
+    /// is not re-verified. A cache size of `0` disables the cache.
+    pub fn new(ledger: Ledger<N, C>, verification_cache_size: usize) -> Self {
         let coinbase_verifying_key = Arc::new(ledger.coinbase_puzzle().coinbase_verifying_key().clone());
-        Self { ledger, coinbase_verifying_key }
+        let verified = NonZeroUsize::new(verification_cache_size).map(|size| Mutex::new(LruCache::new(size)));
+        Self { ledger, coinbase_verifying_key, verified }
+    }
+
+    /// Returns the verification cache key of the given transmission, if the cache is enabled.
+    fn verification_key(&self, transmission: &impl ToBytes) -> Result<Option<VerificationKey<N>>> {
+        match self.verified {
+            Some(_) => Ok(Some((Sha256::digest(transmission.to_bytes_le()?).into(), self.ledger.latest_hash()))),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns `true` if the transmission with the given key was verified successfully.
+    fn is_verified(&self, key: &Option<VerificationKey<N>>) -> bool {
+        match (&self.verified, key) {
+            (Some(verified), Some(key)) => verified.lock().get(key).is_some(),
+            _ => false,
+        }
+    }
+
+    /// Records that the transmission with the given key was verified successfully.
+    fn insert_verified(&self, key: Option<VerificationKey<N>>) {
+        if let (Some(verified), Some(key)) = (&self.verified, key) {
+            verified.lock().put(key, ());
+        }
     }
 }
 
@@ -176,6 +218,12 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for CoreLedgerService<
             bail!("Invalid solution - expected {puzzle_commitment}, found {}", solution.commitment());
         }
 
+        // Skip the verification if the solution was verified successfully in the current ledger state.
+        let key = self.verification_key(&solution)?;
+        if self.is_verified(&key) {
+            return Ok(());
+        }
+
         // Retrieve the coinbase verifying key.
         let coinbase_verifying_key = self.coinbase_verifying_key.clone();
         // Compute the current epoch challenge.
@@ -187,6 +235,7 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for CoreLedgerService<
         if !spawn_blocking!(solution.verify(&coinbase_verifying_key, &epoch_challenge, proof_target))? {
             bail!("Invalid prover solution '{puzzle_commitment}' for the current epoch.");
         }
+        self.insert_verified(key);
         Ok(())
     }
 
@@ -206,9 +255,16 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for CoreLedgerService<
         if transaction.is_fee() {
             bail!("Invalid transaction - 'Transaction::fee' type is not valid at this stage ({})", transaction.id());
         }
+        // Skip the verification if the transaction was verified successfully in the current ledger state.
+        let key = self.verification_key(&transaction)?;
+        if self.is_verified(&key) {
+            return Ok(());
+        }
         // Check the transaction is well-formed.
         let ledger = self.ledger.clone();
-        spawn_blocking!(ledger.check_transaction_basic(&transaction, None, &mut rand::thread_rng()))
+        spawn_blocking!(ledger.check_transaction_basic(&transaction, None, &mut rand::thread_rng()))?;
+        self.insert_verified(key);
+        Ok(())
     }
 
     /// Checks the given block is valid next block.
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{CoreLedgerService, LedgerService};
+use crate::{CoreLedgerService, LedgerService, DEFAULT_VERIFICATION_CACHE_SIZE};
 use async_trait::async_trait;
 use indexmap::IndexMap;
 use snarkvm::{
@@ -42,7 +42,7 @@ impl<N: Network, C: ConsensusStorage<N>> fmt::Debug for TranslucentLedgerService
 impl<N: Network, C: ConsensusStorage<N>> TranslucentLedgerService<N, C> {
     /// Initializes a new ledger service wrapper.
     pub fn new(ledger: Ledger<N, C>) -> Self {
-        Self { inner: CoreLedgerService::new(ledger) }
+        Self { inner: CoreLedgerService::new(ledger, DEFAULT_VERIFICATION_CACHE_SIZE) }
     }
 }
 
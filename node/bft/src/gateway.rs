@@ -237,6 +237,11 @@ impl<N: Network> CommunicationService for Gateway<N> {
         Event::BlockRequest(BlockRequest { start_height, end_height })
     }
 
+    /// Returns `true`, as the gateway only connects to authenticated committee members.
+    fn is_trusted_peer(&self, _peer_ip: SocketAddr) -> bool {
+        true
+    }
+
     /// Sends the given message to specified peer.
     ///
     /// This function returns as soon as the message is queued to be sent,
@@ -0,0 +1,25 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::ProductionGrace;
+use snarkos_node_sync::BlockSyncConfig;
+
+/// The configuration of a primary.
+#[derive(Clone, Debug, Default)]
+pub struct PrimaryConfig {
+    /// The grace period before the primary begins proposing batches.
+    pub production_grace: ProductionGrace,
+    /// The configuration of the block sync module of the primary.
+    pub sync: BlockSyncConfig,
+}
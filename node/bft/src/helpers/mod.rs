@@ -18,6 +18,9 @@ pub use cache::*;
 pub mod channels;
 pub use channels::*;
 
+pub mod config;
+pub use config::*;
+
 pub mod dag;
 pub use dag::*;
 
@@ -42,6 +45,9 @@ pub use storage::*;
 pub mod timestamp;
 pub use timestamp::*;
 
+pub mod warmup;
+pub use warmup::*;
+
 /// Formats an ID into a truncated identifier (for logging purposes).
 pub fn fmt_id(id: impl ToString) -> String {
     let id = id.to_string();
@@ -23,12 +23,17 @@ use snarkvm::{
 
 use indexmap::{IndexMap, IndexSet};
 use parking_lot::RwLock;
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
 #[derive(Clone, Debug)]
 pub struct Ready<N: Network> {
     /// The current map of `(transmission ID, transmission)` entries.
     transmissions: Arc<RwLock<IndexMap<TransmissionID<N>, Transmission<N>>>>,
+    /// The number of prioritized transmissions, which are at the front of the map.
+    num_prioritized: Arc<AtomicUsize>,
 }
 
 impl<N: Network> Default for Ready<N> {
@@ -41,7 +46,7 @@ impl<N: Network> Default for Ready<N> {
 impl<N: Network> Ready<N> {
     /// Initializes a new instance of the ready queue.
     pub fn new() -> Self {
-        Self { transmissions: Default::default() }
+        Self { transmissions: Default::default(), num_prioritized: Default::default() }
     }
 
     /// Returns `true` if the ready queue is empty.
@@ -117,12 +122,37 @@ impl<N: Network> Ready<N> {
         is_new
     }
 
+    /// Moves the specified transmission ahead of the transmissions that are not prioritized, so that it is drained
+    /// first, after the transmissions that were prioritized before it.
+    /// Returns `false` if the ready queue does not contain the transmission, and an error if the maximum number
+    /// of transmissions is already prioritized.
+    pub fn prioritize(&self, transmission_id: impl Into<TransmissionID<N>>, max_prioritized: usize) -> Result<bool> {
+        let transmission_id = transmission_id.into();
+        // Acquire the write lock.
+        let mut transmissions = self.transmissions.write();
+        // Retrieve the position of the transmission.
+        let Some(index) = transmissions.get_index_of(&transmission_id) else { return Ok(false) };
+        let num_prioritized = self.num_prioritized.load(Ordering::SeqCst);
+        // If the transmission is already prioritized, return early.
+        if index < num_prioritized {
+            return Ok(true);
+        }
+        ensure!(num_prioritized < max_prioritized, "Cannot prioritize more than {max_prioritized} transmissions");
+        // Move the transmission behind the previously prioritized transmissions.
+        transmissions.move_index(index, num_prioritized);
+        self.num_prioritized.store(num_prioritized + 1, Ordering::SeqCst);
+        Ok(true)
+    }
+
     /// Removes up to the specified number of transmissions and returns them.
     pub fn drain(&self, num_transmissions: usize) -> IndexMap<TransmissionID<N>, Transmission<N>> {
         // Acquire the write lock.
         let mut transmissions = self.transmissions.write();
         // Determine the number of transmissions to drain.
         let range = 0..transmissions.len().min(num_transmissions);
+        // Update the number of prioritized transmissions, as they are drained first.
+        let num_prioritized = self.num_prioritized.load(Ordering::SeqCst);
+        self.num_prioritized.store(num_prioritized.saturating_sub(range.len()), Ordering::SeqCst);
         // Drain the transmission IDs.
         transmissions.drain(range).collect::<IndexMap<_, _>>()
     }
@@ -223,4 +253,38 @@ mod tests {
         // Check the number of transmissions.
         assert_eq!(ready.num_transmissions(), 1);
     }
+
+    #[test]
+    fn test_ready_prioritize() {
+        let rng = &mut TestRng::default();
+
+        // Sample random fake bytes.
+        let data = |rng: &mut TestRng| Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+
+        // Initialize the ready queue.
+        let ready = Ready::<CurrentNetwork>::new();
+
+        // Insert the commitments.
+        let commitments =
+            (0..4).map(|_| TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()))).collect::<Vec<_>>();
+        for commitment in &commitments {
+            assert!(ready.insert(*commitment, Transmission::Solution(data(rng))));
+        }
+
+        // Prioritize the last two commitments, in order.
+        assert!(ready.prioritize(commitments[3], 2).unwrap());
+        assert!(ready.prioritize(commitments[2], 2).unwrap());
+        // Ensure prioritizing a commitment again is a no-op, and that the limit is enforced.
+        assert!(ready.prioritize(commitments[3], 2).unwrap());
+        assert!(ready.prioritize(commitments[0], 2).is_err());
+        // Ensure an unknown commitment is not prioritized.
+        let commitment_unknown = TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()));
+        assert!(!ready.prioritize(commitment_unknown, 2).unwrap());
+
+        // Ensure the prioritized commitments are drained first.
+        let drained = ready.drain(3).into_keys().collect::<Vec<_>>();
+        assert_eq!(drained, vec![commitments[3], commitments[2], commitments[0]]);
+        // Ensure the limit is released once the prioritized commitments are drained.
+        assert!(ready.prioritize(commitments[1], 1).unwrap());
+    }
 }
@@ -0,0 +1,93 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use parking_lot::Mutex;
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+/// The grace period before a newly-started primary begins proposing batches.
+/// By default, there is no grace period, and the primary proposes batches as soon as it starts.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProductionGrace {
+    /// The duration the node must be synced and connected before it proposes batches.
+    pub duration: Duration,
+    /// The minimum number of connected validators during the grace period.
+    pub min_peers: usize,
+}
+
+/// The warmup of a newly-started primary, which ends once the node has been synced
+/// and connected to enough validators for the entire grace period.
+#[derive(Debug, Default)]
+pub struct Warmup {
+    /// The time since which the node has been continuously synced and connected, if it currently is.
+    ready_since: Mutex<Option<Instant>>,
+    /// The boolean flag for whether the warmup has ended.
+    is_done: AtomicBool,
+}
+
+impl Warmup {
+    /// Updates the warmup with the current state of the node, and returns `true` if the warmup has ended.
+    /// Note: Once the warmup has ended, it does not restart, even if the node later falls behind or loses peers.
+    pub fn update(&self, grace: &ProductionGrace, is_synced: bool, num_connected: usize, now: Instant) -> bool {
+        if grace.duration.is_zero() || self.is_done.load(Ordering::SeqCst) {
+            return true;
+        }
+
+        let mut ready_since = self.ready_since.lock();
+        // If the node is not ready, restart the grace period.
+        if !is_synced || num_connected < grace.min_peers {
+            *ready_since = None;
+            return false;
+        }
+        // Otherwise, check if the node has been ready for the grace period.
+        let since = *ready_since.get_or_insert(now);
+        if now.saturating_duration_since(since) >= grace.duration {
+            self.is_done.store(true, Ordering::SeqCst);
+            info!("The grace period has ended, the primary will now propose batches");
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warmup() {
+        let start = Instant::now();
+
+        // Without a grace period, the warmup has ended immediately.
+        let warmup = Warmup::default();
+        assert!(warmup.update(&ProductionGrace::default(), false, 0, start));
+
+        let grace = ProductionGrace { duration: Duration::from_secs(10), min_peers: 2 };
+        let warmup = Warmup::default();
+        // The node must be synced and connected to enough peers.
+        assert!(!warmup.update(&grace, false, 2, start));
+        assert!(!warmup.update(&grace, true, 1, start));
+        assert!(!warmup.update(&grace, true, 2, start));
+        assert!(!warmup.update(&grace, true, 3, start + Duration::from_secs(9)));
+        // Losing a peer restarts the grace period.
+        assert!(!warmup.update(&grace, true, 1, start + Duration::from_secs(10)));
+        assert!(!warmup.update(&grace, true, 2, start + Duration::from_secs(11)));
+        assert!(!warmup.update(&grace, true, 2, start + Duration::from_secs(20)));
+        assert!(warmup.update(&grace, true, 2, start + Duration::from_secs(21)));
+        // Once the warmup has ended, it does not restart.
+        assert!(warmup.update(&grace, false, 0, start + Duration::from_secs(22)));
+    }
+}
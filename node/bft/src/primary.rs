@@ -22,10 +22,13 @@ use crate::{
         init_worker_channels,
         now,
         BFTSender,
+        PrimaryConfig,
         PrimaryReceiver,
         PrimarySender,
+        ProductionGrace,
         Proposal,
         Storage,
+        Warmup,
     },
     spawn_blocking,
     Gateway,
@@ -64,7 +67,7 @@ use std::{
     future::Future,
     net::SocketAddr,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::{
     sync::{Mutex as TMutex, OnceCell},
@@ -96,6 +99,10 @@ pub struct Primary<N: Network> {
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The lock for propose_batch.
     propose_lock: Arc<TMutex<u64>>,
+    /// The grace period before the primary begins proposing batches.
+    production_grace: ProductionGrace,
+    /// The warmup of the primary, before it begins proposing batches.
+    warmup: Arc<Warmup>,
 }
 
 impl<N: Network> Primary<N> {
@@ -107,11 +114,12 @@ impl<N: Network> Primary<N> {
         ip: Option<SocketAddr>,
         trusted_validators: &[SocketAddr],
         dev: Option<u16>,
+        config: PrimaryConfig,
     ) -> Result<Self> {
         // Initialize the gateway.
         let gateway = Gateway::new(account, ledger.clone(), ip, trusted_validators, dev)?;
         // Initialize the sync module.
-        let sync = Sync::new(gateway.clone(), storage.clone(), ledger.clone());
+        let sync = Sync::new(gateway.clone(), storage.clone(), ledger.clone(), config.sync);
         // Initialize the primary instance.
         Ok(Self {
             sync,
@@ -124,6 +132,8 @@ impl<N: Network> Primary<N> {
             signed_proposals: Default::default(),
             handles: Default::default(),
             propose_lock: Default::default(),
+            production_grace: config.production_grace,
+            warmup: Default::default(),
         })
     }
 
@@ -237,6 +247,29 @@ impl<N: Network> Primary<N> {
     pub fn num_unconfirmed_transactions(&self) -> usize {
         self.workers.iter().map(|worker| worker.num_transactions()).sum()
     }
+
+    /// Returns `true` if the given transaction ID is unconfirmed (i.e. in the memory pool).
+    pub fn contains_unconfirmed_transaction(&self, transaction_id: N::TransactionID) -> bool {
+        self.workers.iter().any(|worker| worker.contains_unconfirmed_transaction(transaction_id))
+    }
+
+    /// Prioritizes the given unconfirmed transaction, so that it is included in the next batch this primary proposes,
+    /// ahead of the other transmissions (if it is still valid when the batch is proposed).
+    pub fn prioritize_transaction(&self, transaction_id: N::TransactionID) -> Result<()> {
+        // Note: Each worker contributes up to this number of transmissions to a batch.
+        let max_prioritized = MAX_TRANSMISSIONS_PER_BATCH / self.num_workers() as usize;
+        for worker in self.workers.iter() {
+            if worker.prioritize_transaction(transaction_id, max_prioritized)? {
+                return Ok(());
+            }
+        }
+        // If the transaction is in the current batch proposal, it is already included.
+        let transmission_id = TransmissionID::Transaction(transaction_id);
+        if self.proposed_batch.read().as_ref().map_or(false, |p| p.contains_transmission(transmission_id)) {
+            return Ok(());
+        }
+        bail!("Transaction '{}' is not in the memory pool", fmt_id(transaction_id))
+    }
 }
 
 impl<N: Network> Primary<N> {
@@ -329,6 +362,19 @@ impl<N: Network> Primary<N> {
             bail!("Primary is safely skipping {}", format!("(round {round} was already certified)").dimmed());
         }
 
+        // If the node is halted on a minority fork, do not build on its chain, pending an operator decision.
+        if self.sync.is_halted_on_minority_fork() {
+            debug!("Primary is safely skipping a batch proposal {}", "(halted on a minority fork)".dimmed());
+            return Ok(());
+        }
+
+        // If the node is still warming up after starting, do not propose a batch on a possibly stale view.
+        let (is_synced, num_connected) = (self.sync.is_synced(), self.gateway.number_of_connected_peers());
+        if !self.warmup.update(&self.production_grace, is_synced, num_connected, Instant::now()) {
+            debug!("Primary is safely skipping a batch proposal {}", "(warming up after startup)".dimmed());
+            return Ok(());
+        }
+
         // Check if the primary is connected to enough validators to reach quorum threshold.
         {
             // Retrieve the committee to check against.
@@ -1488,7 +1534,7 @@ mod tests {
         let storage = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 10);
 
         // Initialize the primary.
-        let mut primary = Primary::new(account, storage, ledger, None, &[], None).unwrap();
+        let mut primary = Primary::new(account, storage, ledger, None, &[], None, Default::default()).unwrap();
 
         // Construct a worker instance.
         primary.workers = Arc::from([Worker::new(
@@ -21,7 +21,7 @@ use crate::{
 };
 use snarkos_node_bft_events::{CertificateRequest, CertificateResponse, Event};
 use snarkos_node_bft_ledger_service::LedgerService;
-use snarkos_node_sync::{locators::BlockLocators, BlockSync, BlockSyncMode};
+use snarkos_node_sync::{locators::BlockLocators, BlockSync, BlockSyncConfig, BlockSyncMode};
 use snarkvm::{
     console::{network::Network, types::Field},
     ledger::{authority::Authority, block::Block, narwhal::BatchCertificate},
@@ -57,9 +57,14 @@ pub struct Sync<N: Network> {
 
 impl<N: Network> Sync<N> {
     /// Initializes a new sync instance.
-    pub fn new(gateway: Gateway<N>, storage: Storage<N>, ledger: Arc<dyn LedgerService<N>>) -> Self {
+    pub fn new(
+        gateway: Gateway<N>,
+        storage: Storage<N>,
+        ledger: Arc<dyn LedgerService<N>>,
+        sync_config: BlockSyncConfig,
+    ) -> Self {
         // Initialize the block sync module.
-        let block_sync = BlockSync::new(BlockSyncMode::Gateway, ledger.clone());
+        let block_sync = BlockSync::new(BlockSyncMode::Gateway, ledger.clone(), sync_config);
         // Return the sync instance.
         Self {
             gateway,
@@ -310,6 +315,11 @@ impl<N: Network> Sync<N> {
         self.block_sync.is_block_synced()
     }
 
+    /// Returns `true` if the node is on a minority fork, and is configured to halt until the fork is resolved.
+    pub fn is_halted_on_minority_fork(&self) -> bool {
+        self.block_sync.is_halted_on_minority_fork()
+    }
+
     /// Returns `true` if the node is in gateway mode.
     pub const fn is_gateway_mode(&self) -> bool {
         self.block_sync.mode().is_gateway()
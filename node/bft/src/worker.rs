@@ -138,6 +138,13 @@ impl<N: Network> Worker<N> {
     pub fn transactions(&self) -> impl '_ + Iterator<Item = (N::TransactionID, Data<Transaction<N>>)> {
         self.ready.transactions()
     }
+
+    /// Returns `true` if the transaction ID exists in the ready queue or the proposed batch.
+    pub fn contains_unconfirmed_transaction(&self, transaction_id: N::TransactionID) -> bool {
+        let transmission_id = TransmissionID::Transaction(transaction_id);
+        self.ready.contains(transmission_id)
+            || self.proposed_batch.read().as_ref().map_or(false, |p| p.contains_transmission(transmission_id))
+    }
 }
 
 impl<N: Network> Worker<N> {
@@ -196,6 +203,16 @@ impl<N: Network> Worker<N> {
         self.ready.drain(num_transmissions).into_iter()
     }
 
+    /// Prioritizes the specified unconfirmed transaction in the ready queue, so that it is drained first.
+    /// Returns `false` if the ready queue does not contain the transaction.
+    pub(crate) fn prioritize_transaction(
+        &self,
+        transaction_id: N::TransactionID,
+        max_prioritized: usize,
+    ) -> Result<bool> {
+        self.ready.prioritize(transaction_id, max_prioritized)
+    }
+
     /// Reinserts the specified transmission into the ready queue.
     pub(crate) fn reinsert(&self, transmission_id: TransmissionID<N>, transmission: Transmission<N>) -> bool {
         // Check if the transmission ID exists.
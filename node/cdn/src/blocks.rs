@@ -22,9 +22,9 @@ use snarkvm::prelude::{
     Serialize,
 };
 
-use anyhow::{anyhow, bail, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use colored::Colorize;
-use core::ops::Range;
+use core::{ops::Range, str::FromStr};
 use futures::{Future, StreamExt};
 use parking_lot::RwLock;
 use reqwest::Client;
@@ -38,21 +38,78 @@ const BLOCKS_PER_FILE: u32 = 50;
 /// The supported network.
 const NETWORK_ID: u16 = 3;
 
-/// Loads blocks from a CDN into the ledger.
+/// The verification performed on each block loaded from a CDN, before it is added to the ledger.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CdnVerification {
+    /// The blocks are trusted, and are only deserialized.
+    #[default]
+    None,
+    /// Each block is checked to extend the ledger, by its height and previous block hash.
+    Headers,
+    /// Each block is fully verified, including its transactions and proofs.
+    Full,
+}
+
+impl FromStr for CdnVerification {
+    type Err = anyhow::Error;
+
+    fn from_str(mode: &str) -> Result<Self> {
+        match mode {
+            "none" => Ok(Self::None),
+            "headers" => Ok(Self::Headers),
+            "full" => Ok(Self::Full),
+            _ => bail!("Invalid CDN verification mode '{mode}' (expected 'none', 'headers', or 'full')"),
+        }
+    }
+}
+
+/// Checks the given block against the ledger, according to the given verification mode.
+fn verify_block<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    block: &Block<N>,
+    verification: CdnVerification,
+) -> Result<()> {
+    match verification {
+        CdnVerification::None => Ok(()),
+        CdnVerification::Headers => {
+            let expected_height = ledger.latest_height() + 1;
+            ensure!(
+                block.height() == expected_height,
+                "Block {} from the CDN has an unexpected height (expected {expected_height})",
+                block.height()
+            );
+            ensure!(
+                block.previous_hash() == ledger.latest_hash(),
+                "Block {} from the CDN does not extend the latest block in the ledger",
+                block.height()
+            );
+            Ok(())
+        }
+        CdnVerification::Full => ledger
+            .check_next_block(block, &mut rand::thread_rng())
+            .map_err(|error| anyhow!("Block {} from the CDN failed verification - {error}", block.height())),
+    }
+}
+
+/// Loads blocks from a CDN into the ledger, verifying each block with the given verification mode.
 ///
 /// On success, this function returns the completed block height.
 /// On failure, this function returns the last successful block height (if any), along with the error.
 pub async fn sync_ledger_with_cdn<N: Network, C: ConsensusStorage<N>>(
     base_url: &str,
     ledger: Ledger<N, C>,
+    verification: CdnVerification,
 ) -> Result<u32, (u32, anyhow::Error)> {
     // Fetch the node height.
     let start_height = ledger.latest_height() + 1;
     // Load the blocks from the CDN into the ledger.
     let ledger_clone = ledger.clone();
-    let result =
-        load_blocks(base_url, start_height, None, move |block: Block<N>| ledger_clone.advance_to_next_block(&block))
-            .await;
+    let result = load_blocks(base_url, start_height, None, move |block: Block<N>| {
+        // Verify the block, before adding it to the ledger.
+        verify_block(&ledger_clone, &block, verification)?;
+        ledger_clone.advance_to_next_block(&block)
+    })
+    .await;
 
     // TODO (howardwu): Find a way to resolve integrity failures.
     // If the sync failed, check the integrity of the ledger.
@@ -389,10 +446,12 @@ mod tests {
     use crate::{
         blocks::{cdn_get, cdn_height, handle_dispatch_error, log_progress, BLOCKS_PER_FILE},
         load_blocks,
+        CdnVerification,
     };
     use snarkvm::prelude::{block::Block, Testnet3};
 
     use anyhow::{anyhow, Result};
+    use core::str::FromStr;
     use parking_lot::RwLock;
     use std::{
         sync::{
@@ -521,4 +580,13 @@ mod tests {
         assert_eq!(result.unwrap(), 42);
         assert_eq!(counter.load(Ordering::SeqCst), 1);
     }
+
+    #[test]
+    fn test_cdn_verification_from_str() {
+        assert_eq!(CdnVerification::from_str("none").unwrap(), CdnVerification::None);
+        assert_eq!(CdnVerification::from_str("headers").unwrap(), CdnVerification::Headers);
+        assert_eq!(CdnVerification::from_str("full").unwrap(), CdnVerification::Full);
+        assert!(CdnVerification::from_str("all").is_err());
+        assert_eq!(CdnVerification::default(), CdnVerification::None);
+    }
 }
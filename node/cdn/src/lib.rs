@@ -18,4 +18,4 @@
 extern crate tracing;
 
 mod blocks;
-pub use blocks::{load_blocks, sync_ledger_with_cdn};
+pub use blocks::{load_blocks, sync_ledger_with_cdn, CdnVerification};
@@ -17,12 +17,16 @@
 #[macro_use]
 extern crate tracing;
 
+mod mempool_stats;
+pub use mempool_stats::*;
+
 use snarkos_account::Account;
 use snarkos_node_bft::{
     helpers::{
         fmt_id,
         init_consensus_channels,
         ConsensusReceiver,
+        PrimaryConfig,
         PrimaryReceiver,
         PrimarySender,
         Storage as NarwhalStorage,
@@ -34,6 +38,7 @@ use snarkos_node_bft::{
 };
 use snarkos_node_bft_ledger_service::LedgerService;
 use snarkos_node_bft_storage_service::BFTPersistentStorage;
+use snarkos_node_sync::{record_validation_phase, stream_block, BlockStream, ValidationHistogram, ValidationPhase};
 use snarkvm::{
     ledger::{
         block::Transaction,
@@ -48,12 +53,25 @@ use colored::Colorize;
 use indexmap::IndexMap;
 use lru::LruCache;
 use parking_lot::Mutex;
-use std::{future::Future, net::SocketAddr, num::NonZeroUsize, sync::Arc};
+use std::{
+    collections::HashSet,
+    future::Future,
+    net::SocketAddr,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 use tokio::{
     sync::{oneshot, OnceCell},
     task::JoinHandle,
 };
 
+/// The interval in seconds at which the memory pool statistics are logged.
+const MEMPOOL_STATS_LOG_INTERVAL_IN_SECS: u64 = 60; // 1 minute
+
 #[derive(Clone)]
 pub struct Consensus<N: Network> {
     /// The ledger.
@@ -66,10 +84,21 @@ pub struct Consensus<N: Network> {
     solutions_queue: Arc<Mutex<IndexMap<PuzzleCommitment<N>, ProverSolution<N>>>>,
     /// The unconfirmed transactions queue.
     transactions_queue: Arc<Mutex<IndexMap<N::TransactionID, Transaction<N>>>>,
+    /// The total size in bytes of the queued transactions.
+    transactions_queue_size: Arc<AtomicUsize>,
     /// The recently-seen unconfirmed solutions.
     seen_solutions: Arc<Mutex<LruCache<PuzzleCommitment<N>, ()>>>,
     /// The recently-seen unconfirmed transactions.
     seen_transactions: Arc<Mutex<LruCache<N::TransactionID, ()>>>,
+    /// The outcomes of the unconfirmed transactions submitted to the memory pool.
+    mempool_stats: Arc<MempoolStats>,
+    /// The map of unconfirmed transaction IDs to the time they entered the memory pool, in insertion order.
+    /// Entries of transactions that left the memory pool are pruned lazily.
+    transactions_first_seen: Arc<Mutex<IndexMap<N::TransactionID, Instant>>>,
+    /// The histogram of block validation timings, if profiling is enabled.
+    validation_histogram: Option<Arc<ValidationHistogram>>,
+    /// The stream of newly-applied blocks, if streaming is enabled.
+    block_stream: Option<BlockStream>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
@@ -82,13 +111,17 @@ impl<N: Network> Consensus<N> {
         ip: Option<SocketAddr>,
         trusted_validators: &[SocketAddr],
         dev: Option<u16>,
+        config: PrimaryConfig,
     ) -> Result<Self> {
+        // Note: The blocks of the BFT are validated and streamed like the synced blocks.
+        let validation_histogram = config.sync.validation_histogram.clone();
+        let block_stream = config.sync.block_stream.clone();
         // Initialize the Narwhal transmissions.
         let transmissions = Arc::new(BFTPersistentStorage::open(dev)?);
         // Initialize the Narwhal storage.
         let storage = NarwhalStorage::new(ledger.clone(), transmissions, MAX_GC_ROUNDS);
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger.clone(), ip, trusted_validators, dev)?;
+        let bft = BFT::new(account, storage, ledger.clone(), ip, trusted_validators, dev, config)?;
         // Return the consensus.
         Ok(Self {
             ledger,
@@ -96,8 +129,13 @@ impl<N: Network> Consensus<N> {
             primary_sender: Default::default(),
             solutions_queue: Default::default(),
             transactions_queue: Default::default(),
+            transactions_queue_size: Default::default(),
             seen_solutions: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1 << 16).unwrap()))),
             seen_transactions: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1 << 16).unwrap()))),
+            mempool_stats: Default::default(),
+            transactions_first_seen: Default::default(),
+            validation_histogram,
+            block_stream,
             handles: Default::default(),
         })
     }
@@ -153,6 +191,59 @@ impl<N: Network> Consensus<N> {
     pub fn num_unconfirmed_transactions(&self) -> usize {
         self.bft.num_unconfirmed_transactions()
     }
+
+    /// Returns the outcomes of the unconfirmed transactions submitted to the memory pool.
+    pub fn mempool_stats(&self) -> &MempoolStats {
+        &self.mempool_stats
+    }
+
+    /// Returns the number of unconfirmed transmissions, beyond which queued transactions are held back from the BFT.
+    pub const fn max_unconfirmed_transmissions(&self) -> usize {
+        MAX_TRANSMISSIONS_PER_BATCH
+    }
+
+    /// Returns the number of unconfirmed transactions queued to be sent to the BFT.
+    pub fn num_queued_transactions(&self) -> usize {
+        self.transactions_queue.lock().len()
+    }
+
+    /// Returns the total size in bytes of the queued and unconfirmed transactions.
+    /// Note: The size of the queued transactions is tracked as they enter and leave the queue,
+    /// and the unconfirmed transactions are only serialized if they are not already held as bytes.
+    pub fn unconfirmed_transactions_size_in_bytes(&self) -> Result<usize> {
+        let mut size = self.transactions_queue_size.load(Ordering::Relaxed);
+        for (_, transaction) in self.unconfirmed_transactions() {
+            size += match transaction {
+                Data::Buffer(bytes) => bytes.len(),
+                Data::Object(transaction) => transaction.to_bytes_le()?.len(),
+            };
+        }
+        Ok(size)
+    }
+
+    /// Returns the size in bytes of the given transaction.
+    fn transaction_size(transaction: &Transaction<N>) -> usize {
+        transaction.to_bytes_le().map_or(0, |bytes| bytes.len())
+    }
+
+    /// Returns the time the oldest queued or unconfirmed transaction has spent in the memory pool, if any.
+    pub fn oldest_unconfirmed_transaction_age(&self) -> Option<Duration> {
+        // Collect the IDs of the transactions currently in the memory pool.
+        let mut transaction_ids = self.transactions_queue.lock().keys().copied().collect::<HashSet<_>>();
+        transaction_ids.extend(self.unconfirmed_transactions().map(|(transaction_id, _)| transaction_id));
+
+        // Prune the transactions that left the memory pool, and return the age of the oldest one.
+        let mut first_seen = self.transactions_first_seen.lock();
+        first_seen.retain(|transaction_id, _| transaction_ids.contains(transaction_id));
+        first_seen.first().map(|(_, timestamp)| timestamp.elapsed())
+    }
+
+    /// Returns `true` if the given transaction ID is unconfirmed (i.e. in the memory pool).
+    /// Note: This includes the transactions that are queued to be sent to the BFT.
+    pub fn contains_unconfirmed_transaction(&self, transaction_id: N::TransactionID) -> bool {
+        self.transactions_queue.lock().contains_key(&transaction_id)
+            || self.bft.contains_unconfirmed_transaction(transaction_id)
+    }
 }
 
 impl<N: Network> Consensus<N> {
@@ -236,27 +327,38 @@ impl<N: Network> Consensus<N> {
 
             // Check that the transaction is not a fee transaction.
             if transaction.is_fee() {
+                self.mempool_stats.record(MempoolOutcome::Invalid);
                 bail!("Transaction '{}' is a fee transaction {}", fmt_id(transaction_id), "(skipping)".dimmed());
             }
             // Check if the transaction was recently seen.
             if self.seen_transactions.lock().put(transaction_id, ()).is_some() {
+                self.mempool_stats.record(MempoolOutcome::Duplicate);
                 // If the transaction was recently seen, return early.
                 return Ok(());
             }
             // Check if the transaction already exists in the ledger.
             if self.ledger.contains_transmission(&TransmissionID::from(&transaction_id))? {
+                self.mempool_stats.record(MempoolOutcome::Confirmed);
                 bail!("Transaction '{}' exists in the ledger {}", fmt_id(transaction_id), "(skipping)".dimmed());
             }
             // Add the transaction to the memory pool.
             trace!("Received unconfirmed transaction '{}' in the queue", fmt_id(transaction_id));
+            let size = Self::transaction_size(&transaction);
             if self.transactions_queue.lock().insert(transaction_id, transaction).is_some() {
+                self.mempool_stats.record(MempoolOutcome::Duplicate);
                 bail!("Transaction '{}' exists in the memory pool", fmt_id(transaction_id));
             }
+            self.transactions_queue_size.fetch_add(size, Ordering::Relaxed);
+            // Record when the transaction entered the memory pool.
+            self.transactions_first_seen.lock().entry(transaction_id).or_insert_with(Instant::now);
         }
+        #[cfg(feature = "metrics")]
+        self.update_mempool_size_metric();
 
         // If the memory pool of this node is full, return early.
         let num_unconfirmed = self.num_unconfirmed_transmissions();
         if num_unconfirmed > MAX_TRANSMISSIONS_PER_BATCH {
+            self.mempool_stats.record(MempoolOutcome::Full);
             return Ok(());
         }
         // Retrieve the transactions.
@@ -270,21 +372,52 @@ impl<N: Network> Consensus<N> {
             // Drain the solutions from the queue.
             queue.drain(..num_transactions).collect::<Vec<_>>()
         };
+        // Update the size of the queue.
+        let drained_size = transactions.iter().map(|(_, transaction)| Self::transaction_size(transaction)).sum();
+        self.transactions_queue_size.fetch_sub(drained_size, Ordering::Relaxed);
         // Iterate over the transactions.
         for (_, transaction) in transactions.into_iter() {
             let transaction_id = transaction.id();
             trace!("Adding unconfirmed transaction '{}' to the memory pool...", fmt_id(transaction_id));
             // Send the unconfirmed transaction to the primary.
-            if let Err(e) =
-                self.primary_sender().send_unconfirmed_transaction(transaction_id, Data::Object(transaction)).await
-            {
-                warn!("Failed to add unconfirmed transaction '{}' to the memory pool - {e}", fmt_id(transaction_id));
+            let result =
+                self.primary_sender().send_unconfirmed_transaction(transaction_id, Data::Object(transaction)).await;
+            match result {
+                Ok(()) => self.mempool_stats.record(MempoolOutcome::Accepted),
+                Err(e) => {
+                    self.mempool_stats.record(MempoolOutcome::Invalid);
+                    let id = fmt_id(transaction_id);
+                    warn!("Failed to add unconfirmed transaction '{id}' to the memory pool - {e}");
+                }
             }
         }
         Ok(())
     }
 }
 
+impl<N: Network> Consensus<N> {
+    /// Prioritizes the given unconfirmed transaction, so that this validator includes it in its next batch proposal,
+    /// ahead of the other transmissions in the memory pool.
+    pub async fn prioritize_transaction(&self, transaction_id: N::TransactionID) -> Result<()> {
+        // If the transaction is still queued, send it to the primary first.
+        let queued = self.transactions_queue.lock().shift_remove(&transaction_id);
+        if let Some(transaction) = queued {
+            self.transactions_queue_size.fetch_sub(Self::transaction_size(&transaction), Ordering::Relaxed);
+            let result =
+                self.primary_sender().send_unconfirmed_transaction(transaction_id, Data::Object(transaction)).await;
+            match result {
+                Ok(()) => self.mempool_stats.record(MempoolOutcome::Accepted),
+                Err(e) => {
+                    self.mempool_stats.record(MempoolOutcome::Invalid);
+                    let id = fmt_id(transaction_id);
+                    bail!("Failed to add unconfirmed transaction '{id}' to the memory pool - {e}");
+                }
+            }
+        }
+        self.bft.primary().prioritize_transaction(transaction_id)
+    }
+}
+
 impl<N: Network> Consensus<N> {
     /// Starts the consensus handlers.
     fn start_handlers(&self, consensus_receiver: ConsensusReceiver<N>) {
@@ -297,6 +430,22 @@ impl<N: Network> Consensus<N> {
                 self_.process_bft_subdag(committed_subdag, transmissions, callback).await;
             }
         });
+
+        // Periodically log the memory pool statistics, if they changed.
+        let self_ = self.clone();
+        self.spawn(async move {
+            let mut last_summary = String::new();
+            loop {
+                tokio::time::sleep(Duration::from_secs(MEMPOOL_STATS_LOG_INTERVAL_IN_SECS)).await;
+                let summary = self_.mempool_stats.summary();
+                if summary != last_summary {
+                    info!("Memory pool transactions: {summary}");
+                    last_summary = summary;
+                }
+                // Prune the first-seen times of the transactions that left the memory pool.
+                self_.oldest_unconfirmed_transaction_age();
+            }
+        });
     }
 
     /// Processes the committed subdag and transmissions from the BFT.
@@ -331,12 +480,32 @@ impl<N: Network> Consensus<N> {
         // Create the candidate next block.
         let next_block = self.ledger.prepare_advance_to_next_quorum_block(subdag, transmissions)?;
         // Check that the block is well-formed.
-        self.ledger.check_next_block(&next_block)?;
+        let timer = Instant::now();
+        let result = self.ledger.check_next_block(&next_block);
+        record_validation_phase(self.validation_histogram.as_deref(), ValidationPhase::Check, timer.elapsed());
+        result?;
         // Advance to the next block.
-        self.ledger.advance_to_next_block(&next_block)?;
+        let timer = Instant::now();
+        let result = self.ledger.advance_to_next_block(&next_block);
+        record_validation_phase(self.validation_histogram.as_deref(), ValidationPhase::Advance, timer.elapsed());
+        result?;
+        // Append the block to the block stream, if it is enabled.
+        stream_block(self.block_stream.as_ref(), &next_block);
+        #[cfg(feature = "metrics")]
+        {
+            metrics::gauge!(metrics::blocks::HEIGHT, next_block.height() as f64);
+            self.update_mempool_size_metric();
+        }
         Ok(())
     }
 
+    /// Updates the metric of the number of queued and unconfirmed transactions in the memory pool.
+    #[cfg(feature = "metrics")]
+    fn update_mempool_size_metric(&self) {
+        let num_transactions = self.num_queued_transactions() + self.bft.num_unconfirmed_transactions();
+        metrics::gauge!(metrics::consensus::MEMPOOL_SIZE, num_transactions as f64);
+    }
+
     /// Reinserts the given transmissions into the memory pool.
     async fn reinsert_transmissions(&self, transmissions: IndexMap<TransmissionID<N>, Transmission<N>>) {
         // Iterate over the transmissions.
@@ -387,3 +556,36 @@ impl<N: Network> Consensus<N> {
         self.handles.lock().iter().for_each(|handle| handle.abort());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkos_node_bft_ledger_service::MockLedgerService;
+    use snarkvm::{
+        ledger::{committee::test_helpers::sample_committee, ledger_test_helpers::sample_fee_public_transaction},
+        prelude::{TestRng, Testnet3},
+    };
+
+    use rand::Rng;
+
+    type CurrentNetwork = Testnet3;
+
+    #[tokio::test]
+    async fn test_contains_queued_transaction() {
+        let rng = &mut TestRng::default();
+        let ledger = Arc::new(MockLedgerService::new(sample_committee(rng)));
+        let account = Account::new(rng).unwrap();
+        let consensus =
+            Consensus::<CurrentNetwork>::new(account, ledger, None, &[], Some(rng.gen()), Default::default()).unwrap();
+
+        let transaction = sample_fee_public_transaction(rng);
+        let transaction_id = transaction.id();
+        assert!(!consensus.contains_unconfirmed_transaction(transaction_id));
+
+        // Queue the transaction, as on submission while the memory pool of the BFT is full.
+        consensus.transactions_queue.lock().insert(transaction_id, transaction);
+        assert_eq!(consensus.num_queued_transactions(), 1);
+        // Ensure the queued transaction is reported as unconfirmed.
+        assert!(consensus.contains_unconfirmed_transaction(transaction_id));
+    }
+}
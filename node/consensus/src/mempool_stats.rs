@@ -0,0 +1,119 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The outcome of submitting an unconfirmed transaction to the memory pool.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MempoolOutcome {
+    /// The transaction was accepted into the memory pool.
+    Accepted,
+    /// The transaction was rejected as malformed or invalid.
+    Invalid,
+    /// The transaction was rejected, as it was already seen or queued.
+    Duplicate,
+    /// The transaction was rejected, as it already exists in the ledger.
+    Confirmed,
+    /// The transaction was held in the queue, as the memory pool is full.
+    /// Note: A held transaction is recorded again once it is sent to the memory pool.
+    Full,
+}
+
+impl MempoolOutcome {
+    /// Returns the label of the outcome, as used in logs and metrics.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "accepted",
+            Self::Invalid => "invalid",
+            Self::Duplicate => "duplicate",
+            Self::Confirmed => "confirmed",
+            Self::Full => "full",
+        }
+    }
+}
+
+/// The counters of unconfirmed transaction outcomes, since the node started.
+#[derive(Debug, Default)]
+pub struct MempoolStats {
+    accepted: AtomicU64,
+    invalid: AtomicU64,
+    duplicate: AtomicU64,
+    confirmed: AtomicU64,
+    full: AtomicU64,
+}
+
+impl MempoolStats {
+    /// Records the given outcome.
+    pub fn record(&self, outcome: MempoolOutcome) {
+        self.counter(outcome).fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        metrics::increment_counter!(metrics::consensus::MEMPOOL_TRANSACTIONS, "outcome" => outcome.as_str());
+    }
+
+    /// Returns the number of transactions recorded with the given outcome.
+    pub fn get(&self, outcome: MempoolOutcome) -> u64 {
+        self.counter(outcome).load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of rejected transactions.
+    /// Note: The transactions held in the queue while the memory pool is full are not rejected.
+    pub fn num_rejected(&self) -> u64 {
+        self.get(MempoolOutcome::Invalid) + self.get(MempoolOutcome::Duplicate) + self.get(MempoolOutcome::Confirmed)
+    }
+
+    /// Returns a one-line summary of the counters.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} accepted, {} rejected ({} invalid, {} duplicate, {} confirmed), {} held (pool full)",
+            self.get(MempoolOutcome::Accepted),
+            self.num_rejected(),
+            self.get(MempoolOutcome::Invalid),
+            self.get(MempoolOutcome::Duplicate),
+            self.get(MempoolOutcome::Confirmed),
+            self.get(MempoolOutcome::Full),
+        )
+    }
+
+    /// Returns the counter for the given outcome.
+    const fn counter(&self, outcome: MempoolOutcome) -> &AtomicU64 {
+        match outcome {
+            MempoolOutcome::Accepted => &self.accepted,
+            MempoolOutcome::Invalid => &self.invalid,
+            MempoolOutcome::Duplicate => &self.duplicate,
+            MempoolOutcome::Confirmed => &self.confirmed,
+            MempoolOutcome::Full => &self.full,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mempool_stats() {
+        let stats = MempoolStats::default();
+        stats.record(MempoolOutcome::Accepted);
+        stats.record(MempoolOutcome::Accepted);
+        stats.record(MempoolOutcome::Duplicate);
+        stats.record(MempoolOutcome::Invalid);
+        stats.record(MempoolOutcome::Full);
+
+        assert_eq!(stats.get(MempoolOutcome::Accepted), 2);
+        assert_eq!(stats.get(MempoolOutcome::Confirmed), 0);
+        assert_eq!(stats.get(MempoolOutcome::Full), 1);
+        assert_eq!(stats.num_rejected(), 2);
+        assert_eq!(stats.summary(), "2 accepted, 2 rejected (1 invalid, 1 duplicate, 0 confirmed), 1 held (pool full)");
+    }
+}
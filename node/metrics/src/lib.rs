@@ -19,17 +19,38 @@ pub use metrics::*;
 // Expose the names at the crate level for easy access.
 pub use names::*;
 
-/// Initialises the metrics and returns a handle to the task running the metrics exporter.
-pub fn initialize() -> tokio::task::JoinHandle<()> {
+use metrics_exporter_prometheus::PrometheusHandle;
+use once_cell::sync::OnceCell;
+use std::{collections::HashMap, net::SocketAddr};
+
+/// The handle to the installed Prometheus recorder, which renders the current metrics.
+static HANDLE: OnceCell<PrometheusHandle> = OnceCell::new();
+
+/// Initialises the metrics, and if a listen address is given, returns a handle to the task running the
+/// metrics exporter, which serves the metrics in the Prometheus text format over HTTP at the given address.
+pub fn initialize(listen_addr: Option<SocketAddr>) -> Option<tokio::task::JoinHandle<()>> {
     use metrics_exporter_prometheus::PrometheusBuilder;
 
-    // Build the recorder and set as global.
-    let (recorder, exporter) = PrometheusBuilder::new().build().expect("can't build the prometheus exporter");
+    // Build the recorder, along with the exporter if a listen address is given.
+    let (recorder, exporter) = match listen_addr {
+        Some(listen_addr) => {
+            let (recorder, exporter) = PrometheusBuilder::new()
+                .with_http_listener(listen_addr)
+                .build()
+                .expect("can't build the prometheus exporter");
+            (recorder, Some(exporter))
+        }
+        None => (PrometheusBuilder::new().build_recorder(), None),
+    };
+    // Keep a handle to the recorder, and set it as global.
+    let _ = HANDLE.set(recorder.handle());
     metrics::set_boxed_recorder(Box::new(recorder)).expect("can't set the prometheus exporter");
 
     // Spawn a dedicated task for the exporter on the runtime.
-    let metrics_exporter_task = tokio::task::spawn(async move {
-        exporter.await.expect("can't await the prometheus exporter");
+    let metrics_exporter_task = exporter.map(|exporter| {
+        tokio::task::spawn(async move {
+            exporter.await.expect("can't await the prometheus exporter");
+        })
     });
 
     // Register the metrics so they exist on init.
@@ -39,8 +60,54 @@ pub fn initialize() -> tokio::task::JoinHandle<()> {
     metrics_exporter_task
 }
 
+/// Returns the current value of each metric, summed over its labels (empty if the metrics are not initialised).
+/// Note: The histograms are keyed by the names of their series, e.g. `<name>_sum` and `<name>_count`.
+pub fn snapshot() -> HashMap<String, f64> {
+    HANDLE.get().map(|handle| parse_snapshot(&handle.render())).unwrap_or_default()
+}
+
+/// Parses the given metrics in the Prometheus text format, summing the values of each metric over its labels.
+fn parse_snapshot(rendered: &str) -> HashMap<String, f64> {
+    let mut snapshot = HashMap::new();
+    for line in rendered.lines().filter(|line| !line.is_empty() && !line.starts_with('#')) {
+        // Note: The value is the last field, as the exporter does not emit timestamps.
+        let Some((series, value)) = line.rsplit_once(' ') else { continue };
+        let Ok(value) = value.parse::<f64>() else { continue };
+        let name = series.split('{').next().unwrap_or(series);
+        *snapshot.entry(name.to_string()).or_default() += value;
+    }
+    snapshot
+}
+
 fn register_metrics() {
     for name in GAUGE_NAMES {
         register_gauge!(name);
     }
+    for name in COUNTER_NAMES {
+        register_counter!(name);
+    }
+    for name in HISTOGRAM_NAMES {
+        register_histogram!(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_snapshot() {
+        let rendered = "\
+# TYPE snarkos_blocks_height_total gauge
+snarkos_blocks_height_total 42
+
+# TYPE snarkos_rest_requests_total counter
+snarkos_rest_requests_total{method=\"GET\",path=\"/testnet3/latest/height\"} 3
+snarkos_rest_requests_total{method=\"POST\",path=\"/testnet3/transaction/broadcast\"} 2
+";
+        let snapshot = parse_snapshot(rendered);
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot[blocks::HEIGHT], 42.0);
+        assert_eq!(snapshot[rest::REQUESTS], 5.0);
+    }
 }
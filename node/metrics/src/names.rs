@@ -12,14 +12,66 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub const GAUGE_NAMES: [&str; 4] = [blocks::HEIGHT, peers::CONNECTED, peers::CANDIDATE, peers::RESTRICTED];
+pub const GAUGE_NAMES: [&str; 7] = [
+    blocks::HEIGHT,
+    consensus::MEMPOOL_SIZE,
+    peers::CONNECTED,
+    peers::CANDIDATE,
+    peers::RESTRICTED,
+    router::TASKS,
+    sync::BLOCKS_IN_FLIGHT,
+];
+
+pub const COUNTER_NAMES: [&str; 11] = [
+    consensus::MEMPOOL_TRANSACTIONS,
+    peers::CONNECTS,
+    peers::DISCONNECTS,
+    rest::REQUESTS,
+    router::BYTES_RECEIVED,
+    router::BYTES_SENT,
+    router::MESSAGES_RECEIVED,
+    router::MESSAGES_SENT,
+    router::OVERSIZED_MESSAGES,
+    router::THROTTLED_BLOCK_REQUESTS,
+    sync::INVALID_BLOCKS,
+];
+
+pub const HISTOGRAM_NAMES: [&str; 2] = [blocks::CHECK_DURATION, blocks::ADVANCE_DURATION];
 
 pub mod blocks {
     pub const HEIGHT: &str = "snarkos_blocks_height_total";
+    pub const CHECK_DURATION: &str = "snarkos_blocks_check_duration_secs";
+    pub const ADVANCE_DURATION: &str = "snarkos_blocks_advance_duration_secs";
+}
+
+pub mod consensus {
+    pub const MEMPOOL_SIZE: &str = "snarkos_consensus_mempool_size_total";
+    pub const MEMPOOL_TRANSACTIONS: &str = "snarkos_consensus_mempool_transactions_total";
 }
 
 pub mod peers {
     pub const CONNECTED: &str = "snarkos_peers_connected_total";
     pub const CANDIDATE: &str = "snarkos_peers_candidate_total";
     pub const RESTRICTED: &str = "snarkos_peers_restricted_total";
+    pub const CONNECTS: &str = "snarkos_peers_connects_total";
+    pub const DISCONNECTS: &str = "snarkos_peers_disconnects_total";
+}
+
+pub mod rest {
+    pub const REQUESTS: &str = "snarkos_rest_requests_total";
+}
+
+pub mod router {
+    pub const BYTES_RECEIVED: &str = "snarkos_router_bytes_received_total";
+    pub const BYTES_SENT: &str = "snarkos_router_bytes_sent_total";
+    pub const MESSAGES_RECEIVED: &str = "snarkos_router_messages_received_total";
+    pub const MESSAGES_SENT: &str = "snarkos_router_messages_sent_total";
+    pub const OVERSIZED_MESSAGES: &str = "snarkos_router_oversized_messages_total";
+    pub const TASKS: &str = "snarkos_router_tasks_total";
+    pub const THROTTLED_BLOCK_REQUESTS: &str = "snarkos_router_throttled_block_requests_total";
+}
+
+pub mod sync {
+    pub const BLOCKS_IN_FLIGHT: &str = "snarkos_sync_blocks_in_flight_total";
+    pub const INVALID_BLOCKS: &str = "snarkos_sync_invalid_blocks_total";
 }
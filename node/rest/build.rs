@@ -0,0 +1,46 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{path::Path, process::Command};
+
+// The build script; it captures the git commit of the build for the `/version` endpoint.
+fn main() {
+    // Retrieve the git commit, if the build is run from within a git repository.
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=SNARKOS_GIT_COMMIT={commit}");
+
+    // Re-run upon a change to the checked out branch.
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+    // Re-run upon a new commit on the checked out branch, which only updates the branch ref and the HEAD log.
+    // Note: A missing file would re-run the script on every build, so only the existing files are watched.
+    let branch_ref = Command::new("git")
+        .args(["symbolic-ref", "-q", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|branch_ref| format!("../../.git/{}", branch_ref.trim()));
+    for path in branch_ref.iter().map(String::as_str).chain(["../../.git/logs/HEAD"]) {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={path}");
+        }
+    }
+}
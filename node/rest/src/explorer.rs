@@ -0,0 +1,50 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::{response::Html, routing::get};
+use std::net::SocketAddr;
+use tokio::task::JoinHandle;
+
+/// The page of the explorer, with a `{{REST_PORT}}` placeholder for the port of the REST server.
+const EXPLORER_PAGE: &str = include_str!("explorer.html");
+
+/// Starts a minimal block explorer at the given address, which serves a static page that
+/// displays the recent blocks, memory pool, and peers, as queried from the REST server.
+///
+/// This is intended for development and small deployments, not as a production explorer.
+pub fn start_explorer(explorer_ip: SocketAddr, rest_ip: SocketAddr) -> JoinHandle<()> {
+    let page = explorer_page(rest_ip.port());
+    let router = axum::Router::new().route("/", get(move || async move { Html(page) }));
+
+    tokio::spawn(async move {
+        axum::Server::bind(&explorer_ip).serve(router.into_make_service()).await.expect("couldn't start the explorer");
+    })
+}
+
+/// Returns the page of the explorer, querying the REST server on the given port.
+fn explorer_page(rest_port: u16) -> String {
+    EXPLORER_PAGE.replace("{{REST_PORT}}", &rest_port.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explorer_page() {
+        let page = explorer_page(3033);
+        assert!(page.contains(":3033/testnet3"));
+        assert!(!page.contains("{{REST_PORT}}"));
+    }
+}
@@ -12,11 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::RestConfig;
 use snarkvm::prelude::*;
 
 use ::time::OffsetDateTime;
 use anyhow::{anyhow, Result};
 use axum::{
+    extract::{ConnectInfo, State},
     headers::authorization::{Authorization, Bearer},
     http::{Request, StatusCode},
     middleware::Next,
@@ -27,6 +29,8 @@ use axum::{
 use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
+use std::{net::SocketAddr, sync::Arc};
+use subtle::ConstantTimeEq;
 
 /// The time a jwt token is valid for.
 pub const EXPIRATION: i64 = 10 * 365 * 24 * 60 * 60; // 10 years.
@@ -40,6 +44,12 @@ fn jwt_secret() -> &'static Vec<u8> {
     })
 }
 
+/// Returns `true` if the presented token matches the expected token.
+/// The comparison runs in constant time, so that its timing does not reveal how much of the token is correct.
+fn tokens_match(presented: &str, expected: &str) -> bool {
+    presented.as_bytes().ct_eq(expected.as_bytes()).into()
+}
+
 /// The Json web token claims.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Claims {
@@ -70,7 +80,11 @@ impl Claims {
     }
 }
 
-pub async fn auth_middleware<B>(request: Request<B>, next: Next<B>) -> Result<Response, Response>
+pub async fn auth_middleware<B>(
+    State(config): State<Arc<RestConfig>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Result<Response, Response>
 where
     B: Send,
 {
@@ -79,12 +93,21 @@ where
     let auth: TypedHeader<Authorization<Bearer>> =
         parts.extract().await.map_err(|_| StatusCode::UNAUTHORIZED.into_response())?;
 
+    // If a static token is set and presented, the request is authenticated.
+    if config.rest_token.as_deref().map_or(false, |token| tokens_match(auth.token(), token)) {
+        // Log the authenticated request, for auditing.
+        info!("Authenticated '{} {}' (REST token)", parts.method, parts.uri);
+        return Ok(next.run(Request::from_parts(parts, body)).await);
+    }
+
     match decode::<Claims>(auth.token(), &DecodingKey::from_secret(jwt_secret()), &Validation::new(Algorithm::HS256)) {
         Ok(decoded) => {
             let claims = decoded.claims;
             if claims.is_expired() {
                 return Err((StatusCode::UNAUTHORIZED, "Expired JSON Web Token".to_owned()).into_response());
             }
+            // Log the authenticated request, for auditing.
+            info!("Authenticated '{} {}' for '{}' (JWT)", parts.method, parts.uri, claims.sub);
         }
 
         Err(_) => {
@@ -97,3 +120,39 @@ where
 
     Ok(next.run(request).await)
 }
+
+pub async fn admin_middleware<B>(
+    State(config): State<Arc<RestConfig>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Result<Response, Response>
+where
+    B: Send,
+{
+    match &config.admin_token {
+        // If an admin token is set, the request must present it.
+        Some(token) => {
+            let (mut parts, body) = request.into_parts();
+            let auth: TypedHeader<Authorization<Bearer>> =
+                parts.extract().await.map_err(|_| StatusCode::UNAUTHORIZED.into_response())?;
+            if !tokens_match(auth.token(), token) {
+                warn!("Rejected an admin request from '{addr}' (invalid admin token)");
+                return Err(StatusCode::UNAUTHORIZED.into_response());
+            }
+            // Log the authenticated request, for auditing.
+            info!("Authenticated '{} {}' from '{addr}' (admin token)", parts.method, parts.uri);
+            Ok(next.run(Request::from_parts(parts, body)).await)
+        }
+        // Otherwise, the request must originate from localhost.
+        None => {
+            if !addr.ip().is_loopback() {
+                warn!("Rejected an admin request from '{addr}' (not from localhost)");
+                return Err(StatusCode::FORBIDDEN.into_response());
+            }
+            // Log the authenticated request, for auditing.
+            info!("Authenticated '{} {}' from '{addr}' (localhost)", request.method(), request.uri());
+            Ok(next.run(request).await)
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{ensure, Result};
+
+/// The path of the batch endpoint.
+pub const BATCH_PATH: &str = "/testnet3/batch";
+/// The default maximum number of requests in a batch.
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 50;
+
+/// Ensures the given batch of request paths is within the given maximum batch size (or `DEFAULT_MAX_BATCH_SIZE`),
+/// and that each path is an absolute path to an endpoint other than the batch endpoint.
+pub(crate) fn check_batch(paths: &[String], max_size: Option<usize>) -> Result<()> {
+    let max_size = max_size.unwrap_or(DEFAULT_MAX_BATCH_SIZE);
+    ensure!(paths.len() <= max_size, "Cannot request more than {max_size} paths per batch (requested {})", paths.len());
+    for path in paths {
+        ensure!(path.starts_with('/'), "Invalid path '{path}' in the batch (expected an absolute path)");
+        let route = path.split('?').next().unwrap_or(path).trim_end_matches('/');
+        ensure!(route != BATCH_PATH, "Cannot nest a batch request in a batch");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_batch() {
+        let paths = |paths: &[&str]| paths.iter().map(|path| path.to_string()).collect::<Vec<_>>();
+
+        assert!(check_batch(&[], None).is_ok());
+        assert!(check_batch(&paths(&["/testnet3/block/1", "/testnet3/blocks?start=0&end=10"]), None).is_ok());
+        assert!(check_batch(&paths(&["/testnet3/block/1"; 2]), Some(2)).is_ok());
+        assert!(check_batch(&paths(&["/testnet3/block/1"; DEFAULT_MAX_BATCH_SIZE]), None).is_ok());
+
+        // Ensure oversized batches, relative paths, and nested batches are rejected.
+        assert!(check_batch(&paths(&["/testnet3/block/1"; DEFAULT_MAX_BATCH_SIZE + 1]), None).is_err());
+        assert!(check_batch(&paths(&["/testnet3/block/1"; 3]), Some(2)).is_err());
+        assert!(check_batch(&paths(&["testnet3/block/1"]), None).is_err());
+        assert!(check_batch(&paths(&["/testnet3/batch"]), None).is_err());
+        assert!(check_batch(&paths(&["/testnet3/batch/?x=1"]), None).is_err());
+    }
+}
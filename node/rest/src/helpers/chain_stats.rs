@@ -0,0 +1,138 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{ensure, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+/// The number of blocks over which the chain statistics are computed, if no window is given.
+pub const DEFAULT_CHAIN_STATS_WINDOW: u32 = 100;
+/// The maximum number of blocks over which the chain statistics are computed.
+pub const MAX_CHAIN_STATS_WINDOW: u32 = 1000;
+
+/// The most recently computed chain statistics, along with the hash of the latest block they cover.
+pub type ChainStatsCache = Mutex<Option<(String, ChainStats)>>;
+
+/// The statistics of the most recent blocks of the chain.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChainStats {
+    /// The number of blocks the statistics are computed over.
+    pub window: u32,
+    /// The height of the first block in the window.
+    pub start_height: u32,
+    /// The height of the last block in the window.
+    pub end_height: u32,
+    /// The average time in seconds in between blocks.
+    pub average_block_interval_secs: f64,
+    /// The number of transactions per second.
+    pub transactions_per_second: f64,
+    /// The average fraction of the maximum number of transactions that a block contains.
+    pub average_block_fullness: f64,
+}
+
+impl ChainStats {
+    /// Returns the chain statistics ending at the block with the given hash, computing them if they are not cached.
+    /// The given function returns the timestamp and number of transactions of the block at the given height.
+    pub fn load(
+        cache: &ChainStatsCache,
+        latest_hash: String,
+        latest_height: u32,
+        window: u32,
+        max_transactions: usize,
+        block_info: impl Fn(u32) -> Result<(i64, usize)>,
+    ) -> Result<Self> {
+        ensure!(window > 0, "The window must be greater than 0");
+        ensure!(window <= MAX_CHAIN_STATS_WINDOW, "The window must not exceed {MAX_CHAIN_STATS_WINDOW} blocks");
+        ensure!(latest_height > 0, "The chain statistics require at least one block after the genesis block");
+
+        // Bound the window by the number of blocks after the genesis block.
+        let window = window.min(latest_height);
+        // Return the cached statistics, if they cover the same blocks.
+        if let Some((hash, stats)) = &*cache.lock() {
+            if *hash == latest_hash && stats.window == window {
+                return Ok(stats.clone());
+            }
+        }
+
+        // Retrieve the blocks in the window, including the preceding block to measure the first interval.
+        let blocks = (latest_height - window..=latest_height).map(block_info).collect::<Result<Vec<_>>>()?;
+        let stats = Self::from_blocks(latest_height, &blocks, max_transactions);
+        *cache.lock() = Some((latest_hash, stats.clone()));
+        Ok(stats)
+    }
+
+    /// Returns the chain statistics of the given blocks, as pairs of the timestamp and number of transactions.
+    /// Note: The first block only marks the start of the window, and its transactions are not counted.
+    fn from_blocks(end_height: u32, blocks: &[(i64, usize)], max_transactions: usize) -> Self {
+        let window = blocks.len().saturating_sub(1) as u32;
+        let (start_timestamp, end_timestamp) = match (blocks.first(), blocks.last()) {
+            (Some((start, _)), Some((end, _))) => (*start, *end),
+            _ => (0, 0),
+        };
+        // Note: The elapsed time is bounded, as block timestamps are not guaranteed to increase.
+        let elapsed_secs = end_timestamp.saturating_sub(start_timestamp).max(0) as f64;
+        let num_transactions = blocks.iter().skip(1).map(|(_, num_transactions)| *num_transactions).sum::<usize>();
+
+        let (average_block_interval_secs, transactions_per_second, average_block_fullness) = match window {
+            0 => (0.0, 0.0, 0.0),
+            _ => (
+                elapsed_secs / window as f64,
+                if elapsed_secs > 0.0 { num_transactions as f64 / elapsed_secs } else { 0.0 },
+                num_transactions as f64 / (window as f64 * max_transactions.max(1) as f64),
+            ),
+        };
+
+        Self {
+            window,
+            start_height: end_height + 1 - window.max(1),
+            end_height,
+            average_block_interval_secs,
+            transactions_per_second,
+            average_block_fullness,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_stats() {
+        // Blocks every 10 seconds, with 0, 4, 2, and 6 transactions.
+        let blocks = [(1_000, 0), (1_010, 4), (1_020, 2), (1_030, 6)];
+        let stats = ChainStats::from_blocks(3, &blocks, 16);
+        assert_eq!(stats.window, 3);
+        assert_eq!(stats.start_height, 1);
+        assert_eq!(stats.end_height, 3);
+        assert_eq!(stats.average_block_interval_secs, 10.0);
+        assert_eq!(stats.transactions_per_second, 0.4);
+        assert_eq!(stats.average_block_fullness, 0.25);
+
+        // Ensure the statistics are cached, and recomputed for a new latest block.
+        let cache = ChainStatsCache::default();
+        let load =
+            |hash: &str| ChainStats::load(&cache, hash.to_string(), 3, 5, 16, |height| Ok(blocks[height as usize]));
+        assert_eq!(load("a").unwrap(), stats);
+        assert_eq!(
+            ChainStats::load(&cache, "a".to_string(), 3, 5, 16, |_| anyhow::bail!("Not cached")).unwrap(),
+            stats
+        );
+        assert!(ChainStats::load(&cache, "b".to_string(), 3, 5, 16, |_| anyhow::bail!("Not cached")).is_err());
+
+        // Ensure the window is bounded.
+        assert!(ChainStats::load(&cache, "a".to_string(), 3, 0, 16, |_| Ok((0, 0))).is_err());
+        assert!(ChainStats::load(&cache, "a".to_string(), 3, MAX_CHAIN_STATS_WINDOW + 1, 16, |_| Ok((0, 0))).is_err());
+    }
+}
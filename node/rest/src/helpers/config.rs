@@ -0,0 +1,70 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::CorsOrigins;
+use snarkos_node_sync::ValidationHistogram;
+
+use anyhow::{ensure, Result};
+use std::{path::PathBuf, sync::Arc};
+
+/// The configuration of a REST server.
+#[derive(Clone, Debug, Default)]
+pub struct RestConfig {
+    /// The maximum number of requests per second accepted from each IP, or `None` to not rate limit requests.
+    pub rate_limit: Option<u32>,
+    /// The maximum number of requests in a batch, or `None` for `DEFAULT_MAX_BATCH_SIZE` requests.
+    pub max_batch_size: Option<usize>,
+    /// The origins allowed to make cross-origin requests, or `None` to allow any origin.
+    pub cors_origins: Option<CorsOrigins>,
+    /// The certificate chain and private key files (both PEM-encoded) to serve HTTPS with,
+    /// or `None` to serve plaintext HTTP.
+    pub tls: Option<(PathBuf, PathBuf)>,
+    /// A static token accepted for the JWT-protected endpoints, in addition to the JWT, if any.
+    pub rest_token: Option<String>,
+    /// The token required to access the admin endpoints, or `None` to only serve them to localhost.
+    pub admin_token: Option<String>,
+    /// The path to the ledger storage reported by the storage endpoint, or `None` for an in-memory ledger.
+    pub storage_path: Option<PathBuf>,
+    /// The path to the crash info file reported by the crash info endpoint, or `None` if crash tracking is disabled.
+    pub crash_info_path: Option<PathBuf>,
+    /// The histogram of block validation timings reported by the validation histogram endpoint, if it is recorded.
+    pub validation_histogram: Option<Arc<ValidationHistogram>>,
+}
+
+impl RestConfig {
+    /// Ensures the configuration is well-formed.
+    pub fn check(&self) -> Result<()> {
+        ensure!(self.rate_limit != Some(0), "The REST rate limit must be greater than zero");
+        ensure!(self.max_batch_size != Some(0), "The maximum REST batch size must be greater than zero");
+        ensure!(self.rest_token.as_deref() != Some(""), "The REST token must not be empty");
+        ensure!(self.admin_token.as_deref() != Some(""), "The admin token must not be empty");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_rest_config() {
+        assert!(RestConfig::default().check().is_ok());
+        assert!(RestConfig { rate_limit: Some(10), max_batch_size: Some(5), ..Default::default() }.check().is_ok());
+
+        // Ensure zero limits and empty tokens are rejected.
+        assert!(RestConfig { rate_limit: Some(0), ..Default::default() }.check().is_err());
+        assert!(RestConfig { max_batch_size: Some(0), ..Default::default() }.check().is_err());
+        assert!(RestConfig { rest_token: Some(String::new()), ..Default::default() }.check().is_err());
+        assert!(RestConfig { admin_token: Some(String::new()), ..Default::default() }.check().is_err());
+    }
+}
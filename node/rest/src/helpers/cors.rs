@@ -0,0 +1,81 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{bail, Result};
+use axum::http::HeaderValue;
+use std::str::FromStr;
+use tower_http::cors::AllowOrigin;
+
+/// Returns the CORS policy for the given origins of the REST server, where `None` allows any origin.
+pub(crate) fn allow_origin(origins: Option<&CorsOrigins>) -> AllowOrigin {
+    match origins {
+        None | Some(CorsOrigins::Any) => AllowOrigin::any(),
+        Some(CorsOrigins::List(origins)) => AllowOrigin::list(origins.iter().cloned()),
+    }
+}
+
+/// The origins allowed to make cross-origin requests to the REST server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CorsOrigins {
+    /// Any origin is allowed.
+    Any,
+    /// Only the given origins (e.g. 'https://example.com') are allowed.
+    List(Vec<HeaderValue>),
+}
+
+impl FromStr for CorsOrigins {
+    type Err = anyhow::Error;
+
+    /// Parses either '*', or a comma-separated list of origins.
+    fn from_str(origins: &str) -> Result<Self> {
+        if origins.trim() == "*" {
+            return Ok(Self::Any);
+        }
+        let mut list = Vec::new();
+        for origin in origins.split(',').map(str::trim).filter(|origin| !origin.is_empty()) {
+            if !origin.starts_with("http://") && !origin.starts_with("https://") {
+                bail!("Invalid CORS origin '{origin}' (expected e.g. 'https://example.com', or '*')");
+            }
+            // Note: A browser sends the origin without a trailing slash, so it must be configured without one.
+            let origin = origin.trim_end_matches('/');
+            match HeaderValue::from_str(origin) {
+                Ok(origin) => list.push(origin),
+                Err(_) => bail!("Invalid CORS origin '{origin}'"),
+            }
+        }
+        match list.is_empty() {
+            true => bail!("The CORS origins must not be empty"),
+            false => Ok(Self::List(list)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cors_origins_from_str() {
+        assert_eq!(CorsOrigins::from_str("*").unwrap(), CorsOrigins::Any);
+        assert_eq!(
+            CorsOrigins::from_str("https://example.com/, http://localhost:3000").unwrap(),
+            CorsOrigins::List(vec![
+                HeaderValue::from_static("https://example.com"),
+                HeaderValue::from_static("http://localhost:3000"),
+            ])
+        );
+        assert!(CorsOrigins::from_str("").is_err());
+        assert!(CorsOrigins::from_str("example.com").is_err());
+    }
+}
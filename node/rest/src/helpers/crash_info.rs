@@ -0,0 +1,122 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The reason recorded for an unclean shutdown without a known cause (e.g. the process was killed).
+const UNKNOWN_REASON: &str = "unknown (the process was killed, or the machine lost power)";
+
+/// The record of the unclean shutdowns of the node, persisted across restarts.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrashInfo {
+    /// The number of unclean shutdowns.
+    pub num_unclean_shutdowns: u64,
+    /// The reason for the last unclean shutdown, if there was one.
+    pub last_reason: Option<String>,
+    /// The boolean flag for whether the node is running, i.e. it has not shut down cleanly since it started.
+    #[serde(default)]
+    is_running: bool,
+    /// The reason recorded for a crash of the current run (e.g. a panic), if any.
+    #[serde(default)]
+    pending_reason: Option<String>,
+}
+
+impl CrashInfo {
+    /// Loads the crash info from the given path, or returns an empty record if the file does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        match path.exists() {
+            true => Ok(serde_json::from_slice(&std::fs::read(path)?)?),
+            false => Ok(Self::default()),
+        }
+    }
+
+    /// Saves the crash info to the given path.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        Ok(std::fs::write(path, serde_json::to_vec_pretty(self)?)?)
+    }
+
+    /// Records the start of a run. If the previous run did not shut down cleanly, it is counted as a crash.
+    fn start(&mut self) {
+        if self.is_running {
+            self.num_unclean_shutdowns += 1;
+            self.last_reason = Some(self.pending_reason.take().unwrap_or_else(|| UNKNOWN_REASON.to_string()));
+        }
+        self.is_running = true;
+        self.pending_reason = None;
+    }
+
+    /// Records a clean shutdown of the current run.
+    fn stop(&mut self) {
+        self.is_running = false;
+        self.pending_reason = None;
+    }
+}
+
+/// Records the start of this run in the crash info file at the given path.
+/// Returns the crash info, including any unclean shutdown of the previous run.
+pub fn initialize_crash_info(path: &Path) -> Result<CrashInfo> {
+    let mut crash_info = CrashInfo::load(path)?;
+    crash_info.start();
+    crash_info.save(path)?;
+    Ok(crash_info)
+}
+
+/// Records the reason for a crash of the current run (e.g. a panic message) in the crash info file at the given path.
+/// The crash is counted on the next start, unless the node still shuts down cleanly.
+pub fn record_crash_reason(path: &Path, reason: String) {
+    update_crash_info(path, |crash_info| crash_info.pending_reason = Some(reason));
+}
+
+/// Records a clean shutdown of the current run in the crash info file at the given path.
+pub fn record_clean_shutdown(path: &Path) {
+    update_crash_info(path, CrashInfo::stop);
+}
+
+/// Applies the given update to the crash info file at the given path.
+fn update_crash_info(path: &Path, update: impl FnOnce(&mut CrashInfo)) {
+    let mut crash_info = CrashInfo::load(path).unwrap_or_default();
+    update(&mut crash_info);
+    if let Err(error) = crash_info.save(path) {
+        error!("Failed to update the crash info in '{}': {error}", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crash_info() {
+        let mut crash_info = CrashInfo::default();
+
+        // A clean run is not counted.
+        crash_info.start();
+        crash_info.stop();
+        crash_info.start();
+        assert_eq!(crash_info.num_unclean_shutdowns, 0);
+
+        // A run that did not shut down cleanly is counted, with its recorded reason.
+        crash_info.pending_reason = Some("panic: oops".to_string());
+        crash_info.start();
+        assert_eq!(crash_info.num_unclean_shutdowns, 1);
+        assert_eq!(crash_info.last_reason.as_deref(), Some("panic: oops"));
+
+        // Without a recorded reason, the reason is unknown.
+        crash_info.start();
+        assert_eq!(crash_info.num_unclean_shutdowns, 2);
+        assert_eq!(crash_info.last_reason.as_deref(), Some(UNKNOWN_REASON));
+    }
+}
@@ -15,5 +15,29 @@
 mod auth;
 pub use auth::*;
 
+mod batch;
+pub use batch::*;
+
+mod chain_stats;
+pub use chain_stats::*;
+
+mod config;
+pub use config::*;
+
+mod cors;
+pub use cors::*;
+
+mod crash_info;
+pub use crash_info::*;
+
 mod error;
 pub use error::*;
+
+mod rate_limit;
+pub use rate_limit::*;
+
+mod storage;
+pub use storage::*;
+
+mod tls;
+pub use tls::*;
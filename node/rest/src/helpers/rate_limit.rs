@@ -0,0 +1,129 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{Request, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use parking_lot::Mutex;
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The interval in seconds at which idle buckets are removed.
+const CLEANUP_INTERVAL_IN_SECS: u64 = 60;
+
+/// Rejects the request with '429 Too Many Requests' if its IP exceeds the rate limit.
+/// Note: This middleware runs before authentication, so it also limits attempts to guess the credentials.
+pub(crate) async fn rate_limit_middleware<B>(
+    State(limiter): State<Option<Arc<RateLimiter>>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Result<Response, StatusCode>
+where
+    B: Send,
+{
+    if let Some(limiter) = limiter {
+        if !limiter.check(addr.ip(), Instant::now()) {
+            debug!("Rate limited '{} {}' from '{addr}'", request.method(), request.uri());
+            return Err(StatusCode::TOO_MANY_REQUESTS);
+        }
+    }
+    Ok(next.run(request).await)
+}
+
+/// A rate limiter with a token bucket per IP, which holds up to one second of requests.
+pub(crate) struct RateLimiter {
+    /// The number of requests per second allowed for each IP.
+    requests_per_sec: f64,
+    /// The buckets of each IP, and the time of the last cleanup.
+    state: Mutex<(HashMap<IpAddr, Bucket>, Instant)>,
+}
+
+/// The token bucket of an IP.
+struct Bucket {
+    /// The number of requests the IP may make without waiting.
+    tokens: f64,
+    /// The time the tokens were last updated.
+    updated: Instant,
+}
+
+impl RateLimiter {
+    /// Initializes a new rate limiter with the given number of requests per second for each IP.
+    pub(crate) fn new(requests_per_sec: u32) -> Self {
+        Self { requests_per_sec: f64::from(requests_per_sec), state: Mutex::new((HashMap::new(), Instant::now())) }
+    }
+
+    /// Takes a token from the bucket of the given IP at the given time, and returns `false` if there is none.
+    fn check(&self, ip: IpAddr, now: Instant) -> bool {
+        let mut state = self.state.lock();
+        let (buckets, last_cleanup) = &mut *state;
+
+        // Remove the buckets that have refilled, as they are equivalent to a new bucket.
+        let full_after = Duration::from_secs(1);
+        if now.saturating_duration_since(*last_cleanup) >= Duration::from_secs(CLEANUP_INTERVAL_IN_SECS) {
+            buckets.retain(|_, bucket| now.saturating_duration_since(bucket.updated) < full_after);
+            *last_cleanup = now;
+        }
+
+        // Refill the bucket for the time since its last update, up to its capacity.
+        let bucket = buckets.entry(ip).or_insert(Bucket { tokens: self.requests_per_sec, updated: now });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.requests_per_sec).min(self.requests_per_sec);
+        bucket.updated = now;
+
+        // Take a token, if there is one.
+        match bucket.tokens >= 1.0 {
+            true => {
+                bucket.tokens -= 1.0;
+                true
+            }
+            false => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::new(2);
+        let (ip, other_ip) = (IpAddr::V4(Ipv4Addr::LOCALHOST), IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+        let start = Instant::now();
+
+        // Ensure each IP may make the given number of requests per second.
+        assert!(limiter.check(ip, start));
+        assert!(limiter.check(ip, start));
+        assert!(!limiter.check(ip, start));
+        assert!(limiter.check(other_ip, start));
+        // Ensure the bucket refills over time.
+        assert!(limiter.check(ip, start + Duration::from_millis(500)));
+        assert!(!limiter.check(ip, start + Duration::from_millis(500)));
+
+        // Ensure the idle buckets are removed on cleanup.
+        let later = start + Duration::from_secs(CLEANUP_INTERVAL_IN_SECS);
+        assert!(limiter.check(ip, later));
+        assert_eq!(limiter.state.lock().0.len(), 1);
+    }
+}
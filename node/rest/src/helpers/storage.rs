@@ -0,0 +1,89 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The statistics of the ledger storage, and the filesystem it resides on.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageStats {
+    /// The size of the ledger storage in bytes.
+    pub ledger_size: u64,
+    /// The space in bytes that is available on the filesystem, if known.
+    pub available_space: Option<u64>,
+    /// The total space in bytes of the filesystem, if known.
+    pub total_space: Option<u64>,
+}
+
+impl StorageStats {
+    /// Returns the statistics of the ledger storage at the given path.
+    pub fn load(path: &Path) -> Result<Self> {
+        let ledger_size = directory_size(path)?;
+        let (available_space, total_space) = filesystem_space(path)?;
+        Ok(Self { ledger_size, available_space, total_space })
+    }
+}
+
+/// Returns the total size in bytes of the files in the given directory, recursively.
+fn directory_size(path: &Path) -> Result<u64> {
+    let mut size = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        match metadata.is_dir() {
+            true => size += directory_size(&entry.path())?,
+            false => size += metadata.len(),
+        }
+    }
+    Ok(size)
+}
+
+/// Returns the available and total space in bytes of the filesystem containing the given path.
+#[cfg(target_family = "unix")]
+fn filesystem_space(path: &Path) -> Result<(Option<u64>, Option<u64>)> {
+    let stats = nix::sys::statvfs::statvfs(path)?;
+    let fragment_size = stats.fragment_size() as u64;
+    let available_space = (stats.blocks_available() as u64).saturating_mul(fragment_size);
+    let total_space = (stats.blocks() as u64).saturating_mul(fragment_size);
+    Ok((Some(available_space), Some(total_space)))
+}
+
+/// Returns the available and total space in bytes of the filesystem containing the given path.
+#[cfg(not(target_family = "unix"))]
+fn filesystem_space(_path: &Path) -> Result<(Option<u64>, Option<u64>)> {
+    Ok((None, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directory_size() {
+        let path = std::env::temp_dir().join(format!("snarkos-rest-storage-{}", std::process::id()));
+        std::fs::create_dir_all(path.join("nested")).unwrap();
+        std::fs::write(path.join("a"), [0u8; 10]).unwrap();
+        std::fs::write(path.join("nested").join("b"), [0u8; 32]).unwrap();
+
+        assert_eq!(directory_size(&path).unwrap(), 42);
+        #[cfg(target_family = "unix")]
+        {
+            let (available_space, total_space) = filesystem_space(&path).unwrap();
+            assert!(available_space.unwrap() <= total_space.unwrap());
+        }
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
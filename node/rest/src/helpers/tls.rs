@@ -0,0 +1,202 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{bail, Result};
+use axum::extract::connect_info::Connected;
+use parking_lot::RwLock;
+use rustls_pemfile::Item;
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::TcpListener,
+    sync::mpsc,
+    task::JoinHandle,
+};
+use tokio_rustls::{
+    rustls::{Certificate, PrivateKey, ServerConfig},
+    TlsAcceptor,
+};
+
+/// The maximum number of seconds a client may take to complete the TLS handshake.
+const TLS_HANDSHAKE_TIMEOUT_IN_SECS: u64 = 10;
+/// The initial number of milliseconds to wait after a failed accept (e.g. on reaching the open files limit).
+const MIN_ACCEPT_BACKOFF_IN_MS: u64 = 10;
+/// The maximum number of milliseconds to wait after consecutive failed accepts.
+const MAX_ACCEPT_BACKOFF_IN_MS: u64 = 1000;
+
+/// The TLS configuration of the REST server, along with the files it is loaded from.
+pub(crate) struct TlsConfig {
+    /// The path to the certificate chain.
+    cert_path: PathBuf,
+    /// The path to the private key.
+    key_path: PathBuf,
+    /// The current server configuration, which is replaced when the certificate is reloaded.
+    config: RwLock<Arc<ServerConfig>>,
+}
+
+impl TlsConfig {
+    /// Loads the certificate chain and private key (both PEM-encoded) with which the REST server serves HTTPS.
+    pub(crate) fn load(cert_path: &Path, key_path: &Path) -> Result<Self> {
+        let config = RwLock::new(Arc::new(load_server_config(cert_path, key_path)?));
+        Ok(Self { cert_path: cert_path.to_path_buf(), key_path: key_path.to_path_buf(), config })
+    }
+
+    /// Reloads the certificate chain and private key from their files, e.g. after a rotation.
+    /// The new certificate is only used if it is valid, and only for the new connections,
+    /// so the existing connections keep their certificate until they close.
+    pub(crate) fn reload(&self) -> Result<()> {
+        let config = load_server_config(&self.cert_path, &self.key_path)?;
+        *self.config.write() = Arc::new(config);
+        info!("Reloaded the REST TLS certificate from '{}'", self.cert_path.display());
+        Ok(())
+    }
+
+    /// Returns the TLS acceptor with the current certificate.
+    fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(self.config.read().clone())
+    }
+}
+
+/// Loads the server configuration from the given certificate chain and private key files.
+fn load_server_config(cert_path: &Path, key_path: &Path) -> Result<ServerConfig> {
+    // Load the certificate chain.
+    let certs = match rustls_pemfile::certs(&mut open_pem(cert_path)?) {
+        Ok(certs) if certs.is_empty() => bail!("The TLS certificate file '{}' is empty", cert_path.display()),
+        Ok(certs) => certs.into_iter().map(Certificate).collect(),
+        Err(error) => bail!("Failed to parse the TLS certificate file '{}' - {error}", cert_path.display()),
+    };
+    // Load the private key, which may be encoded as PKCS #8, PKCS #1 (RSA), or SEC1 (EC).
+    let key = match rustls_pemfile::read_all(&mut open_pem(key_path)?) {
+        Ok(items) => items.into_iter().find_map(|item| match item {
+            Item::PKCS8Key(key) | Item::RSAKey(key) | Item::ECKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        }),
+        Err(error) => bail!("Failed to parse the TLS key file '{}' - {error}", key_path.display()),
+    };
+    let Some(key) = key else { bail!("The TLS key file '{}' does not contain a private key", key_path.display()) };
+
+    match ServerConfig::builder().with_safe_defaults().with_no_client_auth().with_single_cert(certs, key) {
+        Ok(config) => Ok(config),
+        Err(error) => bail!("The TLS certificate and key are invalid - {error}"),
+    }
+}
+
+/// Opens the PEM file at the given path.
+fn open_pem(path: &Path) -> Result<BufReader<File>> {
+    match File::open(path) {
+        Ok(file) => Ok(BufReader::new(file)),
+        Err(error) => bail!("Failed to open '{}' - {error}", path.display()),
+    }
+}
+
+/// A listener that accepts TCP connections to the REST server, and completes their TLS handshakes.
+pub(crate) struct TlsListener {
+    /// The receiver of the connections that completed their TLS handshake.
+    receiver: mpsc::Receiver<TlsConnection>,
+}
+
+impl TlsListener {
+    /// Initializes a new listener, spawning the task that accepts the connections on the given TCP listener,
+    /// and returns the listener along with the handle of that task. The task stops once the listener is dropped.
+    /// Each handshake runs in its own task, so a slow client does not delay the other connections.
+    /// Each connection uses the current certificate of the given TLS configuration, as of its acceptance.
+    pub(crate) fn new(listener: TcpListener, tls: Arc<TlsConfig>) -> (Self, JoinHandle<()>) {
+        let (sender, receiver) = mpsc::channel(64);
+        let handle = tokio::spawn(async move {
+            let mut backoff_in_ms = MIN_ACCEPT_BACKOFF_IN_MS;
+            loop {
+                let (stream, remote_addr) = match listener.accept().await {
+                    // Stop accepting once the server has dropped the listener.
+                    _ if sender.is_closed() => break,
+                    Ok(connection) => {
+                        backoff_in_ms = MIN_ACCEPT_BACKOFF_IN_MS;
+                        connection
+                    }
+                    Err(error) => {
+                        // Back off, as the error usually persists (e.g. 'EMFILE'), and retrying at once would spin.
+                        warn!("Failed to accept a REST connection (retrying in {backoff_in_ms}ms) - {error}");
+                        tokio::time::sleep(Duration::from_millis(backoff_in_ms)).await;
+                        backoff_in_ms = (backoff_in_ms * 2).min(MAX_ACCEPT_BACKOFF_IN_MS);
+                        continue;
+                    }
+                };
+                let acceptor = tls.acceptor();
+                let sender = sender.clone();
+                tokio::spawn(async move {
+                    let timeout = Duration::from_secs(TLS_HANDSHAKE_TIMEOUT_IN_SECS);
+                    match tokio::time::timeout(timeout, acceptor.accept(stream)).await {
+                        Ok(Ok(stream)) => {
+                            let _ = sender.send(TlsConnection { stream, remote_addr }).await;
+                        }
+                        Ok(Err(error)) => debug!("Failed the TLS handshake with '{remote_addr}' - {error}"),
+                        Err(_) => debug!("Timed out the TLS handshake with '{remote_addr}'"),
+                    }
+                });
+            }
+        });
+        (Self { receiver }, handle)
+    }
+}
+
+impl hyper::server::accept::Accept for TlsListener {
+    type Conn = TlsConnection;
+    type Error = io::Error;
+
+    fn poll_accept(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Self::Conn, Self::Error>>> {
+        self.get_mut().receiver.poll_recv(cx).map(|connection| connection.map(Ok))
+    }
+}
+
+/// A TLS connection to the REST server.
+pub(crate) struct TlsConnection {
+    /// The TLS stream.
+    stream: tokio_rustls::server::TlsStream<tokio::net::TcpStream>,
+    /// The address of the client.
+    remote_addr: SocketAddr,
+}
+
+impl Connected<&TlsConnection> for SocketAddr {
+    fn connect_info(connection: &TlsConnection) -> Self {
+        connection.remote_addr
+    }
+}
+
+impl AsyncRead for TlsConnection {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for TlsConnection {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_shutdown(cx)
+    }
+}
@@ -17,6 +17,9 @@
 #[macro_use]
 extern crate tracing;
 
+mod explorer;
+pub use explorer::*;
+
 mod helpers;
 pub use helpers::*;
 
@@ -35,8 +38,15 @@ use snarkvm::{
 
 use anyhow::Result;
 use axum::{
+    body::Body,
     extract::{ConnectInfo, DefaultBodyLimit, Path, Query, State},
-    http::{header::CONTENT_TYPE, Method, Request, StatusCode},
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE},
+        HeaderMap,
+        Method,
+        Request,
+        StatusCode,
+    },
     middleware,
     middleware::Next,
     response::Response,
@@ -44,13 +54,14 @@ use axum::{
     Json,
 };
 use axum_extra::response::ErasedJson;
+use once_cell::sync::OnceCell;
 use parking_lot::Mutex;
-use std::{net::SocketAddr, sync::Arc};
-use tokio::task::JoinHandle;
-use tower_http::{
-    cors::{Any, CorsLayer},
-    trace::TraceLayer,
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Weak},
 };
+use tokio::{net::TcpListener, task::JoinHandle};
+use tower_http::{cors::CorsLayer, trace::TraceLayer};
 
 /// A REST API server for the ledger.
 #[derive(Clone)]
@@ -63,18 +74,47 @@ pub struct Rest<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
     routing: Arc<R>,
     /// The server handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    /// The server router, which dispatches the requests of a batch.
+    /// Note: The reference is weak, as the router holds the server state, so a strong one would leak both.
+    router: Arc<OnceCell<Weak<axum::Router>>>,
+    /// The server configuration.
+    config: Arc<RestConfig>,
+    /// The per-IP rate limiter, if a rate limit is set.
+    rate_limiter: Option<Arc<RateLimiter>>,
+    /// The TLS configuration, if the server serves HTTPS.
+    tls: Option<Arc<TlsConfig>>,
+    /// The cache of the most recently computed chain statistics.
+    chain_stats: Arc<ChainStatsCache>,
 }
 
 impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
-    /// Initializes a new instance of the server.
+    /// Initializes a new instance of the server, with the given configuration.
     pub fn start(
         rest_ip: SocketAddr,
         consensus: Option<Consensus<N>>,
         ledger: Ledger<N, C>,
         routing: Arc<R>,
+        config: RestConfig,
     ) -> Result<Self> {
+        // Ensure the configuration is well-formed, and load the TLS certificate, if one is given.
+        config.check()?;
+        let rate_limiter = config.rate_limit.map(|requests_per_sec| Arc::new(RateLimiter::new(requests_per_sec)));
+        let tls = match &config.tls {
+            Some((cert_path, key_path)) => Some(Arc::new(TlsConfig::load(cert_path, key_path)?)),
+            None => None,
+        };
         // Initialize the server.
-        let mut server = Self { consensus, ledger, routing, handles: Default::default() };
+        let mut server = Self {
+            consensus,
+            ledger,
+            routing,
+            handles: Default::default(),
+            router: Default::default(),
+            config: Arc::new(config),
+            rate_limiter,
+            tls,
+            chain_stats: Default::default(),
+        };
         // Spawn the server.
         server.spawn_server(rest_ip);
         // Return the server.
@@ -92,21 +132,38 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     pub const fn handles(&self) -> &Arc<Mutex<Vec<JoinHandle<()>>>> {
         &self.handles
     }
+
+    /// Reloads the TLS certificate and key from their files, e.g. after a rotation,
+    /// returning `false` if the server does not serve HTTPS.
+    pub fn reload_tls_config(&self) -> Result<bool> {
+        match &self.tls {
+            Some(tls) => tls.reload().map(|_| true),
+            None => Ok(false),
+        }
+    }
 }
 
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     fn spawn_server(&mut self, rest_ip: SocketAddr) {
         let cors = CorsLayer::new()
-            .allow_origin(Any)
+            .allow_origin(allow_origin(self.config.cors_origins.as_ref()))
             .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
             .allow_headers([CONTENT_TYPE]);
 
+        // The admin endpoints are protected with the admin token (or restricted to localhost).
+        let admin_router = axum::Router::new()
+            .route("/testnet3/admin/peers/ban/:ip", post(Self::admin_ban_peer))
+            .route("/testnet3/admin/peers/disconnect/:ip", post(Self::admin_disconnect_peer))
+            .route("/testnet3/admin/transaction/prioritize/:id", post(Self::admin_prioritize_transaction))
+            .route_layer(middleware::from_fn_with_state(self.config.clone(), admin_middleware));
+
         let router = {
             axum::Router::new()
 
             // All the endpoints before the call to `route_layer` are protected with JWT auth.
             .route("/testnet3/node/address", get(Self::get_node_address))
-            .route_layer(middleware::from_fn(auth_middleware))
+            .route("/testnet3/peers/connected", get(Self::get_peers_connected))
+            .route_layer(middleware::from_fn_with_state(self.config.clone(), auth_middleware))
 
             // ----------------- DEPRECATED ROUTES -----------------
             // The following `GET ../latest/..` routes will be removed before mainnet.
@@ -124,6 +181,9 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             .route("/testnet3/latest/committee", get(Self::latest_committee))
             // ------------------------------------------------------
 
+            // GET /version
+            .route("/version", get(Self::get_version))
+
             // GET ../block/..
             .route("/testnet3/block/height/latest", get(Self::get_block_height_latest))
             .route("/testnet3/block/hash/latest", get(Self::get_block_hash_latest))
@@ -148,6 +208,9 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             .route("/testnet3/peers/count", get(Self::get_peers_count))
             .route("/testnet3/peers/all", get(Self::get_peers_all))
             .route("/testnet3/peers/all/metrics", get(Self::get_peers_all_metrics))
+            .route("/testnet3/peers/all/details", get(Self::get_peers_all_details))
+            .route("/testnet3/peers/all/messages", get(Self::get_peers_all_messages))
+            .route("/testnet3/peers/all/reconnects", get(Self::get_peers_all_reconnects))
 
             // GET ../program/..
             .route("/testnet3/program/:id", get(Self::get_program))
@@ -155,19 +218,35 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             .route("/testnet3/program/:id/mapping/:name/:key", get(Self::get_mapping_value))
 
             // GET misc endpoints.
+            .route("/testnet3/genesis", get(Self::get_genesis))
             .route("/testnet3/blocks", get(Self::get_blocks))
             .route("/testnet3/height/:hash", get(Self::get_height))
             .route("/testnet3/memoryPool/transmissions", get(Self::get_memory_pool_transmissions))
             .route("/testnet3/memoryPool/solutions", get(Self::get_memory_pool_solutions))
             .route("/testnet3/memoryPool/transactions", get(Self::get_memory_pool_transactions))
+            .route("/testnet3/memoryPool/info", get(Self::get_memory_pool_info))
+            .route("/testnet3/memoryPool/contains/:id", get(Self::get_memory_pool_contains_transaction))
+            .route("/testnet3/networkInfo", get(Self::get_network_info))
+            .route("/testnet3/chainStats", get(Self::get_chain_stats))
+            .route("/testnet3/crashInfo", get(Self::get_crash_info))
+            .route("/testnet3/storageInfo", get(Self::get_storage_info))
+            .route("/testnet3/validationHistogram", get(Self::get_validation_histogram))
             .route("/testnet3/statePath/:commitment", get(Self::get_state_path_for_commitment))
             .route("/testnet3/stateRoot/latest", get(Self::get_state_root_latest))
             .route("/testnet3/committee/latest", get(Self::get_committee_latest))
 
+            // POST /testnet3/batch
+            .route(BATCH_PATH, post(Self::batch))
+
+            // POST ../admin/..
+            .merge(admin_router)
+
             // Pass in `Rest` to make things convenient.
             .with_state(self.clone())
             // Enable tower-http tracing.
             .layer(TraceLayer::new_for_http())
+            // Rate limit each IP, before authentication.
+            .layer(middleware::from_fn_with_state(self.rate_limiter.clone(), rate_limit_middleware))
             // Custom logging.
             .layer(middleware::from_fn(log_middleware))
             // Enable CORS.
@@ -176,11 +255,27 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             .layer(DefaultBodyLimit::max(10 * 1024 * 1024))
         };
 
+        // Keep a reference to the router, to dispatch the requests of a batch through it.
+        // Note: The server task holds the router, so the reference is valid while the server runs.
+        let router = Arc::new(router);
+        let _ = self.router.set(Arc::downgrade(&router));
+
+        let tls = self.tls.clone();
+        let handles = self.handles.clone();
         self.handles.lock().push(tokio::spawn(async move {
-            axum::Server::bind(&rest_ip)
-                .serve(router.into_make_service_with_connect_info::<SocketAddr>())
-                .await
-                .expect("couldn't start rest server");
+            let make_service = axum::Router::clone(&router).into_make_service_with_connect_info::<SocketAddr>();
+            match tls {
+                // If a TLS configuration is set, serve HTTPS.
+                Some(tls) => {
+                    let listener = TcpListener::bind(rest_ip).await.expect("couldn't bind the rest server");
+                    // Keep the handle of the accept task next to the server task, so that both stop on shutdown.
+                    let (listener, accept_handle) = TlsListener::new(listener, tls);
+                    handles.lock().push(accept_handle);
+                    axum::Server::builder(listener).serve(make_service).await.expect("couldn't start rest server");
+                }
+                // Otherwise, serve plaintext HTTP.
+                None => axum::Server::bind(&rest_ip).serve(make_service).await.expect("couldn't start rest server"),
+            }
         }))
     }
 }
@@ -194,6 +289,13 @@ where
     B: Send,
 {
     info!("Received '{} {}' from '{addr}'", request.method(), request.uri());
+    // Count the request by its route, rather than its full path, to bound the number of labels.
+    #[cfg(feature = "metrics")]
+    {
+        let path = request.extensions().get::<axum::extract::MatchedPath>().map_or("unmatched", |path| path.as_str());
+        let (method, path) = (request.method().to_string(), path.to_string());
+        metrics::increment_counter!(metrics::rest::REQUESTS, "method" => method, "path" => path);
+    }
 
     Ok(next.run(request).await)
 }
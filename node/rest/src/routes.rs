@@ -13,12 +13,19 @@
 // limitations under the License.
 
 use super::*;
-use snarkvm::prelude::{block::Transaction, Identifier, Plaintext};
+use snarkvm::prelude::{
+    block::{Transaction, Transactions},
+    Identifier,
+    Plaintext,
+    ToBytes,
+};
 
 use indexmap::IndexMap;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::time::Duration;
+use tower::ServiceExt;
 
 /// The `get_blocks` query object.
 #[derive(Deserialize, Serialize)]
@@ -35,6 +42,36 @@ pub(crate) struct Metadata {
     metadata: bool,
 }
 
+/// The `get_genesis` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct Parameters {
+    #[serde(default)]
+    parameters: bool,
+}
+
+/// The `get_chain_stats` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct ChainStatsWindow {
+    /// The number of most recent blocks to compute the statistics over.
+    #[serde(default = "default_chain_stats_window")]
+    window: u32,
+}
+
+/// The `admin_disconnect_peer` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct DisconnectOptions {
+    /// The number of seconds to restrict the peer for after disconnecting, if any.
+    ban_secs: Option<u64>,
+}
+
+/// Returns the default window of `get_chain_stats`.
+const fn default_chain_stats_window() -> u32 {
+    DEFAULT_CHAIN_STATS_WINDOW
+}
+
+/// The maximum size in bytes of a genesis block served by `get_genesis`.
+const MAX_GENESIS_SIZE_IN_BYTES: usize = 16 * 1024 * 1024; // 16 MiB
+
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     // ----------------- DEPRECATED FUNCTIONS -----------------
     // The functions below are associated with deprecated routes.
@@ -108,6 +145,34 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         Ok(ErasedJson::pretty(block))
     }
 
+    // GET /testnet3/genesis
+    // GET /testnet3/genesis?parameters={true}
+    // Returns the genesis block, in the same JSON format as `/testnet3/block/0`. If `parameters` is set,
+    // the block is returned as `{ "network_id", "network", "genesis_hash", "block" }`, so a client can
+    // check that it is on the expected network. Genesis blocks above `MAX_GENESIS_SIZE_IN_BYTES` are refused.
+    pub(crate) async fn get_genesis(
+        State(rest): State<Self>,
+        Query(parameters): Query<Parameters>,
+    ) -> Result<ErasedJson, RestError> {
+        let block = rest.ledger.get_block(0)?;
+
+        // Ensure the genesis block is within the size bound.
+        let size = block.to_bytes_le()?.len();
+        if size > MAX_GENESIS_SIZE_IN_BYTES {
+            return Err(RestError(format!("The genesis block is too large to serve ({size} bytes)")));
+        }
+
+        match parameters.parameters {
+            true => Ok(ErasedJson::pretty(json!({
+                "network_id": N::ID,
+                "network": N::NAME,
+                "genesis_hash": block.hash(),
+                "block": block,
+            }))),
+            false => Ok(ErasedJson::pretty(block)),
+        }
+    }
+
     // GET /testnet3/blocks?start={start_height}&end={end_height}
     pub(crate) async fn get_blocks(
         State(rest): State<Self>,
@@ -196,6 +261,33 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         }
     }
 
+    // GET /testnet3/memoryPool/info
+    pub(crate) async fn get_memory_pool_info(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        match rest.consensus {
+            Some(consensus) => Ok(ErasedJson::pretty(json!({
+                "transactions": consensus.num_unconfirmed_transactions(),
+                "queued_transactions": consensus.num_queued_transactions(),
+                "solutions": consensus.num_unconfirmed_solutions(),
+                "transactions_size_in_bytes": consensus.unconfirmed_transactions_size_in_bytes()?,
+                "oldest_transaction_age_secs": consensus.oldest_unconfirmed_transaction_age().map(|age| age.as_secs()),
+                "transmissions": consensus.num_unconfirmed_transmissions(),
+                "max_transmissions": consensus.max_unconfirmed_transmissions(),
+            }))),
+            None => Err(RestError("Route isn't available for this node type".to_string())),
+        }
+    }
+
+    // GET /testnet3/memoryPool/contains/{transactionID}
+    pub(crate) async fn get_memory_pool_contains_transaction(
+        State(rest): State<Self>,
+        Path(tx_id): Path<N::TransactionID>,
+    ) -> Result<ErasedJson, RestError> {
+        match rest.consensus {
+            Some(consensus) => Ok(ErasedJson::pretty(consensus.contains_unconfirmed_transaction(tx_id))),
+            None => Err(RestError("Route isn't available for this node type".to_string())),
+        }
+    }
+
     // GET /testnet3/program/{programID}
     pub(crate) async fn get_program(
         State(rest): State<Self>,
@@ -267,11 +359,149 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         ErasedJson::pretty(rest.routing.router().connected_metrics())
     }
 
+    // GET /testnet3/peers/all/details
+    pub(crate) async fn get_peers_all_details(State(rest): State<Self>) -> ErasedJson {
+        let peers = rest
+            .routing
+            .router()
+            .get_connected_peers()
+            .iter()
+            .map(|peer| {
+                json!({
+                    "ip": peer.ip(),
+                    "address": peer.address(),
+                    "node_type": peer.node_type(),
+                    "version": peer.version(),
+                    "is_outbound": peer.is_outbound(),
+                    "connected_secs": peer.first_seen().elapsed().as_secs(),
+                    "last_seen_secs": peer.last_seen().elapsed().as_secs(),
+                })
+            })
+            .collect::<Vec<_>>();
+        ErasedJson::pretty(peers)
+    }
+
+    // GET /testnet3/peers/all/messages
+    pub(crate) async fn get_peers_all_messages(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.routing.router().connected_message_stats())
+    }
+
+    // GET /testnet3/peers/all/reconnects
+    pub(crate) async fn get_peers_all_reconnects(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.routing.router().reconnect_statuses())
+    }
+
+    // GET /testnet3/peers/connected
+    pub(crate) async fn get_peers_connected(State(rest): State<Self>) -> ErasedJson {
+        let router = rest.routing.router();
+        let peers = router
+            .get_connected_peers()
+            .iter()
+            .map(|peer| {
+                json!({
+                    "ip": peer.ip(),
+                    "node_type": peer.node_type(),
+                    "last_seen_secs": peer.last_seen().elapsed().as_secs(),
+                })
+            })
+            .collect::<Vec<_>>();
+        // Include the listener IP of this node, to distinguish it from its peers.
+        ErasedJson::pretty(json!({ "local_ip": router.local_ip(), "peers": peers }))
+    }
+
+    // GET /testnet3/networkInfo
+    pub(crate) async fn get_network_info(State(rest): State<Self>) -> ErasedJson {
+        let router = rest.routing.router();
+        let (messages_sent, bytes_sent) = router.stats().sent();
+        let (messages_received, bytes_received) = router.stats().received();
+        ErasedJson::pretty(json!({
+            "peers": {
+                "connected": router.number_of_connected_peers(),
+                "validators": router.number_of_connected_validators(),
+                "provers": router.number_of_connected_provers(),
+                "clients": router.number_of_connected_clients(),
+                "candidates": router.number_of_candidate_peers(),
+                "restricted": router.number_of_restricted_peers(),
+            },
+            "latest_height": rest.ledger.latest_height(),
+            "greatest_peer_height": router.greatest_peer_height(),
+            "is_synced": router.is_synced(),
+            "messages_sent": messages_sent,
+            "bytes_sent": bytes_sent,
+            "messages_received": messages_received,
+            "bytes_received": bytes_received,
+            "uptime_secs": router.uptime().as_secs(),
+        }))
+    }
+
+    // GET /testnet3/chainStats
+    // GET /testnet3/chainStats?window={window}
+    // Returns the average block interval, transactions per second, and block fullness over the last `window` blocks
+    // (default `DEFAULT_CHAIN_STATS_WINDOW`, at most `MAX_CHAIN_STATS_WINDOW`). The result is cached until a new block.
+    pub(crate) async fn get_chain_stats(
+        State(rest): State<Self>,
+        Query(parameters): Query<ChainStatsWindow>,
+    ) -> Result<ErasedJson, RestError> {
+        // Note: The block reads are blocking, so they run off the async runtime.
+        let stats = tokio::task::spawn_blocking(move || {
+            let latest_block = rest.ledger.latest_block();
+            ChainStats::load(
+                &rest.chain_stats,
+                latest_block.hash().to_string(),
+                latest_block.height(),
+                parameters.window,
+                Transactions::<N>::MAX_TRANSACTIONS,
+                |height| {
+                    let block = rest.ledger.get_block(height)?;
+                    Ok((block.timestamp(), block.transactions().len()))
+                },
+            )
+        })
+        .await
+        .map_err(|error| RestError(format!("Failed to compute the chain statistics - {error}")))??;
+        Ok(ErasedJson::pretty(stats))
+    }
+
+    // GET /testnet3/crashInfo
+    pub(crate) async fn get_crash_info(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        match &rest.config.crash_info_path {
+            Some(path) => Ok(ErasedJson::pretty(CrashInfo::load(path)?)),
+            None => Err(RestError("Crash tracking is not enabled on this node".to_string())),
+        }
+    }
+
+    // GET /testnet3/storageInfo
+    pub(crate) async fn get_storage_info(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        match &rest.config.storage_path {
+            Some(path) => Ok(ErasedJson::pretty(StorageStats::load(path)?)),
+            None => Err(RestError("The node does not store its ledger on disk".to_string())),
+        }
+    }
+
+    // GET /testnet3/validationHistogram
+    // Returns the distribution of block validation timings for each phase, if the node records them.
+    pub(crate) async fn get_validation_histogram(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        match &rest.config.validation_histogram {
+            Some(histogram) => Ok(ErasedJson::pretty(histogram.snapshot())),
+            None => Err(RestError("Validation timings are not recorded on this node".to_string())),
+        }
+    }
+
     // GET /testnet3/node/address
     pub(crate) async fn get_node_address(State(rest): State<Self>) -> ErasedJson {
         ErasedJson::pretty(rest.routing.router().address())
     }
 
+    // GET /version
+    pub(crate) async fn get_version(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "commit": env!("SNARKOS_GIT_COMMIT"),
+            "network": N::NAME,
+            "node_type": rest.routing.router().node_type(),
+        }))
+    }
+
     // GET /testnet3/find/blockHash/{transactionID}
     pub(crate) async fn find_block_hash(
         State(rest): State<Self>,
@@ -304,6 +534,54 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         Ok(ErasedJson::pretty(rest.ledger.find_transition_id(&input_or_output_id)?))
     }
 
+    // POST /testnet3/admin/peers/ban/{ip}
+    pub(crate) async fn admin_ban_peer(
+        State(rest): State<Self>,
+        Path(peer_ip): Path<SocketAddr>,
+    ) -> Result<ErasedJson, RestError> {
+        let router = rest.routing.router();
+        // Disconnect from the peer, before restricting it (as disconnecting returns it to the candidate peers).
+        let was_connected = router.disconnect(peer_ip).await.map_err(|error| RestError(error.to_string()))?;
+        router.insert_restricted_peer(peer_ip);
+        info!("Banned '{peer_ip}' via the admin API");
+        Ok(ErasedJson::pretty(json!({ "peer": peer_ip, "was_connected": was_connected })))
+    }
+
+    // POST /testnet3/admin/peers/disconnect/{ip}
+    // POST /testnet3/admin/peers/disconnect/{ip}?ban_secs={ban_secs}
+    pub(crate) async fn admin_disconnect_peer(
+        State(rest): State<Self>,
+        Path(peer_ip): Path<SocketAddr>,
+        Query(options): Query<DisconnectOptions>,
+    ) -> Result<ErasedJson, RestError> {
+        let router = rest.routing.router();
+        // Ensure the peer is connected.
+        if !router.is_connected(&peer_ip) {
+            return Err(RestError(format!("Peer '{peer_ip}' is not connected")));
+        }
+        // Disconnect from the peer, before restricting it (as disconnecting returns it to the candidate peers).
+        router.disconnect(peer_ip).await.map_err(|error| RestError(error.to_string()))?;
+        if let Some(ban_secs) = options.ban_secs {
+            router.insert_restricted_peer_for(peer_ip, Duration::from_secs(ban_secs));
+        }
+        info!("Disconnected '{peer_ip}' via the admin API (banned for {}s)", options.ban_secs.unwrap_or(0));
+        Ok(ErasedJson::pretty(json!({ "peer": peer_ip, "ban_secs": options.ban_secs })))
+    }
+
+    // POST /testnet3/admin/transaction/prioritize/{transactionID}
+    pub(crate) async fn admin_prioritize_transaction(
+        State(rest): State<Self>,
+        Path(tx_id): Path<N::TransactionID>,
+    ) -> Result<ErasedJson, RestError> {
+        // Ensure the node is a validator, as only validators propose the transmissions for blocks.
+        let Some(consensus) = rest.consensus else {
+            return Err(RestError("Only a validator can prioritize transactions".to_string()));
+        };
+        consensus.prioritize_transaction(tx_id).await?;
+        info!("Prioritized transaction '{tx_id}' via the admin API");
+        Ok(ErasedJson::pretty(tx_id))
+    }
+
     // POST /testnet3/transaction/broadcast
     pub(crate) async fn transaction_broadcast(
         State(rest): State<Self>,
@@ -327,4 +605,41 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
 
         Ok(ErasedJson::pretty(tx_id))
     }
+
+    // POST /testnet3/batch
+    // Dispatches each path in the batch as a GET request, and returns the responses in order.
+    pub(crate) async fn batch(
+        State(rest): State<Self>,
+        ConnectInfo(addr): ConnectInfo<SocketAddr>,
+        headers: HeaderMap,
+        Json(paths): Json<Vec<String>>,
+    ) -> Result<ErasedJson, RestError> {
+        check_batch(&paths, rest.config.max_batch_size)?;
+        let Some(router) = rest.router.get().and_then(Weak::upgrade) else {
+            return Err(RestError("The REST server is not running".to_string()));
+        };
+
+        let mut responses = Vec::with_capacity(paths.len());
+        for path in paths {
+            // Note: Each request is dispatched through the layers of the server, so it is rate limited,
+            // and carries the credentials of the batch for the JWT-protected endpoints.
+            let mut request = Request::get(&path).body(Body::empty()).map_err(|e| RestError(e.to_string()))?;
+            if let Some(authorization) = headers.get(AUTHORIZATION) {
+                request.headers_mut().insert(AUTHORIZATION, authorization.clone());
+            }
+            request.extensions_mut().insert(ConnectInfo(addr));
+
+            let response = match axum::Router::clone(&router).oneshot(request).await {
+                Ok(response) => response,
+                Err(infallible) => match infallible {},
+            };
+            let status = response.status().as_u16();
+            let bytes = hyper::body::to_bytes(response.into_body()).await.map_err(|e| RestError(e.to_string()))?;
+            // Return the body as JSON if it is JSON, and as text otherwise.
+            let body = serde_json::from_slice::<serde_json::Value>(&bytes)
+                .unwrap_or_else(|_| serde_json::Value::String(String::from_utf8_lossy(&bytes).into_owned()));
+            responses.push(json!({ "path": path, "status": status, "body": body }));
+        }
+        Ok(ErasedJson::pretty(responses))
+    }
 }
@@ -25,6 +25,8 @@ use std::borrow::Cow;
 pub struct ChallengeResponse<N: Network> {
     pub genesis_header: Header<N>,
     pub signature: Data<Signature<N>>,
+    /// The proof of knowledge of the network secret, if the node is part of a private network.
+    pub secret_proof: Option<[u8; 32]>,
 }
 
 impl<N: Network> MessageTrait for ChallengeResponse<N> {
@@ -38,13 +40,31 @@ impl<N: Network> MessageTrait for ChallengeResponse<N> {
 impl<N: Network> ToBytes for ChallengeResponse<N> {
     fn write_le<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
         self.genesis_header.write_le(&mut writer)?;
-        self.signature.write_le(&mut writer)
+        self.signature.write_le(&mut writer)?;
+        match &self.secret_proof {
+            Some(proof) => {
+                1u8.write_le(&mut writer)?;
+                writer.write_all(proof)
+            }
+            None => 0u8.write_le(&mut writer),
+        }
     }
 }
 
 impl<N: Network> FromBytes for ChallengeResponse<N> {
     fn read_le<R: io::Read>(mut reader: R) -> io::Result<Self> {
-        Ok(Self { genesis_header: Header::read_le(&mut reader)?, signature: Data::read_le(reader)? })
+        let genesis_header = Header::read_le(&mut reader)?;
+        let signature = Data::read_le(&mut reader)?;
+        let secret_proof = match u8::read_le(&mut reader)? {
+            0 => None,
+            1 => {
+                let mut proof = [0u8; 32];
+                reader.read_exact(&mut proof)?;
+                Some(proof)
+            }
+            _ => return Err(error("Invalid 'ChallengeResponse' message")),
+        };
+        Ok(Self { genesis_header, signature, secret_proof })
     }
 }
 
@@ -80,8 +100,12 @@ pub mod prop_tests {
     }
 
     pub fn any_challenge_response() -> BoxedStrategy<ChallengeResponse<CurrentNetwork>> {
-        (any_signature(), any_genesis_header())
-            .prop_map(|(sig, genesis_header)| ChallengeResponse { signature: Data::Object(sig), genesis_header })
+        (any_signature(), any_genesis_header(), any::<Option<[u8; 32]>>())
+            .prop_map(|(sig, genesis_header, secret_proof)| ChallengeResponse {
+                signature: Data::Object(sig),
+                genesis_header,
+                secret_proof,
+            })
             .boxed()
     }
 
@@ -94,6 +118,7 @@ pub mod prop_tests {
             ChallengeResponse::read_le(buf.into_inner().reader()).unwrap();
 
         assert_eq!(original.genesis_header, deserialized.genesis_header);
+        assert_eq!(original.secret_proof, deserialized.secret_proof);
         assert_eq!(
             original.signature.deserialize_blocking().unwrap(),
             deserialized.signature.deserialize_blocking().unwrap()
@@ -17,37 +17,54 @@ use snarkvm::prelude::{FromBytes, Network, ToBytes};
 
 use ::bytes::{Buf, BufMut, BytesMut};
 use core::marker::PhantomData;
+use std::sync::Arc;
 use tokio_util::codec::{Decoder, Encoder, LengthDelimitedCodec};
 
 /// The maximum size of a message that can be transmitted during the handshake.
 const MAXIMUM_HANDSHAKE_MESSAGE_SIZE: usize = 1024 * 1024; // 1 MiB
 
 /// The maximum size of a message that can be transmitted in the network.
-pub(crate) const MAXIMUM_MESSAGE_SIZE: usize = 128 * 1024 * 1024; // 128 MiB
+pub const MAXIMUM_MESSAGE_SIZE: usize = 128 * 1024 * 1024; // 128 MiB
+
+/// The number of bytes in the length prefix of a message frame.
+const LENGTH_PREFIX_SIZE: usize = 4;
+
+/// A callback for a message whose length prefix exceeds the maximum message size, given the claimed size.
+pub type OversizedMessageHandler = Arc<dyn Fn(usize) + Send + Sync>;
 
 /// The codec used to decode and encode network `Message`s.
 pub struct MessageCodec<N: Network> {
     codec: LengthDelimitedCodec,
+    /// The maximum size of a message.
+    max_message_size: usize,
+    /// The callback for an oversized message, if one is set.
+    on_oversized: Option<OversizedMessageHandler>,
     _phantom: PhantomData<N>,
 }
 
 impl<N: Network> MessageCodec<N> {
     pub fn handshake() -> Self {
-        let mut codec = Self::default();
-        codec.codec.set_max_frame_length(MAXIMUM_HANDSHAKE_MESSAGE_SIZE);
-        codec
+        Self::with_max_message_size(MAXIMUM_HANDSHAKE_MESSAGE_SIZE, None)
     }
-}
 
-impl<N: Network> Default for MessageCodec<N> {
-    fn default() -> Self {
+    /// Initializes a codec that rejects messages larger than the given size, before allocating them,
+    /// and calls the given callback (if any) for each rejected message.
+    pub fn with_max_message_size(max_message_size: usize, on_oversized: Option<OversizedMessageHandler>) -> Self {
         Self {
-            codec: LengthDelimitedCodec::builder().max_frame_length(MAXIMUM_MESSAGE_SIZE).little_endian().new_codec(),
+            codec: LengthDelimitedCodec::builder().max_frame_length(max_message_size).little_endian().new_codec(),
+            max_message_size,
+            on_oversized,
             _phantom: Default::default(),
         }
     }
 }
 
+impl<N: Network> Default for MessageCodec<N> {
+    fn default() -> Self {
+        Self::with_max_message_size(MAXIMUM_MESSAGE_SIZE, None)
+    }
+}
+
 impl<N: Network> Encoder<Message<N>> for MessageCodec<N> {
     type Error = std::io::Error;
 
@@ -69,6 +86,18 @@ impl<N: Network> Decoder for MessageCodec<N> {
     type Item = Message<N>;
 
     fn decode(&mut self, source: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        // Reject the message if its length prefix exceeds the maximum message size, before reading its bytes.
+        if source.len() >= LENGTH_PREFIX_SIZE {
+            let size = u32::from_le_bytes([source[0], source[1], source[2], source[3]]) as usize;
+            if size > self.max_message_size {
+                if let Some(on_oversized) = &self.on_oversized {
+                    on_oversized(size);
+                }
+                let error = format!("Message of {size} bytes exceeds the maximum of {} bytes", self.max_message_size);
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, error));
+            }
+        }
+
         // Decode a frame containing bytes belonging to a message.
         let bytes = match self.codec.decode(source)? {
             Some(bytes) => bytes,
@@ -86,3 +115,33 @@ impl<N: Network> Decoder for MessageCodec<N> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_oversized_message_is_rejected() {
+        let rejected = Arc::new(AtomicUsize::new(0));
+        let rejected_clone = rejected.clone();
+        let mut codec = MessageCodec::<CurrentNetwork>::with_max_message_size(
+            16,
+            Some(Arc::new(move |size| rejected_clone.store(size, Ordering::SeqCst))),
+        );
+
+        // A length prefix within the limit waits for the rest of the frame.
+        let mut source = BytesMut::from(&16u32.to_le_bytes()[..]);
+        assert!(codec.decode(&mut source).unwrap().is_none());
+        assert_eq!(rejected.load(Ordering::SeqCst), 0);
+
+        // A length prefix above the limit is rejected before the payload arrives.
+        let mut source = BytesMut::from(&17u32.to_le_bytes()[..]);
+        let error = codec.decode(&mut source).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(rejected.load(Ordering::SeqCst), 17);
+    }
+}
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::{
-    messages::{ChallengeRequest, ChallengeResponse, DisconnectReason, Message, MessageCodec, MessageTrait},
+    messages::{ChallengeRequest, ChallengeResponse, DisconnectReason, Message, MessageCodec, MessageTrait, NodeType},
     Peer,
     Router,
 };
@@ -25,7 +25,9 @@ use snarkvm::{
 
 use anyhow::{bail, Result};
 use futures::SinkExt;
+use hmac::{Hmac, Mac};
 use rand::{rngs::OsRng, Rng};
+use sha2::Sha256;
 use std::{io, net::SocketAddr};
 use tokio::net::TcpStream;
 use tokio_stream::StreamExt;
@@ -148,11 +150,12 @@ impl<N: Network> Router<N> {
         let peer_response = expect_message!(Message::ChallengeResponse, framed, peer_addr);
         // Listen for the challenge request message.
         let peer_request = expect_message!(Message::ChallengeRequest, framed, peer_addr);
+        // Log the fields of the challenge request.
+        self.log_challenge_request(peer_addr, &peer_request);
 
         // Verify the challenge response. If a disconnect reason was returned, send the disconnect message and abort.
-        if let Some(reason) = self
-            .verify_challenge_response(peer_addr, peer_request.address, peer_response, genesis_header, our_nonce)
-            .await
+        if let Some(reason) =
+            self.verify_challenge_response(peer_addr, &peer_request, peer_response, genesis_header, our_nonce).await
         {
             send(&mut framed, peer_addr, reason.into()).await?;
             return Err(error(format!("Dropped '{peer_addr}' for reason: {reason:?}")));
@@ -162,18 +165,29 @@ impl<N: Network> Router<N> {
             send(&mut framed, peer_addr, reason.into()).await?;
             return Err(error(format!("Dropped '{peer_addr}' for reason: {reason:?}")));
         }
+        // Ensure the node has a free connection slot for the peer's node type.
+        if let Some(reason) = self.verify_connection_limit(peer_ip, peer_request.node_type) {
+            send(&mut framed, peer_addr, reason.into()).await?;
+            return Err(error(format!("Dropped '{peer_addr}' for reason: {reason:?}")));
+        }
         /* Step 3: Send the challenge response. */
 
         // Sign the counterparty nonce.
-        let Ok(our_signature) = self.account.sign_bytes(&peer_request.nonce.to_le_bytes(), rng) else {
+        let Ok(our_signature) = self.account.sign_bytes(&self.challenge_bytes(peer_request.nonce), rng) else {
             return Err(error(format!("Failed to sign the challenge request nonce from '{peer_addr}'")));
         };
+        // Prove knowledge of the network secret, if any.
+        let secret_proof = self.secret_proof(peer_request.nonce, our_nonce, self.address());
         // Send the challenge response.
-        let our_response = ChallengeResponse { genesis_header, signature: Data::Object(our_signature) };
+        let our_response = ChallengeResponse { genesis_header, signature: Data::Object(our_signature), secret_proof };
         send(&mut framed, peer_addr, Message::ChallengeResponse(our_response)).await?;
 
+        debug!(
+            "Handshake with '{peer_addr}' succeeded ({} on version {}, listening on '{peer_ip}')",
+            peer_request.node_type, peer_request.version
+        );
         // Add the peer to the router.
-        self.insert_connected_peer(Peer::new(peer_ip, &peer_request), peer_addr);
+        self.insert_connected_peer(Peer::new(peer_ip, &peer_request, true), peer_addr);
 
         Ok((peer_ip, framed))
     }
@@ -193,6 +207,8 @@ impl<N: Network> Router<N> {
 
         // Listen for the challenge request message.
         let peer_request = expect_message!(Message::ChallengeRequest, framed, peer_addr);
+        // Log the fields of the challenge request.
+        self.log_challenge_request(peer_addr, &peer_request);
 
         // Obtain the peer's listening address.
         *peer_ip = Some(SocketAddr::new(peer_addr.ip(), peer_request.listener_port));
@@ -207,21 +223,39 @@ impl<N: Network> Router<N> {
             send(&mut framed, peer_addr, reason.into()).await?;
             return Err(error(format!("Dropped '{peer_addr}' for reason: {reason:?}")));
         }
+        // Ensure the node has a free connection slot for the peer's node type.
+        if let Some(reason) = self.verify_connection_limit(peer_ip, peer_request.node_type) {
+            send(&mut framed, peer_addr, reason.into()).await?;
+            return Err(error(format!("Dropped '{peer_addr}' for reason: {reason:?}")));
+        }
+        // Ensure the node has a free inbound connection slot.
+        if let Some(reason) = self.verify_inbound_limit(peer_ip) {
+            send(&mut framed, peer_addr, reason.into()).await?;
+            return Err(error(format!("Dropped '{peer_addr}' for reason: {reason:?}")));
+        }
+        // Ensure the node is able to serve the peer, if it is still syncing.
+        if let Some(reason) = self.verify_is_serving(peer_ip, peer_request.node_type) {
+            send(&mut framed, peer_addr, reason.into()).await?;
+            return Err(error(format!("Dropped '{peer_addr}' for reason: {reason:?}")));
+        }
         /* Step 2: Send the challenge response followed by own challenge request. */
 
         // Initialize an RNG.
         let rng = &mut OsRng;
 
+        // Sample a random nonce.
+        let our_nonce = rng.gen();
+
         // Sign the counterparty nonce.
-        let Ok(our_signature) = self.account.sign_bytes(&peer_request.nonce.to_le_bytes(), rng) else {
+        let Ok(our_signature) = self.account.sign_bytes(&self.challenge_bytes(peer_request.nonce), rng) else {
             return Err(error(format!("Failed to sign the challenge request nonce from '{peer_addr}'")));
         };
+        // Prove knowledge of the network secret, if any.
+        let secret_proof = self.secret_proof(peer_request.nonce, our_nonce, self.address());
         // Send the challenge response.
-        let our_response = ChallengeResponse { genesis_header, signature: Data::Object(our_signature) };
+        let our_response = ChallengeResponse { genesis_header, signature: Data::Object(our_signature), secret_proof };
         send(&mut framed, peer_addr, Message::ChallengeResponse(our_response)).await?;
 
-        // Sample a random nonce.
-        let our_nonce = rng.gen();
         // Send the challenge request.
         let our_request = ChallengeRequest::new(self.local_ip().port(), self.node_type, self.address(), our_nonce);
         send(&mut framed, peer_addr, Message::ChallengeRequest(our_request)).await?;
@@ -231,15 +265,18 @@ impl<N: Network> Router<N> {
         // Listen for the challenge response message.
         let peer_response = expect_message!(Message::ChallengeResponse, framed, peer_addr);
         // Verify the challenge response. If a disconnect reason was returned, send the disconnect message and abort.
-        if let Some(reason) = self
-            .verify_challenge_response(peer_addr, peer_request.address, peer_response, genesis_header, our_nonce)
-            .await
+        if let Some(reason) =
+            self.verify_challenge_response(peer_addr, &peer_request, peer_response, genesis_header, our_nonce).await
         {
             send(&mut framed, peer_addr, reason.into()).await?;
             return Err(error(format!("Dropped '{peer_addr}' for reason: {reason:?}")));
         }
+        debug!(
+            "Handshake with '{peer_addr}' succeeded ({} on version {}, listening on '{peer_ip}')",
+            peer_request.node_type, peer_request.version
+        );
         // Add the peer to the router.
-        self.insert_connected_peer(Peer::new(peer_ip, &peer_request), peer_addr);
+        self.insert_connected_peer(Peer::new(peer_ip, &peer_request, false), peer_addr);
 
         Ok((peer_ip, framed))
     }
@@ -276,6 +313,43 @@ impl<N: Network> Router<N> {
         Ok(())
     }
 
+    /// Returns the bytes to sign in response to the given challenge nonce.
+    /// In development mode, the development tag is appended to the nonce, so that development
+    /// nodes are only ever able to complete a handshake with other development nodes.
+    fn challenge_bytes(&self, nonce: u64) -> Vec<u8> {
+        let mut bytes = nonce.to_le_bytes().to_vec();
+        if self.is_dev() {
+            bytes.extend_from_slice(Self::DEV_CHALLENGE_TAG);
+        }
+        bytes
+    }
+
+    /// Returns the MAC over the given challenge nonce, the nonce of the responding node, and its address,
+    /// keyed with the network secret, or `None` if no network secret is configured.
+    fn secret_mac(&self, challenge_nonce: u64, response_nonce: u64, address: Address<N>) -> Option<Hmac<Sha256>> {
+        let secret = self.network_secret.as_ref()?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+        mac.update(&challenge_nonce.to_le_bytes());
+        mac.update(&response_nonce.to_le_bytes());
+        mac.update(address.to_string().as_bytes());
+        Some(mac)
+    }
+
+    /// Returns the proof of knowledge of the network secret to send in response to the given challenge nonce,
+    /// or `None` if no network secret is configured. The secret itself never leaves the node.
+    fn secret_proof(&self, challenge_nonce: u64, response_nonce: u64, address: Address<N>) -> Option<[u8; 32]> {
+        self.secret_mac(challenge_nonce, response_nonce, address).map(|mac| mac.finalize().into_bytes().into())
+    }
+
+    /// Logs the fields of the given challenge request, to help diagnose handshakes with unexpected peers.
+    fn log_challenge_request(&self, peer_addr: SocketAddr, message: &ChallengeRequest<N>) {
+        let ChallengeRequest { version, listener_port, node_type, address, nonce: _ } = message;
+        debug!(
+            "Handshake with '{peer_addr}' - received a challenge request \
+            (version {version}, node type {node_type}, address {address}, listening port {listener_port})"
+        );
+    }
+
     /// Verifies the given challenge request. Returns a disconnect reason if the request is invalid.
     fn verify_challenge_request(
         &self,
@@ -293,21 +367,72 @@ impl<N: Network> Router<N> {
         None
     }
 
+    /// Verifies that connecting to a peer of the given node type does not exceed its connection limit.
+    /// Returns a disconnect reason if the limit is reached. Trusted peers are exempt from the limit.
+    fn verify_connection_limit(&self, peer_ip: SocketAddr, node_type: NodeType) -> Option<DisconnectReason> {
+        // Skip the check for trusted peers.
+        if self.trusted_peers().contains(&peer_ip) {
+            return None;
+        }
+        // Ensure the number of connected peers of this node type is below its limit.
+        if self.connection_limits().is_exceeded(node_type, self.number_of_connected_peers_of_type(node_type)) {
+            debug!("Dropping '{peer_ip}' (reached the {node_type} connection limit)");
+            return Some(DisconnectReason::TooManyPeers);
+        }
+        None
+    }
+
+    /// Verifies that accepting an inbound peer does not exceed the inbound connection limit.
+    /// Returns a disconnect reason if the limit is reached. Trusted peers are exempt from the limit.
+    fn verify_inbound_limit(&self, peer_ip: SocketAddr) -> Option<DisconnectReason> {
+        // Skip the check for trusted peers.
+        if self.trusted_peers().contains(&peer_ip) {
+            return None;
+        }
+        if self.connection_limits().is_direction_exceeded(false, self.number_of_inbound_peers()) {
+            debug!("Dropping '{peer_ip}' (reached the inbound connection limit)");
+            return Some(DisconnectReason::TooManyPeers);
+        }
+        None
+    }
+
+    /// Verifies that the node is able to serve an inbound peer of the given node type.
+    /// Returns a disconnect reason if the node is configured to refuse non-validators while it is syncing.
+    fn verify_is_serving(&self, peer_ip: SocketAddr, node_type: NodeType) -> Option<DisconnectReason> {
+        // Validators and trusted peers are always accepted.
+        if node_type.is_validator() || self.trusted_peers().contains(&peer_ip) {
+            return None;
+        }
+        if self.connection_limits().reject_while_syncing && !self.is_synced() {
+            debug!("Dropping '{peer_ip}' (this node is still syncing)");
+            return Some(DisconnectReason::INeedToSyncFirst);
+        }
+        None
+    }
+
     /// Verifies the given challenge response. Returns a disconnect reason if the response is invalid.
     async fn verify_challenge_response(
         &self,
         peer_addr: SocketAddr,
-        peer_address: Address<N>,
+        peer_request: &ChallengeRequest<N>,
         response: ChallengeResponse<N>,
         expected_genesis_header: Header<N>,
         expected_nonce: u64,
     ) -> Option<DisconnectReason> {
         // Retrieve the components of the challenge response.
-        let ChallengeResponse { genesis_header, signature } = response;
+        let ChallengeResponse { genesis_header, signature, secret_proof } = response;
 
         // Verify the challenge response, by checking that the block header matches.
         if genesis_header != expected_genesis_header {
-            warn!("Handshake with '{peer_addr}' failed (incorrect block header)");
+            debug!(
+                "Handshake with '{peer_addr}' - received genesis header root {}, expected {}",
+                genesis_header.to_root().map(|root| root.to_string()).unwrap_or_default(),
+                expected_genesis_header.to_root().map(|root| root.to_string()).unwrap_or_default()
+            );
+            match self.is_dev() {
+                true => warn!("Handshake with '{peer_addr}' failed (incorrect block header - not a development peer)"),
+                false => warn!("Handshake with '{peer_addr}' failed (incorrect block header)"),
+            }
             return Some(DisconnectReason::InvalidChallengeResponse);
         }
         // Perform the deferred non-blocking deserialization of the signature.
@@ -316,10 +441,21 @@ impl<N: Network> Router<N> {
             return Some(DisconnectReason::InvalidChallengeResponse);
         };
         // Verify the signature.
-        if !signature.verify_bytes(&peer_address, &expected_nonce.to_le_bytes()) {
-            warn!("Handshake with '{peer_addr}' failed (invalid signature)");
+        if !signature.verify_bytes(&peer_request.address, &self.challenge_bytes(expected_nonce)) {
+            match self.is_dev() {
+                true => warn!("Handshake with '{peer_addr}' failed (invalid signature or development tag)"),
+                false => warn!("Handshake with '{peer_addr}' failed (invalid signature)"),
+            }
             return Some(DisconnectReason::InvalidChallengeResponse);
         }
+        // Verify the proof of knowledge of the network secret, if any.
+        if let Some(mac) = self.secret_mac(expected_nonce, peer_request.nonce, peer_request.address) {
+            // Note: The MAC is verified in constant time.
+            if !secret_proof.map_or(false, |proof| mac.verify_slice(&proof).is_ok()) {
+                warn!("Handshake with '{peer_addr}' failed (invalid network secret)");
+                return Some(DisconnectReason::InvalidChallengeResponse);
+            }
+        }
         None
     }
 }
@@ -14,6 +14,7 @@
 
 use crate::{
     messages::{DisconnectReason, Message, PeerRequest},
+    min_priority_to_connect,
     Outbound,
     Router,
 };
@@ -40,11 +41,15 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
     const MEDIAN_NUMBER_OF_PEERS: usize = max(Self::MAXIMUM_NUMBER_OF_PEERS / 2, Self::MINIMUM_NUMBER_OF_PEERS);
     /// The maximum number of peers permitted to maintain connections with.
     const MAXIMUM_NUMBER_OF_PEERS: usize = 21;
+    /// The percentage of connected peers on a newer message version, at which the network is considered upgraded.
+    const UPGRADE_THRESHOLD_PERCENT: usize = 80;
 
     /// Handles the heartbeat request.
     fn heartbeat(&self) {
         self.safety_check_minimum_number_of_peers();
         self.log_connected_peers();
+        #[cfg(feature = "metrics")]
+        self.update_traffic_metrics();
 
         // Remove any stale connected peers.
         self.remove_stale_connected_peers();
@@ -58,6 +63,8 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
         self.handle_trusted_peers();
         // Keep the puzzle request up to date.
         self.handle_puzzle_request();
+        // Check if the network has upgraded past the node's message version.
+        self.handle_upgrade_awareness();
     }
 
     /// TODO (howardwu): Consider checking minimum number of validators, to exclude clients and provers.
@@ -82,6 +89,15 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
         }
     }
 
+    /// This function updates the metrics of the bytes sent to and received from the peers.
+    #[cfg(feature = "metrics")]
+    fn update_traffic_metrics(&self) {
+        let (_, bytes_sent) = self.router().stats().sent();
+        let (_, bytes_received) = self.router().stats().received();
+        metrics::absolute_counter!(metrics::router::BYTES_SENT, bytes_sent);
+        metrics::absolute_counter!(metrics::router::BYTES_RECEIVED, bytes_received);
+    }
+
     /// This function removes any connected peers that have not communicated within the predefined time.
     fn remove_stale_connected_peers(&self) {
         // Check if any connected peer is stale.
@@ -227,14 +243,39 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
         }
     }
 
-    /// This function attempts to connect to any disconnected trusted peers.
+    /// This function attempts to connect to any disconnected trusted peers, with an exponential backoff.
+    /// Once the maximum number of reconnection attempts to a peer is reached, it is no longer retried.
+    /// The trusted peers of a lower priority are only connected to once the ones of a higher priority fail to connect.
     fn handle_trusted_peers(&self) {
+        // Determine the minimum priority of the trusted peers to connect to, where a trusted peer holds back
+        // the ones of a lower priority while it is connected, or not yet retried since it was last connected.
+        let min_priority = min_priority_to_connect(self.router().trusted_peers().iter().map(|peer_ip| {
+            let is_ready = self.router().is_connected(peer_ip) || !self.router().reconnects.is_reconnecting(peer_ip);
+            (self.router().peer_priority(peer_ip), is_ready)
+        }));
+
         // Ensure that the trusted nodes are connected.
         for peer_ip in self.router().trusted_peers() {
-            // If the peer is not connected, attempt to connect to it.
-            if !self.router().is_connected(peer_ip) {
+            // If the peer is connected, reset its reconnection backoff.
+            if self.router().is_connected(peer_ip) {
+                if let Some(attempts) = self.router().reconnects.reset(peer_ip) {
+                    info!("Connected to trusted peer '{peer_ip}' (after {attempts} attempt(s))");
+                }
+            }
+            // If a trusted peer of a higher priority is ready, hold back this peer as a fallback.
+            else if self.router().peer_priority(peer_ip) < min_priority {
+                trace!("Holding back the connection to trusted peer '{peer_ip}' (a higher-priority peer is ready)");
+            }
+            // If the peer is not connected, and its backoff has elapsed, attempt to connect to it.
+            else if !self.router().reconnects.is_exhausted(peer_ip) && self.router().reconnects.is_due(peer_ip) {
+                // Schedule the next reconnection attempt.
+                let attempts = self.router().reconnects.record_attempt(*peer_ip);
                 // Attempt to connect to the trusted peer.
                 self.router().connect(*peer_ip);
+                // If this was the last attempt, report that the peer will no longer be retried.
+                if self.router().reconnects.is_exhausted(peer_ip) {
+                    warn!("Made the last reconnection attempt ({attempts}) to trusted peer '{peer_ip}'");
+                }
             }
         }
     }
@@ -243,4 +284,43 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
     fn handle_puzzle_request(&self) {
         // No-op
     }
+
+    /// This function checks if the overwhelming majority of connected peers are on a newer message version,
+    /// which suggests a network upgrade that this node missed. If so, a critical alert is logged,
+    /// and the router pauses the production and relay of solutions and transactions.
+    fn handle_upgrade_awareness(&self) {
+        // Skip if upgrade awareness is not enabled.
+        if !self.router().is_upgrade_aware() {
+            return;
+        }
+
+        // Retrieve the connected peers.
+        let connected_peers = self.router().get_connected_peers();
+        let num_connected = connected_peers.len();
+        // Skip if there are too few connected peers to draw a conclusion from.
+        if num_connected < Self::MINIMUM_NUMBER_OF_PEERS {
+            return;
+        }
+
+        // Count the connected peers on a newer message version.
+        let own_version = Message::<N>::VERSION;
+        let num_upgraded = connected_peers.iter().filter(|peer| peer.version() > own_version).count();
+        // Determine if the network has upgraded past the node's message version.
+        let is_outdated = num_upgraded * 100 >= num_connected * Self::UPGRADE_THRESHOLD_PERCENT;
+
+        match (self.router().set_outdated(is_outdated), is_outdated) {
+            (false, true) => error!(
+                "CRITICAL: {num_upgraded} of {num_connected} connected peers are on a newer message version than \
+                 this node (v{own_version}). The network has likely upgraded - pausing the production and relay of \
+                 solutions and transactions. Please update snarkOS."
+            ),
+            (true, true) => {
+                error!("CRITICAL: The network has upgraded past this node (v{own_version}). Please update snarkOS.")
+            }
+            (true, false) => {
+                info!("Most connected peers are on the node's message version again - resuming production and relay")
+            }
+            (false, false) => (),
+        }
+    }
 }
@@ -39,6 +39,8 @@ pub struct Cache<N: Network> {
     seen_inbound_connections: RwLock<IndexMap<IpAddr, VecDeque<OffsetDateTime>>>,
     /// The map of peer IPs to their recent timestamps.
     seen_inbound_messages: RwLock<IndexMap<SocketAddr, VecDeque<OffsetDateTime>>>,
+    /// The map of peer IPs to their recent block request timestamps.
+    seen_inbound_block_requests: RwLock<IndexMap<SocketAddr, VecDeque<OffsetDateTime>>>,
     /// The map of peer IPs to their recent timestamps.
     seen_inbound_puzzle_requests: RwLock<IndexMap<SocketAddr, VecDeque<OffsetDateTime>>>,
     /// The map of solution commitments to their last seen timestamp.
@@ -68,6 +70,7 @@ impl<N: Network> Cache<N> {
         Self {
             seen_inbound_connections: Default::default(),
             seen_inbound_messages: Default::default(),
+            seen_inbound_block_requests: Default::default(),
             seen_inbound_puzzle_requests: Default::default(),
             seen_inbound_solutions: RwLock::new(LinkedHashMap::with_capacity(MAX_CACHE_SIZE)),
             seen_inbound_transactions: RwLock::new(LinkedHashMap::with_capacity(MAX_CACHE_SIZE)),
@@ -90,6 +93,11 @@ impl<N: Network> Cache<N> {
         Self::retain_and_insert(&self.seen_inbound_messages, peer_ip, interval_in_secs)
     }
 
+    /// Inserts a new block request timestamp for the given peer IP, returning the number of recent requests.
+    pub fn insert_inbound_block_request(&self, peer_ip: SocketAddr) -> usize {
+        Self::retain_and_insert(&self.seen_inbound_block_requests, peer_ip, 60)
+    }
+
     /// Inserts a new timestamp for the given peer IP, returning the number of recent requests.
     pub fn insert_inbound_puzzle_request(&self, peer_ip: SocketAddr) -> usize {
         Self::retain_and_insert(&self.seen_inbound_puzzle_requests, peer_ip, 60)
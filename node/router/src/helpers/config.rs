@@ -0,0 +1,36 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{ConnectionLimits, ReconnectBackoff};
+
+use indexmap::IndexMap;
+use std::net::SocketAddr;
+
+/// The configuration of a router, beyond its identity and its trusted peers.
+#[derive(Clone, Debug, Default)]
+pub struct RouterConfig {
+    /// The shared secret peers must prove knowledge of during the handshake, if any.
+    pub network_secret: Option<String>,
+    /// The per-type and per-direction limits on the connected peers.
+    pub connection_limits: ConnectionLimits,
+    /// The backoff in between reconnection attempts to the trusted peers.
+    pub reconnect_backoff: ReconnectBackoff,
+    /// The priorities of the trusted peers, where a trusted peer without one has `DEFAULT_PEER_PRIORITY`.
+    /// The node prefers to connect to the trusted peers of a higher priority, and falls back to the ones of a lower
+    /// priority while none of the former is connected.
+    pub peer_priorities: IndexMap<SocketAddr, u8>,
+    /// The maximum number of running tasks, beyond which a (likely) task leak is reported, or `None` for no limit.
+    pub max_tasks: Option<usize>,
+    /// If `true`, tasks beyond `max_tasks` are not spawned at all.
+    pub refuse_excess_tasks: bool,
+}
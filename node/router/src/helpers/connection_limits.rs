@@ -0,0 +1,113 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::messages::NodeType;
+
+use std::net::SocketAddr;
+
+/// The limits on the peer connections of the node, such as the maximum number of connected peers for each node type.
+/// A limit of `None` leaves the node type bounded only by the overall maximum number of peers.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionLimits {
+    /// The maximum number of connected validators.
+    pub max_validators: Option<usize>,
+    /// The maximum number of connected provers.
+    pub max_provers: Option<usize>,
+    /// The maximum number of connected clients.
+    pub max_clients: Option<usize>,
+    /// The maximum number of peers connected by an inbound connection.
+    pub max_inbound: Option<usize>,
+    /// The maximum number of peers connected by an outbound connection.
+    pub max_outbound: Option<usize>,
+    /// If `true`, inbound connections from non-validators are refused while the node is syncing.
+    pub reject_while_syncing: bool,
+    /// The number of seconds a connection may go without receiving a message, or spend writing one,
+    /// before it is considered dead and dropped. A timeout of `None` leaves the connections without a deadline.
+    pub io_timeout_secs: Option<u64>,
+    /// An additional address to listen for inbound connections on, e.g. an IPv6 address alongside an IPv4 node IP.
+    pub extra_listener: Option<SocketAddr>,
+    /// If set, whether the IPv6 listeners only accept IPv6 connections (`IPV6_V6ONLY`), instead of the OS default.
+    pub ipv6_only: Option<bool>,
+    /// The maximum number of block requests served to each peer per minute. Requests beyond the limit are dropped.
+    /// A limit of `None` serves every well-formed block request.
+    pub serve_rate_limit: Option<usize>,
+    /// The maximum size in bytes of a message from a peer. A peer that sends a larger message is disconnected
+    /// and restricted. A limit of `None` uses the default maximum message size.
+    pub max_message_size: Option<usize>,
+}
+
+impl ConnectionLimits {
+    /// Returns the maximum number of connected peers for the given node type, if one is set.
+    pub const fn limit(&self, node_type: NodeType) -> Option<usize> {
+        match node_type {
+            NodeType::Validator => self.max_validators,
+            NodeType::Prover => self.max_provers,
+            NodeType::Client => self.max_clients,
+        }
+    }
+
+    /// Returns `true` if a new peer of the given node type would exceed its limit,
+    /// given the number of already-connected peers of that type.
+    pub fn is_exceeded(&self, node_type: NodeType, num_connected: usize) -> bool {
+        self.limit(node_type).map(|limit| num_connected >= limit).unwrap_or(false)
+    }
+
+    /// Returns `true` if a new peer in the given direction would exceed its limit,
+    /// given the number of already-connected peers in that direction.
+    pub fn is_direction_exceeded(&self, is_outbound: bool, num_connected: usize) -> bool {
+        let limit = match is_outbound {
+            true => self.max_outbound,
+            false => self.max_inbound,
+        };
+        limit.map(|limit| num_connected >= limit).unwrap_or(false)
+    }
+
+    /// Returns `true` if serving another block request would exceed the per-peer serve rate limit,
+    /// given the number of block requests received from the peer in the last minute (including this one).
+    pub fn is_serve_rate_exceeded(&self, num_recent_requests: usize) -> bool {
+        self.serve_rate_limit.map(|limit| num_recent_requests > limit).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_limits() {
+        // By default, there are no per-type limits.
+        let limits = ConnectionLimits::default();
+        assert!(!limits.is_exceeded(NodeType::Validator, usize::MAX));
+        assert!(!limits.is_exceeded(NodeType::Prover, usize::MAX));
+        assert!(!limits.is_exceeded(NodeType::Client, usize::MAX));
+
+        // Limit the number of clients only.
+        let limits = ConnectionLimits { max_clients: Some(2), ..Default::default() };
+        assert!(!limits.is_exceeded(NodeType::Client, 1));
+        assert!(limits.is_exceeded(NodeType::Client, 2));
+        assert!(!limits.is_exceeded(NodeType::Validator, 2));
+
+        // Limit the number of inbound peers only.
+        let limits = ConnectionLimits { max_inbound: Some(3), ..Default::default() };
+        assert!(!limits.is_direction_exceeded(false, 2));
+        assert!(limits.is_direction_exceeded(false, 3));
+        assert!(!limits.is_direction_exceeded(true, usize::MAX));
+
+        // Limit the rate of served block requests only.
+        assert!(!ConnectionLimits::default().is_serve_rate_exceeded(usize::MAX));
+        let limits = ConnectionLimits { serve_rate_limit: Some(10), ..Default::default() };
+        assert!(!limits.is_serve_rate_exceeded(10));
+        assert!(limits.is_serve_rate_exceeded(11));
+    }
+}
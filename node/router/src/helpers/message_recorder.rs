@@ -0,0 +1,198 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::messages::{Message, MAXIMUM_MESSAGE_SIZE};
+use snarkvm::prelude::{FromBytes, Network, ToBytes};
+
+use anyhow::{bail, Result};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    net::SocketAddr,
+    path::Path,
+    sync::mpsc::{self, Receiver, SyncSender, TrySendError},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The maximum number of entries queued for the recording file.
+/// While the queue is full (e.g. the disk is slow), new messages are not recorded.
+const MESSAGE_RECORDER_CAPACITY: usize = 1024;
+
+/// An inbound message, as recorded by the message recorder.
+#[derive(Clone, Debug)]
+pub struct RecordedMessage<N: Network> {
+    /// The UNIX timestamp (in milliseconds) at which the message was received.
+    pub timestamp: u64,
+    /// The listener IP of the peer that sent the message.
+    pub peer_ip: SocketAddr,
+    /// The message.
+    pub message: Message<N>,
+}
+
+/// A recorder that appends every inbound message to a file, for later replay.
+/// The entries are written to the file by a dedicated thread, so a slow disk does not delay the inbound messages.
+///
+/// Each entry is encoded as the timestamp (`u64`), the length-prefixed (`u16`) peer IP string,
+/// and the length-prefixed (`u32`) message bytes, all in little-endian.
+#[derive(Debug)]
+pub struct MessageRecorder {
+    /// The sender of encoded entries to the writer thread.
+    sender: SyncSender<Vec<u8>>,
+}
+
+impl MessageRecorder {
+    /// Initializes a new message recorder, creating (or truncating) the file at the given path,
+    /// and spawning the thread that writes to it.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = match File::create(path.as_ref()) {
+            Ok(file) => file,
+            Err(error) => bail!("Failed to create the message recording '{}' - {error}", path.as_ref().display()),
+        };
+        let (sender, receiver) = mpsc::sync_channel(MESSAGE_RECORDER_CAPACITY);
+        std::thread::spawn(move || write_entries(BufWriter::new(file), receiver));
+        Ok(Self { sender })
+    }
+
+    /// Records the given inbound message from the given peer.
+    /// This never blocks the caller, and fails if the queue of the writer thread is full.
+    pub fn record<N: Network>(&self, peer_ip: SocketAddr, message: &Message<N>) -> Result<()> {
+        match self.sender.try_send(encode_entry(peer_ip, message)?) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => bail!("The message recording is not keeping up"),
+            Err(TrySendError::Disconnected(_)) => bail!("The message recording is closed"),
+        }
+    }
+
+    /// Loads the recorded messages from the file at the given path.
+    pub fn load<N: Network, P: AsRef<Path>>(path: P) -> Result<Vec<RecordedMessage<N>>> {
+        let file = match File::open(path.as_ref()) {
+            Ok(file) => file,
+            Err(error) => bail!("Failed to open the message recording '{}' - {error}", path.as_ref().display()),
+        };
+        let mut reader = BufReader::new(file);
+
+        let mut messages = Vec::new();
+        loop {
+            // Read the timestamp, stopping at the end of the file.
+            let mut timestamp = [0u8; 8];
+            match reader.read_exact(&mut timestamp) {
+                Ok(()) => (),
+                Err(error) if error.kind() == ErrorKind::UnexpectedEof => break,
+                Err(error) => return Err(error.into()),
+            }
+            // Read the peer IP.
+            let mut length = [0u8; 2];
+            reader.read_exact(&mut length)?;
+            let mut peer_ip = vec![0u8; u16::from_le_bytes(length) as usize];
+            reader.read_exact(&mut peer_ip)?;
+            // Read the message, rejecting an oversized message before allocating it (e.g. in a corrupt recording).
+            let mut length = [0u8; 4];
+            reader.read_exact(&mut length)?;
+            let length = u32::from_le_bytes(length) as usize;
+            if length > MAXIMUM_MESSAGE_SIZE {
+                bail!("The recording holds an oversized message ({length} bytes)");
+            }
+            let mut message = vec![0u8; length];
+            reader.read_exact(&mut message)?;
+
+            messages.push(RecordedMessage {
+                timestamp: u64::from_le_bytes(timestamp),
+                peer_ip: String::from_utf8(peer_ip)?.parse()?,
+                message: Message::from_bytes_le(&message)?,
+            });
+        }
+        Ok(messages)
+    }
+}
+
+/// Encodes the given inbound message from the given peer as an entry of the recording, at the current time.
+fn encode_entry<N: Network>(peer_ip: SocketAddr, message: &Message<N>) -> Result<Vec<u8>> {
+    // Retrieve the current timestamp.
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0);
+    // Serialize the peer IP and message.
+    let peer_ip = peer_ip.to_string();
+    let message = message.to_bytes_le()?;
+
+    let mut entry = Vec::with_capacity(8 + 2 + peer_ip.len() + 4 + message.len());
+    entry.extend_from_slice(&timestamp.to_le_bytes());
+    entry.extend_from_slice(&(peer_ip.len() as u16).to_le_bytes());
+    entry.extend_from_slice(peer_ip.as_bytes());
+    entry.extend_from_slice(&(message.len() as u32).to_le_bytes());
+    entry.extend_from_slice(&message);
+    Ok(entry)
+}
+
+/// Writes the received entries to the recording, until the sender is dropped.
+/// Each entry is flushed, so that the recording survives a crash.
+fn write_entries(mut writer: BufWriter<File>, receiver: Receiver<Vec<u8>>) {
+    while let Ok(entry) = receiver.recv() {
+        if let Err(error) = writer.write_all(&entry).and_then(|_| writer.flush()) {
+            warn!("Failed to write to the message recording - {error}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::Ping;
+    use snarkvm::prelude::Testnet3;
+
+    use std::net::Ipv4Addr;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_record_and_load() {
+        let path = std::env::temp_dir().join(format!("snarkos-test-message-recording-{}", std::process::id()));
+        let peer_ip = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 4130);
+
+        // Record a few messages, and close the recording.
+        let message = Message::<CurrentNetwork>::Ping(Ping::new(crate::messages::NodeType::Client, None));
+        let (sender, receiver) = mpsc::sync_channel(MESSAGE_RECORDER_CAPACITY);
+        sender.send(encode_entry(peer_ip, &message).unwrap()).unwrap();
+        sender.send(encode_entry(peer_ip, &message).unwrap()).unwrap();
+        drop(sender);
+        write_entries(BufWriter::new(File::create(&path).unwrap()), receiver);
+
+        // Load the recorded messages.
+        let messages = MessageRecorder::load::<CurrentNetwork, _>(&path).unwrap();
+        assert_eq!(messages.len(), 2);
+        for recorded in messages {
+            assert_eq!(recorded.peer_ip, peer_ip);
+            assert_eq!(recorded.message, message);
+        }
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_load_oversized_message() {
+        let path = std::env::temp_dir().join(format!("snarkos-test-oversized-recording-{}", std::process::id()));
+        let peer_ip = "127.0.0.1:4130";
+
+        // Write an entry whose message length exceeds the maximum message size.
+        let mut entry = 0u64.to_le_bytes().to_vec();
+        entry.extend_from_slice(&(peer_ip.len() as u16).to_le_bytes());
+        entry.extend_from_slice(peer_ip.as_bytes());
+        entry.extend_from_slice(&u32::MAX.to_le_bytes());
+        std::fs::write(&path, entry).unwrap();
+
+        // Ensure the entry is rejected.
+        let error = MessageRecorder::load::<CurrentNetwork, _>(&path).unwrap_err();
+        assert!(error.to_string().contains("oversized message"));
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use indexmap::IndexMap;
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::{borrow::Cow, net::SocketAddr};
+
+/// The message counters of a peer, aggregated by message type.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct PeerMessageStats {
+    /// The number of messages sent to the peer, by message name.
+    pub sent: IndexMap<Cow<'static, str>, u64>,
+    /// The number of messages received from the peer, by message name.
+    pub received: IndexMap<Cow<'static, str>, u64>,
+}
+
+#[derive(Debug, Default)]
+pub struct MessageStats {
+    /// The map of peer IPs to their message counters.
+    /// Note: The memory is bounded by the number of connected peers and message types.
+    peers: RwLock<IndexMap<SocketAddr, PeerMessageStats>>,
+}
+
+impl MessageStats {
+    /// Increments the counter of messages of the given type sent to the peer.
+    pub fn increment_sent(&self, peer_ip: SocketAddr, name: Cow<'static, str>) {
+        *self.peers.write().entry(peer_ip).or_default().sent.entry(name).or_default() += 1;
+    }
+
+    /// Increments the counter of messages of the given type received from the peer.
+    pub fn increment_received(&self, peer_ip: SocketAddr, name: Cow<'static, str>) {
+        *self.peers.write().entry(peer_ip).or_default().received.entry(name).or_default() += 1;
+    }
+
+    /// Returns the message counters for the given peer, if they exist.
+    pub fn get(&self, peer_ip: &SocketAddr) -> Option<PeerMessageStats> {
+        self.peers.read().get(peer_ip).cloned()
+    }
+
+    /// Returns the message counters for all peers.
+    pub fn get_all(&self) -> Vec<(SocketAddr, PeerMessageStats)> {
+        self.peers.read().iter().map(|(peer_ip, stats)| (*peer_ip, stats.clone())).collect()
+    }
+
+    /// Removes the message counters for the given peer.
+    pub fn remove(&self, peer_ip: &SocketAddr) {
+        self.peers.write().remove(peer_ip);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_message_stats() {
+        let stats = MessageStats::default();
+        let peer_ip = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 1234);
+
+        // Check that the stats are empty.
+        assert!(stats.get(&peer_ip).is_none());
+
+        // Record a few messages.
+        stats.increment_sent(peer_ip, "Ping".into());
+        stats.increment_sent(peer_ip, "Ping".into());
+        stats.increment_received(peer_ip, "Pong".into());
+
+        // Check that the messages are aggregated by type.
+        let peer_stats = stats.get(&peer_ip).unwrap();
+        assert_eq!(peer_stats.sent.len(), 1);
+        assert_eq!(peer_stats.sent.get("Ping"), Some(&2));
+        assert_eq!(peer_stats.received.get("Pong"), Some(&1));
+        assert_eq!(stats.get_all().len(), 1);
+
+        // Remove the peer.
+        stats.remove(&peer_ip);
+        assert!(stats.get(&peer_ip).is_none());
+    }
+}
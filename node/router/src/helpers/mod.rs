@@ -15,8 +15,26 @@
 mod cache;
 pub use cache::Cache;
 
+mod config;
+pub use config::*;
+
+mod connection_limits;
+pub use connection_limits::*;
+
+mod message_recorder;
+pub use message_recorder::*;
+
+mod message_stats;
+pub use message_stats::*;
+
 mod peer;
 pub use peer::*;
 
+mod peer_priority;
+pub use peer_priority::*;
+
+mod reconnect;
+pub use reconnect::*;
+
 mod resolver;
 pub use resolver::*;
@@ -28,6 +28,8 @@ pub struct Peer<N: Network> {
     node_type: NodeType,
     /// The message version of the peer.
     version: u32,
+    /// If `true`, the connection to the peer was initiated by this node.
+    is_outbound: bool,
     /// The timestamp of the first message received from the peer.
     first_seen: Instant,
     /// The timestamp of the last message received from this peer.
@@ -36,12 +38,13 @@ pub struct Peer<N: Network> {
 
 impl<N: Network> Peer<N> {
     /// Initializes a new instance of `Peer`.
-    pub fn new(listening_ip: SocketAddr, challenge_request: &ChallengeRequest<N>) -> Self {
+    pub fn new(listening_ip: SocketAddr, challenge_request: &ChallengeRequest<N>, is_outbound: bool) -> Self {
         Self {
             peer_ip: listening_ip,
             address: challenge_request.address,
             node_type: challenge_request.node_type,
             version: challenge_request.version,
+            is_outbound,
             first_seen: Instant::now(),
             last_seen: Instant::now(),
         }
@@ -82,6 +85,11 @@ impl<N: Network> Peer<N> {
         self.version
     }
 
+    /// Returns `true` if the connection to the peer was initiated by this node.
+    pub const fn is_outbound(&self) -> bool {
+        self.is_outbound
+    }
+
     /// Returns the first seen timestamp of the peer.
     pub fn first_seen(&self) -> Instant {
         self.first_seen
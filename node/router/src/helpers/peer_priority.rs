@@ -0,0 +1,42 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// The priority of a trusted peer that is not given one.
+pub const DEFAULT_PEER_PRIORITY: u8 = 0;
+
+/// Returns the minimum priority of the trusted peers to connect to, given the priority of each trusted peer,
+/// and whether it is ready, i.e. connected, or not yet retried since it was last connected.
+/// The trusted peers of a lower priority are held back while a trusted peer of a higher priority is ready.
+pub fn min_priority_to_connect(peers: impl IntoIterator<Item = (u8, bool)>) -> u8 {
+    peers.into_iter().filter(|(_, is_ready)| *is_ready).map(|(priority, _)| priority).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_priority_to_connect() {
+        // Without priorities, all trusted peers are connected to.
+        assert_eq!(min_priority_to_connect(Vec::<(u8, bool)>::new()), 0);
+        assert_eq!(min_priority_to_connect([(0, true), (0, false)]), 0);
+
+        // While the anchor is ready, the fallbacks are held back.
+        assert_eq!(min_priority_to_connect([(10, true), (0, true), (0, false)]), 10);
+        // Once the anchor failed to connect, the node falls back to the next priority.
+        assert_eq!(min_priority_to_connect([(10, false), (5, true), (0, true)]), 5);
+        assert_eq!(min_priority_to_connect([(10, false), (5, false), (0, true)]), 0);
+        assert_eq!(min_priority_to_connect([(10, false), (5, false), (0, false)]), 0);
+    }
+}
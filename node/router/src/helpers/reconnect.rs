@@ -0,0 +1,215 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{ensure, Result};
+use indexmap::IndexMap;
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::{
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+/// The exponential backoff parameters for reconnecting to trusted peers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ReconnectBackoff {
+    /// The delay after the first failed reconnection attempt.
+    pub initial: Duration,
+    /// The factor by which the delay grows after each failed attempt.
+    pub multiplier: u32,
+    /// The maximum delay in between reconnection attempts.
+    pub max: Duration,
+    /// The maximum number of consecutive reconnection attempts, or `0` to retry indefinitely.
+    pub max_attempts: u32,
+}
+
+impl Default for ReconnectBackoff {
+    /// Initializes the backoff to retry on every heartbeat, without growth.
+    fn default() -> Self {
+        Self { initial: Duration::from_secs(25), multiplier: 2, max: Duration::from_secs(25), max_attempts: 0 }
+    }
+}
+
+impl ReconnectBackoff {
+    /// Initializes a new reconnection backoff, ensuring the parameters are well-formed.
+    pub fn new(initial: Duration, multiplier: u32, max: Duration) -> Result<Self> {
+        ensure!(multiplier >= 1, "The reconnection backoff multiplier must be at least 1");
+        ensure!(max >= initial, "The maximum reconnection delay must be at least the initial delay");
+        Ok(Self { initial, multiplier, max, max_attempts: 0 })
+    }
+
+    /// Sets the maximum number of consecutive reconnection attempts, where `0` retries indefinitely.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+}
+
+/// The reconnection state of a peer, as reported in the peer diagnostics.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ReconnectStatus {
+    /// The number of consecutive reconnection attempts.
+    pub attempts: u32,
+    /// The current delay (in seconds) in between reconnection attempts.
+    pub delay_secs: u64,
+    /// The number of seconds until the next reconnection attempt.
+    pub next_attempt_in_secs: u64,
+}
+
+#[derive(Clone, Debug)]
+struct ReconnectState {
+    /// The number of consecutive reconnection attempts.
+    attempts: u32,
+    /// The current delay in between reconnection attempts.
+    delay: Duration,
+    /// The earliest time at which the next reconnection attempt may be made.
+    next_attempt: Instant,
+}
+
+#[derive(Debug, Default)]
+pub struct Reconnects {
+    /// The backoff parameters.
+    backoff: ReconnectBackoff,
+    /// The map of peer IPs to their reconnection state.
+    peers: RwLock<IndexMap<SocketAddr, ReconnectState>>,
+}
+
+impl Reconnects {
+    /// Initializes the reconnection tracker with the given backoff parameters.
+    pub fn new(backoff: ReconnectBackoff) -> Self {
+        Self { backoff, peers: Default::default() }
+    }
+
+    /// Returns `true` if a reconnection attempt to the given peer is due.
+    pub fn is_due(&self, peer_ip: &SocketAddr) -> bool {
+        self.peers.read().get(peer_ip).map(|state| Instant::now() >= state.next_attempt).unwrap_or(true)
+    }
+
+    /// Returns `true` if reconnection attempts to the given peer were made since it was last connected.
+    pub fn is_reconnecting(&self, peer_ip: &SocketAddr) -> bool {
+        self.peers.read().contains_key(peer_ip)
+    }
+
+    /// Returns `true` if the maximum number of reconnection attempts to the given peer has been reached.
+    pub fn is_exhausted(&self, peer_ip: &SocketAddr) -> bool {
+        let max_attempts = self.backoff.max_attempts;
+        max_attempts > 0 && self.peers.read().get(peer_ip).map(|state| state.attempts >= max_attempts).unwrap_or(false)
+    }
+
+    /// Records a reconnection attempt to the given peer, and schedules the next attempt.
+    /// Returns the number of consecutive reconnection attempts to the peer.
+    pub fn record_attempt(&self, peer_ip: SocketAddr) -> u32 {
+        let now = Instant::now();
+        let mut peers = self.peers.write();
+        let state = peers.entry(peer_ip).or_insert_with(|| ReconnectState {
+            attempts: 0,
+            delay: self.backoff.initial,
+            next_attempt: now,
+        });
+        // Grow the delay after each consecutive attempt.
+        if state.attempts > 0 {
+            state.delay = state.delay.saturating_mul(self.backoff.multiplier).min(self.backoff.max);
+        }
+        state.attempts += 1;
+        state.next_attempt = now + state.delay;
+        state.attempts
+    }
+
+    /// Resets the reconnection state of the given peer.
+    /// Returns the number of reconnection attempts that were made, if the peer was being reconnected to.
+    pub fn reset(&self, peer_ip: &SocketAddr) -> Option<u32> {
+        self.peers.write().remove(peer_ip).map(|state| state.attempts)
+    }
+
+    /// Returns the reconnection state of all peers that are being reconnected to.
+    pub fn get_all(&self) -> Vec<(SocketAddr, ReconnectStatus)> {
+        let now = Instant::now();
+        self.peers
+            .read()
+            .iter()
+            .map(|(peer_ip, state)| {
+                (*peer_ip, ReconnectStatus {
+                    attempts: state.attempts,
+                    delay_secs: state.delay.as_secs(),
+                    next_attempt_in_secs: state.next_attempt.saturating_duration_since(now).as_secs(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_reconnect_backoff_new() {
+        assert!(ReconnectBackoff::new(Duration::from_secs(5), 2, Duration::from_secs(60)).is_ok());
+        assert!(ReconnectBackoff::new(Duration::from_secs(60), 2, Duration::from_secs(5)).is_err());
+        assert!(ReconnectBackoff::new(Duration::from_secs(5), 0, Duration::from_secs(60)).is_err());
+    }
+
+    #[test]
+    fn test_reconnects() {
+        let backoff = ReconnectBackoff::new(Duration::from_secs(10), 2, Duration::from_secs(30)).unwrap();
+        let reconnects = Reconnects::new(backoff);
+        let peer_ip = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 4130);
+
+        // The first attempt is always due.
+        assert!(reconnects.is_due(&peer_ip));
+        assert!(!reconnects.is_reconnecting(&peer_ip));
+
+        // Record a few attempts, and check the delay grows up to the maximum.
+        reconnects.record_attempt(peer_ip);
+        assert!(!reconnects.is_due(&peer_ip));
+        assert!(reconnects.is_reconnecting(&peer_ip));
+        assert_eq!(reconnects.get_all()[0].1.delay_secs, 10);
+        reconnects.record_attempt(peer_ip);
+        assert_eq!(reconnects.get_all()[0].1.delay_secs, 20);
+        reconnects.record_attempt(peer_ip);
+        assert_eq!(reconnects.get_all()[0].1.delay_secs, 30);
+        reconnects.record_attempt(peer_ip);
+        assert_eq!(reconnects.get_all()[0].1.delay_secs, 30);
+        assert_eq!(reconnects.get_all()[0].1.attempts, 4);
+
+        // Without a maximum, the attempts are never exhausted.
+        assert!(!reconnects.is_exhausted(&peer_ip));
+
+        // Reset the peer.
+        assert_eq!(reconnects.reset(&peer_ip), Some(4));
+        assert!(reconnects.is_due(&peer_ip));
+        assert!(!reconnects.is_reconnecting(&peer_ip));
+        assert!(reconnects.get_all().is_empty());
+        assert_eq!(reconnects.reset(&peer_ip), None);
+    }
+
+    #[test]
+    fn test_reconnects_max_attempts() {
+        let backoff = ReconnectBackoff::new(Duration::from_secs(1), 2, Duration::from_secs(60)).unwrap();
+        let reconnects = Reconnects::new(backoff.with_max_attempts(2));
+        let peer_ip = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 4130);
+
+        assert!(!reconnects.is_exhausted(&peer_ip));
+        assert_eq!(reconnects.record_attempt(peer_ip), 1);
+        assert!(!reconnects.is_exhausted(&peer_ip));
+        assert_eq!(reconnects.record_attempt(peer_ip), 2);
+        assert!(reconnects.is_exhausted(&peer_ip));
+
+        // Resetting the peer allows it to be retried again.
+        reconnects.reset(&peer_ip);
+        assert!(!reconnects.is_exhausted(&peer_ip));
+    }
+}
@@ -24,8 +24,10 @@ use crate::{
         UnconfirmedSolution,
         UnconfirmedTransaction,
     },
+    MessageRecorder,
     Outbound,
     Peer,
+    RecordedMessage,
 };
 use snarkos_node_tcp::protocols::Reading;
 use snarkvm::prelude::{
@@ -35,20 +37,44 @@ use snarkvm::prelude::{
 };
 
 use anyhow::{anyhow, bail, Result};
-use std::{net::SocketAddr, time::Instant};
+use std::{net::SocketAddr, path::Path, time::Instant};
 use tokio::task::spawn_blocking;
 
+/// The duration in seconds to sleep in between ping requests with a connected peer.
+pub const PING_SLEEP_IN_SECS: u64 = 20; // 20 seconds
+
 #[async_trait]
 pub trait Inbound<N: Network>: Reading + Outbound<N> {
     /// The maximum number of puzzle requests per interval.
     const MAXIMUM_PUZZLE_REQUESTS_PER_INTERVAL: usize = 5;
     /// The duration in seconds to sleep in between ping requests with a connected peer.
-    const PING_SLEEP_IN_SECS: u64 = 20; // 20 seconds
+    const PING_SLEEP_IN_SECS: u64 = PING_SLEEP_IN_SECS;
     /// The time frame to enforce the `MESSAGE_LIMIT`.
     const MESSAGE_LIMIT_TIME_FRAME_IN_SECS: i64 = 5;
     /// The maximum number of messages accepted within `MESSAGE_LIMIT_TIME_FRAME_IN_SECS`.
     const MESSAGE_LIMIT: usize = 500;
 
+    /// Replays the inbound messages recorded in the file at the given path, in order.
+    /// Returns the number of messages that were handled successfully.
+    async fn replay_messages(&self, path: &Path) -> Result<usize> {
+        // Load the recorded messages.
+        let messages = MessageRecorder::load::<N, _>(path)?;
+        info!("Replaying {} recorded messages from '{}'", messages.len(), path.display());
+
+        let mut num_handled = 0;
+        for RecordedMessage { timestamp, peer_ip, message } in messages {
+            // Resolve the peer to itself, as there is no live connection to the peer.
+            self.router().resolver.insert_peer(peer_ip, peer_ip);
+            // Handle the message.
+            let name = message.name();
+            match self.inbound(peer_ip, message).await {
+                Ok(()) => num_handled += 1,
+                Err(error) => warn!("Replayed '{name}' from '{peer_ip}' (at {timestamp}) failed - {error}"),
+            }
+        }
+        Ok(num_handled)
+    }
+
     /// Handles the inbound message from the peer.
     async fn inbound(&self, peer_addr: SocketAddr, message: Message<N>) -> Result<()> {
         // Retrieve the listener IP for the peer.
@@ -65,6 +91,12 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
         }
 
         trace!("Received '{}' from '{peer_ip}'", message.name());
+        // Update the message counters for the peer.
+        self.router().message_stats.increment_received(peer_ip, message.name());
+        #[cfg(feature = "metrics")]
+        metrics::increment_counter!(metrics::router::MESSAGES_RECEIVED, "type" => message.name().to_string());
+        // Record the message, if message recording is enabled.
+        self.router().record_message(peer_ip, &message);
 
         // This match statement handles the inbound message by deserializing the message,
         // checking the message is valid, and then calling the appropriate (trait) handler.
@@ -80,6 +112,17 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
                 if end_height - start_height > DataBlocks::<N>::MAXIMUM_NUMBER_OF_BLOCKS as u32 {
                     bail!("Block request from '{peer_ip}' has an excessive range ({start_height}..{end_height})")
                 }
+                // Drop the block request if the peer exceeds the serve rate limit.
+                let num_recent_requests = self.router().cache.insert_inbound_block_request(peer_ip);
+                if self.router().connection_limits().is_serve_rate_exceeded(num_recent_requests) {
+                    debug!("Throttling block request from '{peer_ip}' ({num_recent_requests} in the last minute)");
+                    #[cfg(feature = "metrics")]
+                    metrics::increment_counter!(
+                        metrics::router::THROTTLED_BLOCK_REQUESTS,
+                        "peer" => peer_ip.to_string()
+                    );
+                    return Ok(());
+                }
 
                 let node = self.clone();
                 match spawn_blocking(move || node.block_request(peer_ip, message)).await? {
@@ -39,15 +39,27 @@ pub use outbound::*;
 mod routing;
 pub use routing::*;
 
-use crate::messages::NodeType;
+use crate::messages::{Message, MessageCodec, NodeType, MAXIMUM_MESSAGE_SIZE};
 use snarkos_account::Account;
-use snarkos_node_tcp::{is_bogon_ip, is_unspecified_ip, Config, Tcp};
+use snarkos_node_tcp::{is_bogon_ip, is_unspecified_ip, Config, Stats, Tcp};
 use snarkvm::prelude::{Address, Network, PrivateKey, ViewKey};
 
 use anyhow::{bail, Result};
 use indexmap::{IndexMap, IndexSet};
 use parking_lot::{Mutex, RwLock};
-use std::{collections::HashSet, future::Future, net::SocketAddr, ops::Deref, str::FromStr, sync::Arc, time::Instant};
+use std::{
+    collections::HashSet,
+    future::Future,
+    net::SocketAddr,
+    ops::Deref,
+    path::Path,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 use tokio::task::JoinHandle;
 
 #[derive(Clone)]
@@ -70,10 +82,16 @@ pub struct InnerRouter<N: Network> {
     account: Account<N>,
     /// The cache.
     cache: Cache<N>,
+    /// The per-peer message counters.
+    message_stats: MessageStats,
+    /// The recorder for inbound messages, if message recording is enabled.
+    message_recorder: RwLock<Option<MessageRecorder>>,
     /// The resolver.
     resolver: Resolver,
     /// The set of trusted peers.
     trusted_peers: IndexSet<SocketAddr>,
+    /// The priorities of the trusted peers.
+    peer_priorities: IndexMap<SocketAddr, u8>,
     /// The map of connected peer IPs to their peer handlers.
     connected_peers: RwLock<IndexMap<SocketAddr, Peer<N>>>,
     /// The set of handshaking peers. While `Tcp` already recognizes the connecting IP addresses
@@ -83,15 +101,41 @@ pub struct InnerRouter<N: Network> {
     connecting_peers: Mutex<HashSet<SocketAddr>>,
     /// The set of candidate peer IPs.
     candidate_peers: RwLock<IndexSet<SocketAddr>>,
-    /// The set of restricted peer IPs.
+    /// The map of restricted peer IPs to the time at which their restriction expires.
     restricted_peers: RwLock<IndexMap<SocketAddr, Instant>>,
     /// The spawned handles.
     handles: Mutex<Vec<JoinHandle<()>>>,
+    /// The maximum number of running tasks, beyond which a task leak is reported (`0` means no limit).
+    max_tasks: usize,
+    /// If `true`, tasks beyond the maximum number of running tasks are refused.
+    refuse_excess_tasks: bool,
     /// The boolean flag for the development mode.
     is_dev: bool,
+    /// The shared secret peers must prove knowledge of during the handshake, if any.
+    network_secret: Option<String>,
+    /// The per-type limits on the number of connected peers.
+    connection_limits: ConnectionLimits,
+    /// The boolean flag for whether the node is synced, as reported by the node.
+    is_synced: AtomicBool,
+    /// If `true`, unconfirmed solutions and transactions are only relayed once the node is synced.
+    relay_after_sync: AtomicBool,
+    /// If `true`, the node checks whether the network has upgraded past its own message version.
+    upgrade_awareness: AtomicBool,
+    /// The boolean flag for whether the network has upgraded past the node's own message version.
+    is_outdated: AtomicBool,
+    /// If `true`, peers that send a block which fails verification are restricted, instead of only disconnected.
+    ban_on_invalid_block: AtomicBool,
+    /// The greatest block height advertised by the peers, as reported by the node.
+    greatest_peer_height: AtomicU32,
+    /// The timestamp of when the router was initialized.
+    launched: Instant,
+    /// The reconnection state of the trusted peers.
+    reconnects: Reconnects,
 }
 
 impl<N: Network> Router<N> {
+    /// The tag mixed into the handshake challenge by development nodes.
+    const DEV_CHALLENGE_TAG: &'static [u8] = b"snarkos-dev";
     /// The maximum number of candidate peers permitted to be stored in the node.
     const MAXIMUM_CANDIDATE_PEERS: usize = 10_000;
     /// The maximum number of connection failures permitted by an inbound connecting peer.
@@ -103,6 +147,7 @@ impl<N: Network> Router<N> {
 
 impl<N: Network> Router<N> {
     /// Initializes a new `Router` instance.
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         node_ip: SocketAddr,
         node_type: NodeType,
@@ -110,23 +155,51 @@ impl<N: Network> Router<N> {
         trusted_peers: &[SocketAddr],
         max_peers: u16,
         is_dev: bool,
+        router_config: RouterConfig,
     ) -> Result<Self> {
-        // Initialize the TCP stack.
-        let tcp = Tcp::new(Config::new(node_ip, max_peers));
+        let RouterConfig {
+            network_secret,
+            connection_limits,
+            reconnect_backoff,
+            peer_priorities,
+            max_tasks,
+            refuse_excess_tasks,
+        } = router_config;
+        // Initialize the TCP stack, with the read and write deadline for each connection, and any additional listener.
+        let mut config = Config::new(node_ip, max_peers);
+        config.io_timeout_ms = connection_limits.io_timeout_secs.map(|secs| secs.saturating_mul(1000));
+        config.extra_listening_addrs = connection_limits.extra_listener.into_iter().collect();
+        config.ipv6_only = connection_limits.ipv6_only;
+        let tcp = Tcp::new(config);
         // Initialize the router.
         Ok(Self(Arc::new(InnerRouter {
             tcp,
             node_type,
             account,
             cache: Default::default(),
+            message_stats: Default::default(),
+            message_recorder: Default::default(),
             resolver: Default::default(),
             trusted_peers: trusted_peers.iter().copied().collect(),
+            peer_priorities,
             connected_peers: Default::default(),
             connecting_peers: Default::default(),
             candidate_peers: Default::default(),
             restricted_peers: Default::default(),
             handles: Default::default(),
+            max_tasks: max_tasks.unwrap_or(0),
+            refuse_excess_tasks,
             is_dev,
+            network_secret,
+            connection_limits,
+            is_synced: AtomicBool::new(true),
+            relay_after_sync: Default::default(),
+            upgrade_awareness: Default::default(),
+            is_outdated: Default::default(),
+            ban_on_invalid_block: Default::default(),
+            greatest_peer_height: Default::default(),
+            launched: Instant::now(),
+            reconnects: Reconnects::new(reconnect_backoff),
         })))
     }
 }
@@ -169,6 +242,12 @@ impl<N: Network> Router<N> {
         if self.number_of_connected_peers() >= self.max_connected_peers() {
             bail!("Dropping connection attempt to '{peer_ip}' (maximum peers reached)")
         }
+        // Ensure the node does not surpass the maximum number of outbound connections, unless the peer is trusted.
+        if self.connection_limits.is_direction_exceeded(true, self.number_of_outbound_peers())
+            && !self.trusted_peers().contains(&peer_ip)
+        {
+            bail!("Dropping connection attempt to '{peer_ip}' (maximum outbound peers reached)")
+        }
         // Ensure the node is not already connected to this peer.
         if self.is_connected(&peer_ip) {
             bail!("Dropping connection attempt to '{peer_ip}' (already connected)")
@@ -240,6 +319,68 @@ impl<N: Network> Router<N> {
         self.account.address()
     }
 
+    /// Returns `true` if the node last reported itself as synced.
+    pub fn is_synced(&self) -> bool {
+        self.is_synced.load(Ordering::SeqCst)
+    }
+
+    /// Updates whether the node is synced.
+    pub fn set_synced(&self, is_synced: bool) {
+        self.is_synced.store(is_synced, Ordering::SeqCst);
+    }
+
+    /// Pauses the relay of unconfirmed solutions and transactions whenever the node is not synced.
+    pub fn enable_relay_after_sync(&self) {
+        self.relay_after_sync.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if the node relays unconfirmed solutions and transactions to its peers.
+    pub fn is_relaying(&self) -> bool {
+        !self.is_outdated() && (!self.relay_after_sync.load(Ordering::SeqCst) || self.is_synced())
+    }
+
+    /// Enables the detection of a network upgrade past the node's own message version,
+    /// which pauses the production and relay of solutions and transactions.
+    pub fn enable_upgrade_awareness(&self) {
+        self.upgrade_awareness.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if the node checks whether the network has upgraded past its own message version.
+    pub fn is_upgrade_aware(&self) -> bool {
+        self.upgrade_awareness.load(Ordering::SeqCst)
+    }
+
+    /// Returns `true` if upgrade awareness is enabled, and the network has upgraded past the node's message version.
+    pub fn is_outdated(&self) -> bool {
+        self.is_upgrade_aware() && self.is_outdated.load(Ordering::SeqCst)
+    }
+
+    /// Updates whether the network has upgraded past the node's own message version.
+    /// Returns the previous value.
+    pub fn set_outdated(&self, is_outdated: bool) -> bool {
+        self.is_outdated.swap(is_outdated, Ordering::SeqCst)
+    }
+
+    /// Returns the greatest block height advertised by the peers, as last reported by the node.
+    pub fn greatest_peer_height(&self) -> u32 {
+        self.greatest_peer_height.load(Ordering::SeqCst)
+    }
+
+    /// Updates the greatest block height advertised by the peers.
+    pub fn set_greatest_peer_height(&self, height: u32) {
+        self.greatest_peer_height.store(height, Ordering::SeqCst);
+    }
+
+    /// Returns the TCP statistics (message and byte counters) of the node.
+    pub fn stats(&self) -> &Stats {
+        self.tcp.stats()
+    }
+
+    /// Returns the duration since the router was initialized.
+    pub fn uptime(&self) -> Duration {
+        self.launched.elapsed()
+    }
+
     /// Returns `true` if the node is in development mode.
     pub fn is_dev(&self) -> bool {
         self.is_dev
@@ -282,11 +423,7 @@ impl<N: Network> Router<N> {
 
     /// Returns `true` if the given IP is restricted.
     pub fn is_restricted(&self, ip: &SocketAddr) -> bool {
-        self.restricted_peers
-            .read()
-            .get(ip)
-            .map(|time| time.elapsed().as_secs() < Self::RADIO_SILENCE_IN_SECS)
-            .unwrap_or(false)
+        self.restricted_peers.read().get(ip).map(|expiry| Instant::now() < *expiry).unwrap_or(false)
     }
 
     /// Returns the maximum number of connected peers.
@@ -294,11 +431,48 @@ impl<N: Network> Router<N> {
         self.tcp.config().max_connections as usize
     }
 
+    /// Returns the per-type limits on the number of connected peers.
+    pub fn connection_limits(&self) -> &ConnectionLimits {
+        &self.connection_limits
+    }
+
+    /// Returns the codec for the messages from the given peer, which enforces the maximum message size.
+    /// A peer that sends an oversized message is restricted, in addition to being disconnected.
+    pub fn message_codec(&self, peer_addr: SocketAddr) -> MessageCodec<N> {
+        let max_message_size = self.connection_limits.max_message_size.unwrap_or(MAXIMUM_MESSAGE_SIZE);
+        let router = self.clone();
+        MessageCodec::with_max_message_size(
+            max_message_size,
+            Some(Arc::new(move |size| {
+                let peer_ip = router.resolve_to_listener(&peer_addr).unwrap_or(peer_addr);
+                warn!("Peer '{peer_ip}' sent an oversized message ({size} bytes, the maximum is {max_message_size})");
+                #[cfg(feature = "metrics")]
+                metrics::increment_counter!(metrics::router::OVERSIZED_MESSAGES, "peer" => peer_ip.to_string());
+                router.insert_restricted_peer(peer_ip);
+            })),
+        )
+    }
+
+    /// Returns the number of connected peers of the given node type.
+    pub fn number_of_connected_peers_of_type(&self, node_type: NodeType) -> usize {
+        self.connected_peers.read().values().filter(|peer| peer.node_type() == node_type).count()
+    }
+
     /// Returns the number of connected peers.
     pub fn number_of_connected_peers(&self) -> usize {
         self.connected_peers.read().len()
     }
 
+    /// Returns the number of peers connected by an inbound connection.
+    pub fn number_of_inbound_peers(&self) -> usize {
+        self.connected_peers.read().values().filter(|peer| !peer.is_outbound()).count()
+    }
+
+    /// Returns the number of peers connected by an outbound connection.
+    pub fn number_of_outbound_peers(&self) -> usize {
+        self.connected_peers.read().values().filter(|peer| peer.is_outbound()).count()
+    }
+
     /// Returns the number of connected validators.
     pub fn number_of_connected_validators(&self) -> usize {
         self.connected_peers.read().values().filter(|peer| peer.is_validator()).count()
@@ -319,6 +493,13 @@ impl<N: Network> Router<N> {
         self.candidate_peers.read().len()
     }
 
+    /// Returns `true` if the node has no connected peers, and no candidate or bootstrap peers to connect to.
+    pub fn is_isolated(&self) -> bool {
+        self.number_of_connected_peers() == 0
+            && self.number_of_candidate_peers() == 0
+            && self.bootstrap_peers().is_empty()
+    }
+
     /// Returns the number of restricted peers.
     pub fn number_of_restricted_peers(&self) -> usize {
         self.restricted_peers.read().len()
@@ -369,6 +550,11 @@ impl<N: Network> Router<N> {
         &self.trusted_peers
     }
 
+    /// Returns the priority of the given trusted peer.
+    pub fn peer_priority(&self, peer_ip: &SocketAddr) -> u8 {
+        self.peer_priorities.get(peer_ip).copied().unwrap_or(DEFAULT_PEER_PRIORITY)
+    }
+
     /// Returns the list of bootstrap peers.
     pub fn bootstrap_peers(&self) -> Vec<SocketAddr> {
         if cfg!(feature = "test") || self.is_dev {
@@ -388,6 +574,31 @@ impl<N: Network> Router<N> {
         self.connected_peers.read().iter().map(|(ip, peer)| (*ip, peer.node_type())).collect()
     }
 
+    /// Returns the message counters for the connected peers, aggregated by message type.
+    pub fn connected_message_stats(&self) -> Vec<(SocketAddr, PeerMessageStats)> {
+        self.message_stats.get_all()
+    }
+
+    /// Returns the reconnection state of the trusted peers that are being reconnected to.
+    pub fn reconnect_statuses(&self) -> Vec<(SocketAddr, ReconnectStatus)> {
+        self.reconnects.get_all()
+    }
+
+    /// Enables the recording of all inbound messages to the file at the given path.
+    pub fn enable_message_recording<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        *self.message_recorder.write() = Some(MessageRecorder::new(path)?);
+        Ok(())
+    }
+
+    /// Records the given inbound message from the given peer, if message recording is enabled.
+    pub fn record_message(&self, peer_ip: SocketAddr, message: &Message<N>) {
+        if let Some(recorder) = self.message_recorder.read().as_ref() {
+            if let Err(error) = recorder.record(peer_ip, message) {
+                warn!("Failed to record the '{}' message from '{peer_ip}' - {error}", message.name());
+            }
+        }
+    }
+
     /// Inserts the given peer into the connected peers.
     pub fn insert_connected_peer(&self, peer: Peer<N>, peer_addr: SocketAddr) {
         let peer_ip = peer.ip();
@@ -399,6 +610,11 @@ impl<N: Network> Router<N> {
         self.candidate_peers.write().remove(&peer_ip);
         // Remove this peer from the restricted peers, if it exists.
         self.restricted_peers.write().remove(&peer_ip);
+        #[cfg(feature = "metrics")]
+        {
+            metrics::increment_counter!(metrics::peers::CONNECTS);
+            metrics::gauge!(metrics::peers::CONNECTED, self.number_of_connected_peers() as f64);
+        }
     }
 
     /// Inserts the given peer IPs to the set of candidate peers.
@@ -423,10 +639,35 @@ impl<N: Network> Router<N> {
 
     /// Inserts the given peer into the restricted peers.
     pub fn insert_restricted_peer(&self, peer_ip: SocketAddr) {
+        self.insert_restricted_peer_for(peer_ip, Duration::from_secs(Self::RADIO_SILENCE_IN_SECS));
+    }
+
+    /// Inserts the given peer into the restricted peers, for the given duration.
+    pub fn insert_restricted_peer_for(&self, peer_ip: SocketAddr, duration: Duration) {
         // Remove this peer from the candidate peers, if it exists.
         self.candidate_peers.write().remove(&peer_ip);
-        // Add the peer to the restricted peers.
-        self.restricted_peers.write().insert(peer_ip, Instant::now());
+        // Add the peer to the restricted peers, saturating the expiry for an excessive duration.
+        let now = Instant::now();
+        let expiry = now.checked_add(duration).unwrap_or(now + Duration::from_secs(u32::MAX as u64));
+        self.restricted_peers.write().insert(peer_ip, expiry);
+    }
+
+    /// Restricts (instead of only disconnecting) the peers that send a block which fails verification.
+    pub fn enable_ban_on_invalid_block(&self) {
+        self.ban_on_invalid_block.store(true, Ordering::SeqCst);
+    }
+
+    /// Penalizes the given peer for sending a block which failed verification,
+    /// by disconnecting from it, and restricting it if so configured.
+    pub fn penalize_invalid_block_sender(&self, peer_ip: SocketAddr) {
+        match self.ban_on_invalid_block.load(Ordering::SeqCst) {
+            true => {
+                warn!("Banning '{peer_ip}' for sending an invalid block");
+                self.insert_restricted_peer(peer_ip);
+            }
+            false => warn!("Disconnecting from '{peer_ip}' for sending an invalid block"),
+        }
+        self.disconnect(peer_ip);
     }
 
     /// Updates the connected peer with the given function.
@@ -454,8 +695,15 @@ impl<N: Network> Router<N> {
         self.resolver.remove_peer(&peer_ip);
         // Remove this peer from the connected peers, if it exists.
         self.connected_peers.write().remove(&peer_ip);
+        // Remove the message counters for this peer.
+        self.message_stats.remove(&peer_ip);
         // Add the peer to the candidate peers.
         self.candidate_peers.write().insert(peer_ip);
+        #[cfg(feature = "metrics")]
+        {
+            metrics::increment_counter!(metrics::peers::DISCONNECTS);
+            metrics::gauge!(metrics::peers::CONNECTED, self.number_of_connected_peers() as f64);
+        }
     }
 
     #[cfg(feature = "test")]
@@ -468,9 +716,28 @@ impl<N: Network> Router<N> {
         self.candidate_peers.write().remove(&peer_ip);
     }
 
+    /// Returns the number of running tasks spawned by the router.
+    pub fn number_of_tasks(&self) -> usize {
+        self.handles.lock().iter().filter(|handle| !handle.is_finished()).count()
+    }
+
     /// Spawns a task with the given future; it should only be used for long-running tasks.
     pub fn spawn<T: Future<Output = ()> + Send + 'static>(&self, future: T) {
-        self.handles.lock().push(tokio::spawn(future));
+        let mut handles = self.handles.lock();
+        // Prune the handles of the finished tasks.
+        handles.retain(|handle| !handle.is_finished());
+        // Check the number of running tasks against the limit, if one is set.
+        let max_tasks = self.max_tasks;
+        if max_tasks > 0 && handles.len() >= max_tasks {
+            if self.refuse_excess_tasks {
+                warn!("Refusing to spawn a task ({} tasks are running; the limit is {max_tasks})", handles.len());
+                return;
+            }
+            warn!("{} tasks are running, above the limit of {max_tasks} (likely a task leak)", handles.len() + 1);
+        }
+        handles.push(tokio::spawn(future));
+        #[cfg(feature = "metrics")]
+        metrics::gauge!(metrics::router::TASKS, handles.len() as f64);
     }
 
     /// Shuts down the router.
@@ -61,6 +61,10 @@ pub trait Outbound<N: Network>: Writing<Message = Message<N>> {
         }
         // Retrieve the message name.
         let name = message.name();
+        // Update the message counters for the peer.
+        self.router().message_stats.increment_sent(peer_ip, name.clone());
+        #[cfg(feature = "metrics")]
+        metrics::increment_counter!(metrics::router::MESSAGES_SENT, "type" => name.to_string());
         // Send the message to the peer.
         trace!("Sending '{name}' to '{peer_ip}'");
         let result = self.unicast(peer_addr, message);
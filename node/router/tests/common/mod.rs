@@ -23,7 +23,7 @@ use std::{
 };
 
 use snarkos_account::Account;
-use snarkos_node_router::{messages::NodeType, Router};
+use snarkos_node_router::{messages::NodeType, ConnectionLimits, Router, RouterConfig};
 use snarkvm::prelude::{block::Block, FromBytes, Network, Testnet3 as CurrentNetwork};
 
 /// A helper macro to print the TCP listening address, along with the connected and connecting peers.
@@ -78,6 +78,7 @@ pub async fn client(listening_port: u16, max_peers: u16) -> TestRouter<CurrentNe
         &[],
         max_peers,
         true,
+        Default::default(),
     )
     .await
     .expect("couldn't create client router")
@@ -94,6 +95,7 @@ pub async fn prover(listening_port: u16, max_peers: u16) -> TestRouter<CurrentNe
         &[],
         max_peers,
         true,
+        Default::default(),
     )
     .await
     .expect("couldn't create prover router")
@@ -110,8 +112,64 @@ pub async fn validator(listening_port: u16, max_peers: u16) -> TestRouter<Curren
         &[],
         max_peers,
         true,
+        Default::default(),
     )
     .await
     .expect("couldn't create validator router")
     .into()
 }
+
+/// Initializes a client router that requires the given network secret from its peers.
+#[allow(dead_code)]
+pub async fn private_client(listening_port: u16, max_peers: u16, network_secret: &str) -> TestRouter<CurrentNetwork> {
+    Router::new(
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listening_port),
+        NodeType::Client,
+        sample_account(),
+        &[],
+        max_peers,
+        true,
+        RouterConfig { network_secret: Some(network_secret.to_string()), ..Default::default() },
+    )
+    .await
+    .expect("couldn't create private client router")
+    .into()
+}
+
+/// Initializes a validator router with the given per-type connection limits.
+#[allow(dead_code)]
+pub async fn limited_validator(
+    listening_port: u16,
+    max_peers: u16,
+    limits: ConnectionLimits,
+) -> TestRouter<CurrentNetwork> {
+    Router::new(
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listening_port),
+        NodeType::Validator,
+        sample_account(),
+        &[],
+        max_peers,
+        true,
+        RouterConfig { connection_limits: limits, ..Default::default() },
+    )
+    .await
+    .expect("couldn't create limited validator router")
+    .into()
+}
+
+/// Initializes a client router that refuses to run more than the given number of tasks.
+#[allow(dead_code)]
+pub async fn task_limited_client(listening_port: u16, max_peers: u16, max_tasks: usize) -> TestRouter<CurrentNetwork> {
+    Router::new(
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listening_port),
+        NodeType::Client,
+        sample_account(),
+        &[],
+        max_peers,
+        true,
+        RouterConfig { max_tasks: Some(max_tasks), refuse_excess_tasks: true, ..Default::default() },
+    )
+    .await
+    .expect("couldn't create task-limited client router")
+    .into()
+}
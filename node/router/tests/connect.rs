@@ -15,7 +15,11 @@
 mod common;
 use common::*;
 
-use snarkos_node_tcp::{protocols::Handshake, P2P};
+use snarkos_node_router::ConnectionLimits;
+use snarkos_node_tcp::{
+    protocols::{Handshake, Reading},
+    P2P,
+};
 
 use core::time::Duration;
 
@@ -150,6 +154,173 @@ async fn test_connect_with_handshake() {
     }
 }
 
+#[tokio::test]
+async fn test_connect_with_network_secret() {
+    // Create 3 routers, two of which share the same network secret.
+    let node0 = private_client(0, 2, "secret").await;
+    let node1 = private_client(0, 2, "secret").await;
+    let node2 = private_client(0, 2, "other").await;
+
+    // Enable handshake protocol.
+    node0.enable_handshake().await;
+    node1.enable_handshake().await;
+    node2.enable_handshake().await;
+
+    // Start listening.
+    node0.tcp().enable_listener().await.unwrap();
+    node1.tcp().enable_listener().await.unwrap();
+    node2.tcp().enable_listener().await.unwrap();
+
+    {
+        // Connect node0 to node1.
+        node0.connect(node1.local_ip());
+        // Sleep briefly.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        print_tcp!(node0);
+        print_tcp!(node1);
+
+        // Check the router level.
+        assert_eq!(node0.number_of_connected_peers(), 1);
+        assert_eq!(node1.number_of_connected_peers(), 1);
+    }
+    {
+        // Connect node2 to node0.
+        node2.connect(node0.local_ip());
+        // Sleep briefly.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        print_tcp!(node0);
+        print_tcp!(node2);
+
+        // Check the router level, ensuring the peer with the wrong secret was rejected.
+        assert_eq!(node0.number_of_connected_peers(), 1);
+        assert_eq!(node2.number_of_connected_peers(), 0);
+    }
+}
+
+#[tokio::test]
+async fn test_connect_with_connection_limits() {
+    // Create a validator that accepts at most 1 client, along with 2 clients.
+    let limits = ConnectionLimits { max_clients: Some(1), ..Default::default() };
+    let node0 = limited_validator(0, 3, limits).await;
+    let node1 = client(0, 3).await;
+    let node2 = client(0, 3).await;
+
+    // Enable handshake protocol.
+    node0.enable_handshake().await;
+    node1.enable_handshake().await;
+    node2.enable_handshake().await;
+
+    // Start listening.
+    node0.tcp().enable_listener().await.unwrap();
+    node1.tcp().enable_listener().await.unwrap();
+    node2.tcp().enable_listener().await.unwrap();
+
+    {
+        // Connect node1 to node0.
+        node1.connect(node0.local_ip());
+        // Sleep briefly.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        print_tcp!(node0);
+        print_tcp!(node1);
+
+        // Check the router level.
+        assert_eq!(node0.number_of_connected_peers(), 1);
+        assert_eq!(node1.number_of_connected_peers(), 1);
+    }
+    {
+        // Connect node2 to node0.
+        node2.connect(node0.local_ip());
+        // Sleep briefly.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        print_tcp!(node0);
+        print_tcp!(node2);
+
+        // Check the router level, ensuring the second client was rejected.
+        assert_eq!(node0.number_of_connected_peers(), 1);
+        assert_eq!(node2.number_of_connected_peers(), 0);
+    }
+}
+
+#[tokio::test]
+async fn test_connect_with_direction_limits() {
+    // Create a validator that accepts at most 1 inbound peer and makes no outbound connections, along with 2 clients.
+    let limits = ConnectionLimits { max_inbound: Some(1), max_outbound: Some(0), ..Default::default() };
+    let node0 = limited_validator(0, 3, limits).await;
+    let node1 = client(0, 3).await;
+    let node2 = client(0, 3).await;
+
+    // Enable handshake protocol.
+    node0.enable_handshake().await;
+    node1.enable_handshake().await;
+    node2.enable_handshake().await;
+
+    // Start listening.
+    node0.tcp().enable_listener().await.unwrap();
+    node1.tcp().enable_listener().await.unwrap();
+    node2.tcp().enable_listener().await.unwrap();
+
+    {
+        // Connect node1 to node0.
+        node1.connect(node0.local_ip());
+        // Sleep briefly.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        print_tcp!(node0);
+        print_tcp!(node1);
+
+        // Check the router level.
+        assert_eq!(node0.number_of_inbound_peers(), 1);
+        assert_eq!(node1.number_of_outbound_peers(), 1);
+    }
+    {
+        // Connect node2 to node0, and node0 to node2.
+        node2.connect(node0.local_ip());
+        assert!(node0.connect(node2.local_ip()).is_none());
+        // Sleep briefly.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        print_tcp!(node0);
+        print_tcp!(node2);
+
+        // Check the router level, ensuring both connections were refused.
+        assert_eq!(node0.number_of_connected_peers(), 1);
+        assert_eq!(node2.number_of_connected_peers(), 0);
+    }
+}
+
+#[tokio::test]
+async fn test_connect_with_io_timeout() {
+    // Create a validator that drops connections which are silent for over 1 second, along with a client.
+    let limits = ConnectionLimits { io_timeout_secs: Some(1), ..Default::default() };
+    let node0 = limited_validator(0, 2, limits).await;
+    let node1 = client(0, 2).await;
+
+    // Enable handshake protocol, and reading on the validator.
+    node0.enable_handshake().await;
+    node1.enable_handshake().await;
+    node0.enable_reading().await;
+
+    // Start listening.
+    node0.tcp().enable_listener().await.unwrap();
+    node1.tcp().enable_listener().await.unwrap();
+
+    // Connect node1 to node0.
+    node1.connect(node0.local_ip());
+    // Sleep briefly.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(node0.tcp().num_connected(), 1);
+
+    // The client never sends a message, so the validator drops the connection once the deadline expires.
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+
+    print_tcp!(node0);
+    assert_eq!(node0.tcp().num_connected(), 0);
+}
+
 #[ignore]
 #[tokio::test]
 async fn test_connect_simultaneously_with_handshake() {
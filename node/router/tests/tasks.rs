@@ -0,0 +1,35 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+use common::*;
+
+use core::time::Duration;
+
+#[tokio::test]
+async fn test_task_limit() {
+    // Initialize a client with a limit of 2 running tasks, refusing any excess tasks.
+    let node = task_limited_client(0, 1, 2).await;
+
+    // Spawn 3 long-running tasks; only the first 2 are spawned.
+    for _ in 0..3 {
+        node.spawn(std::future::pending());
+    }
+    assert_eq!(node.number_of_tasks(), 2);
+
+    // Ensure finished tasks do not count towards the limit.
+    node.shut_down().await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(node.number_of_tasks(), 0);
+}
@@ -0,0 +1,36 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+use common::*;
+
+#[tokio::test]
+async fn test_upgrade_awareness() {
+    let node = client(0, 1).await;
+
+    // Without upgrade awareness, a detected network upgrade is ignored.
+    node.set_outdated(true);
+    assert!(!node.is_outdated());
+    assert!(node.is_relaying());
+
+    // With upgrade awareness, a detected network upgrade pauses the relay.
+    node.enable_upgrade_awareness();
+    assert!(node.is_outdated());
+    assert!(!node.is_relaying());
+
+    // Once the network is back on the node's version, the relay resumes.
+    assert!(node.set_outdated(false));
+    assert!(!node.is_outdated());
+    assert!(node.is_relaying());
+}
@@ -14,10 +14,10 @@
 
 mod router;
 
-use crate::traits::NodeInterface;
+use crate::{supervise, traits::NodeInterface, NodeConfig};
 use snarkos_account::Account;
-use snarkos_node_bft::ledger_service::CoreLedgerService;
-use snarkos_node_rest::Rest;
+use snarkos_node_bft::ledger_service::{CoreLedgerService, DEFAULT_VERIFICATION_CACHE_SIZE};
+use snarkos_node_rest::{Rest, RestConfig};
 use snarkos_node_router::{
     messages::{Message, NodeType, UnconfirmedSolution},
     Heartbeat,
@@ -65,6 +65,8 @@ pub struct Client<N: Network, C: ConsensusStorage<N>> {
     genesis: Block<N>,
     /// The coinbase puzzle.
     coinbase_puzzle: CoinbasePuzzle<N>,
+    /// The configuration of the node.
+    config: Arc<NodeConfig>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The shutdown signal.
@@ -81,9 +83,10 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
         genesis: Block<N>,
         cdn: Option<String>,
         dev: Option<u16>,
+        config: NodeConfig,
     ) -> Result<Self> {
         // Initialize the signal handler.
-        let signal_node = Self::handle_signals();
+        let signal_node = Self::handle_signals(config.crash_info_path.clone());
 
         // Initialize the ledger.
         let ledger = Ledger::<N, C>::load(genesis.clone(), dev)?;
@@ -92,16 +95,19 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
         // Initialize the CDN.
         if let Some(base_url) = cdn {
             // Sync the ledger with the CDN.
-            if let Err((_, error)) = snarkos_node_cdn::sync_ledger_with_cdn(&base_url, ledger.clone()).await {
+            if let Err((_, error)) =
+                snarkos_node_cdn::sync_ledger_with_cdn(&base_url, ledger.clone(), config.cdn_verification).await
+            {
                 crate::log_clean_error(dev);
                 return Err(error);
             }
         }
 
         // Initialize the ledger service.
-        let ledger_service = Arc::new(CoreLedgerService::<N, C>::new(ledger.clone()));
+        let cache_size = config.verification_cache_size.unwrap_or(DEFAULT_VERIFICATION_CACHE_SIZE);
+        let ledger_service = Arc::new(CoreLedgerService::<N, C>::new(ledger.clone(), cache_size));
         // Initialize the sync module.
-        let sync = BlockSync::new(BlockSyncMode::Router, ledger_service.clone());
+        let sync = BlockSync::new(BlockSyncMode::Router, ledger_service.clone(), config.sync.clone());
 
         // Initialize the node router.
         let router = Router::new(
@@ -111,6 +117,7 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
             trusted_peers,
             Self::MAXIMUM_NUMBER_OF_PEERS as u16,
             dev.is_some(),
+            config.router.clone(),
         )
         .await?;
         // Load the coinbase puzzle.
@@ -123,13 +130,21 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
             sync: Arc::new(sync),
             genesis,
             coinbase_puzzle,
+            config: Arc::new(config),
             handles: Default::default(),
             shutdown: Default::default(),
         };
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest = Some(Rest::start(rest_ip, None, ledger.clone(), Arc::new(node.clone()))?);
+            // Note: The REST server reports the crash info and the validation timings of the node.
+            let rest_config = RestConfig {
+                crash_info_path: node.config.crash_info_path.clone(),
+                validation_histogram: node.config.sync.validation_histogram.clone(),
+                ..node.config.rest.clone()
+            };
+            let rest = Rest::start(rest_ip, None, ledger.clone(), Arc::new(node.clone()), rest_config)?;
+            node.rest = Some(rest);
         }
         // Initialize the routing.
         node.initialize_routing().await;
@@ -157,22 +172,32 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
 impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
     /// Initializes the sync pool.
     fn initialize_sync(&self) {
+        // Mark the node as unsynced, until the first sync routine completes.
+        self.router.set_synced(false);
         // Start the sync loop.
         let node = self.clone();
-        self.handles.lock().push(tokio::spawn(async move {
-            loop {
-                // If the Ctrl-C handler registered the signal, stop the node.
-                if node.shutdown.load(std::sync::atomic::Ordering::Relaxed) {
-                    info!("Shutting down block production");
-                    break;
+        let sync_loop = move || {
+            let node = node.clone();
+            async move {
+                loop {
+                    // If the Ctrl-C handler registered the signal, stop the node.
+                    if node.shutdown.load(std::sync::atomic::Ordering::Relaxed) {
+                        info!("Shutting down block production");
+                        break;
+                    }
+
+                    // Sleep briefly to avoid triggering spam detection.
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                    // Perform the sync routine.
+                    node.sync.try_block_sync(&node).await;
+                    // Report the sync status to the router.
+                    node.router.set_synced(node.sync.is_block_synced());
+                    node.router.set_greatest_peer_height(node.sync.greatest_peer_height().unwrap_or(0));
                 }
-
-                // Sleep briefly to avoid triggering spam detection.
-                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-                // Perform the sync routine.
-                node.sync.try_block_sync(&node).await;
             }
-        }));
+        };
+        let task = supervise(self.clone(), "sync", self.shutdown.clone(), sync_loop);
+        self.handles.lock().push(tokio::spawn(task));
     }
 
     /// Spawns a task with the given future; it should only be used for long-running tasks.
@@ -183,6 +208,20 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
 
 #[async_trait]
 impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Client<N, C> {
+    /// Returns the configuration of the node.
+    fn config(&self) -> &NodeConfig {
+        &self.config
+    }
+
+    /// Reloads the TLS certificate of the REST server from its files, returning `false` if the node does not serve
+    /// its REST endpoints over HTTPS.
+    fn reload_rest_tls_config(&self) -> Result<bool> {
+        match &self.rest {
+            Some(rest) => rest.reload_tls_config(),
+            None => Ok(false),
+        }
+    }
+
     /// Shuts down the node.
     async fn shut_down(&self) {
         info!("Shutting down...");
@@ -109,8 +109,8 @@ impl<N: Network, C: ConsensusStorage<N>> Reading for Client<N, C> {
 
     /// Creates a [`Decoder`] used to interpret messages from the network.
     /// The `side` param indicates the connection side **from the node's perspective**.
-    fn codec(&self, _peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
-        Default::default()
+    fn codec(&self, peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
+        self.router.message_codec(peer_addr)
     }
 
     /// Processes a message received from the network.
@@ -139,6 +139,11 @@ impl<N: Network, C: ConsensusStorage<N>> CommunicationService for Client<N, C> {
         Message::BlockRequest(BlockRequest { start_height, end_height })
     }
 
+    /// Returns `true` if the given peer is one of the trusted peers of the node.
+    fn is_trusted_peer(&self, peer_ip: SocketAddr) -> bool {
+        self.router().trusted_peers().contains(&peer_ip)
+    }
+
     /// Sends the given message to specified peer.
     ///
     /// This function returns as soon as the message is queued to be sent,
@@ -188,7 +193,13 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Client<N, C> {
     fn block_response(&self, peer_ip: SocketAddr, blocks: Vec<Block<N>>) -> bool {
         // Tries to advance with blocks from the sync module.
         match self.sync.advance_with_sync_blocks(peer_ip, blocks) {
-            Ok(()) => true,
+            Ok(invalid_block_senders) => {
+                // Penalize the peers that sent a block which failed verification (this disconnects them).
+                for sender_ip in invalid_block_senders {
+                    self.router.penalize_invalid_block_sender(sender_ip);
+                }
+                true
+            }
             Err(error) => {
                 warn!("{error}");
                 false
@@ -277,12 +288,15 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Client<N, C> {
             .await;
 
             match is_valid {
-                // If the solution is valid, propagate the `UnconfirmedSolution`.
-                Ok(Ok(true)) => {
+                // If the solution is valid, propagate the `UnconfirmedSolution` (unless relaying is paused).
+                Ok(Ok(true)) if self.router.is_relaying() => {
                     let message = Message::UnconfirmedSolution(serialized);
                     // Propagate the "UnconfirmedSolution".
                     self.propagate(message, &[peer_ip]);
                 }
+                Ok(Ok(true)) => {
+                    trace!("Skipping the relay of solution '{}' (relaying is paused)", solution.commitment())
+                }
                 Ok(Ok(false)) | Ok(Err(_)) => {
                     trace!("Invalid prover solution '{}' for the proof target.", solution.commitment())
                 }
@@ -303,6 +317,11 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Client<N, C> {
         if transaction.is_fee() {
             return true; // Maintain the connection.
         }
+        // Skip the relay while relaying is paused (e.g. the node is syncing, if so configured).
+        if !self.router.is_relaying() {
+            trace!("Skipping the relay of transaction '{}' (relaying is paused)", transaction.id());
+            return true; // Maintain the connection.
+        }
         // Check that the transaction is well-formed and unique.
         if self.ledger.check_transaction_basic(&transaction, None, &mut rand::thread_rng()).is_ok() {
             // Propagate the `UnconfirmedTransaction`.
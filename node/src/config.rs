@@ -0,0 +1,46 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::TaskFailurePolicies;
+use snarkos_node_bft::helpers::ProductionGrace;
+use snarkos_node_cdn::CdnVerification;
+use snarkos_node_rest::RestConfig;
+use snarkos_node_router::RouterConfig;
+use snarkos_node_sync::BlockSyncConfig;
+
+use std::{path::PathBuf, time::Duration};
+
+/// The configuration of a node, beyond its identity, its peers, and its ledger.
+#[derive(Clone, Debug, Default)]
+pub struct NodeConfig {
+    /// The configuration of the router.
+    pub router: RouterConfig,
+    /// The configuration of the block sync module.
+    pub sync: BlockSyncConfig,
+    /// The configuration of the REST server (unused by provers).
+    pub rest: RestConfig,
+    /// The verification performed on each block imported from the CDN (unused by provers).
+    pub cdn_verification: CdnVerification,
+    /// The path to the crash info file, which records the unclean shutdowns of the node, if crash tracking is enabled.
+    /// Note: The REST server reports the crash info from this path, regardless of `rest.crash_info_path`.
+    pub crash_info_path: Option<PathBuf>,
+    /// The action taken when a supervised background task of the node fails.
+    pub task_failure_policies: TaskFailurePolicies,
+    /// The duration the node may take to shut down, or `None` for `DEFAULT_SHUTDOWN_TIMEOUT_IN_SECS`.
+    pub shutdown_timeout: Option<Duration>,
+    /// The grace period before the primary begins proposing batches (only used by validators).
+    pub production_grace: ProductionGrace,
+    /// The number of successful transmission verifications to cache, or `None` for `DEFAULT_VERIFICATION_CACHE_SIZE`
+    /// (unused by provers).
+    pub verification_cache_size: Option<usize>,
+}
@@ -24,6 +24,8 @@ extern crate tracing;
 pub use snarkos_node_bft as bft;
 pub use snarkos_node_cdn as cdn;
 pub use snarkos_node_consensus as consensus;
+#[cfg(feature = "metrics")]
+pub use snarkos_node_metrics as metrics;
 pub use snarkos_node_rest as rest;
 pub use snarkos_node_router as router;
 pub use snarkos_node_sync as sync;
@@ -33,6 +35,9 @@ pub use snarkvm;
 mod client;
 pub use client::*;
 
+mod config;
+pub use config::*;
+
 mod prover;
 pub use prover::*;
 
@@ -42,6 +47,9 @@ pub use validator::*;
 mod node;
 pub use node::*;
 
+mod supervisor;
+pub use supervisor::*;
+
 mod traits;
 pub use traits::*;
 
@@ -12,12 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{traits::NodeInterface, Client, Prover, Validator};
+use crate::{traits::NodeInterface, Client, NodeConfig, Prover, Validator};
 use snarkos_account::Account;
-use snarkos_node_router::messages::NodeType;
+use snarkos_node_router::{messages::NodeType, Inbound, Routing};
 use snarkvm::prelude::{
     block::Block,
-    store::helpers::{memory::ConsensusMemory, rocksdb::ConsensusDB},
+    store::{
+        helpers::{memory::ConsensusMemory, rocksdb::ConsensusDB},
+        ConsensusStorage,
+    },
     Address,
     Network,
     PrivateKey,
@@ -25,18 +28,19 @@ use snarkvm::prelude::{
 };
 
 use anyhow::Result;
-use std::{net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, path::Path, sync::Arc};
 
-pub enum Node<N: Network> {
+/// A node of any type, backed by the ledger storage `C` (RocksDB, by default).
+pub enum Node<N: Network, C: ConsensusStorage<N> = ConsensusDB<N>> {
     /// A validator is a full node, capable of validating blocks.
-    Validator(Arc<Validator<N, ConsensusDB<N>>>),
+    Validator(Arc<Validator<N, C>>),
     /// A prover is a light node, capable of producing proofs for consensus.
     Prover(Arc<Prover<N, ConsensusMemory<N>>>),
     /// A client node is a full node, capable of querying with the network.
-    Client(Arc<Client<N, ConsensusDB<N>>>),
+    Client(Arc<Client<N, C>>),
 }
 
-impl<N: Network> Node<N> {
+impl<N: Network, C: ConsensusStorage<N>> Node<N, C> {
     /// Initializes a new validator node.
     pub async fn new_validator(
         node_ip: SocketAddr,
@@ -48,10 +52,22 @@ impl<N: Network> Node<N> {
         genesis: Block<N>,
         cdn: Option<String>,
         dev: Option<u16>,
+        config: NodeConfig,
     ) -> Result<Self> {
         Ok(Self::Validator(Arc::new(
-            Validator::new(node_ip, rest_ip, bft_ip, account, trusted_peers, trusted_validators, genesis, cdn, dev)
-                .await?,
+            Validator::new(
+                node_ip,
+                rest_ip,
+                bft_ip,
+                account,
+                trusted_peers,
+                trusted_validators,
+                genesis,
+                cdn,
+                dev,
+                config,
+            )
+            .await?,
         )))
     }
 
@@ -62,8 +78,9 @@ impl<N: Network> Node<N> {
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
         dev: Option<u16>,
+        config: NodeConfig,
     ) -> Result<Self> {
-        Ok(Self::Prover(Arc::new(Prover::new(node_ip, account, trusted_peers, genesis, dev).await?)))
+        Ok(Self::Prover(Arc::new(Prover::new(node_ip, account, trusted_peers, genesis, dev, config).await?)))
     }
 
     /// Initializes a new client node.
@@ -75,8 +92,11 @@ impl<N: Network> Node<N> {
         genesis: Block<N>,
         cdn: Option<String>,
         dev: Option<u16>,
+        config: NodeConfig,
     ) -> Result<Self> {
-        Ok(Self::Client(Arc::new(Client::new(node_ip, rest_ip, account, trusted_peers, genesis, cdn, dev).await?)))
+        Ok(Self::Client(Arc::new(
+            Client::new(node_ip, rest_ip, account, trusted_peers, genesis, cdn, dev, config).await?,
+        )))
     }
 
     /// Returns the node type.
@@ -123,4 +143,59 @@ impl<N: Network> Node<N> {
             Self::Client(node) => node.is_dev(),
         }
     }
+
+    /// Returns `true` if the node has no connected peers, and no candidate or bootstrap peers to connect to.
+    pub fn is_isolated(&self) -> bool {
+        match self {
+            Self::Validator(node) => node.router().is_isolated(),
+            Self::Prover(node) => node.router().is_isolated(),
+            Self::Client(node) => node.router().is_isolated(),
+        }
+    }
+
+    /// Enables the recording of all inbound messages to the file at the given path.
+    pub fn record_messages(&self, path: &Path) -> Result<()> {
+        match self {
+            Self::Validator(node) => node.router().enable_message_recording(path),
+            Self::Prover(node) => node.router().enable_message_recording(path),
+            Self::Client(node) => node.router().enable_message_recording(path),
+        }
+    }
+
+    /// Pauses the relay of unconfirmed solutions and transactions until the node is synced.
+    pub fn relay_after_sync(&self) {
+        match self {
+            Self::Validator(node) => node.router().enable_relay_after_sync(),
+            Self::Prover(node) => node.router().enable_relay_after_sync(),
+            Self::Client(node) => node.router().enable_relay_after_sync(),
+        }
+    }
+
+    /// Pauses the production and relay of solutions and transactions if the network upgrades past this node.
+    pub fn upgrade_awareness(&self) {
+        match self {
+            Self::Validator(node) => node.router().enable_upgrade_awareness(),
+            Self::Prover(node) => node.router().enable_upgrade_awareness(),
+            Self::Client(node) => node.router().enable_upgrade_awareness(),
+        }
+    }
+
+    /// Restricts (instead of only disconnecting) the peers that send a block which fails verification.
+    pub fn ban_on_invalid_block(&self) {
+        match self {
+            Self::Validator(node) => node.router().enable_ban_on_invalid_block(),
+            Self::Prover(node) => node.router().enable_ban_on_invalid_block(),
+            Self::Client(node) => node.router().enable_ban_on_invalid_block(),
+        }
+    }
+
+    /// Replays the inbound messages recorded in the file at the given path.
+    /// Returns the number of messages that were handled successfully.
+    pub async fn replay_messages(&self, path: &Path) -> Result<usize> {
+        match self {
+            Self::Validator(node) => node.replay_messages(path).await,
+            Self::Prover(node) => node.replay_messages(path).await,
+            Self::Client(node) => node.replay_messages(path).await,
+        }
+    }
 }
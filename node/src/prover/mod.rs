@@ -14,7 +14,7 @@
 
 mod router;
 
-use crate::traits::NodeInterface;
+use crate::{supervise, traits::NodeInterface, NodeConfig};
 use snarkos_account::Account;
 use snarkos_node_bft::ledger_service::ProverLedgerService;
 use snarkos_node_router::{
@@ -73,6 +73,8 @@ pub struct Prover<N: Network, C: ConsensusStorage<N>> {
     puzzle_instances: Arc<AtomicU8>,
     /// The maximum number of puzzle instances.
     max_puzzle_instances: u8,
+    /// The configuration of the node.
+    config: Arc<NodeConfig>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The shutdown signal.
@@ -89,14 +91,15 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
         dev: Option<u16>,
+        config: NodeConfig,
     ) -> Result<Self> {
         // Initialize the signal handler.
-        let signal_node = Self::handle_signals();
+        let signal_node = Self::handle_signals(config.crash_info_path.clone());
 
         // Initialize the ledger service.
         let ledger_service = Arc::new(ProverLedgerService::new());
         // Initialize the sync module.
-        let sync = BlockSync::new(BlockSyncMode::Router, ledger_service.clone());
+        let sync = BlockSync::new(BlockSyncMode::Router, ledger_service.clone(), config.sync.clone());
 
         // Initialize the node router.
         let router = Router::new(
@@ -106,6 +109,7 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
             trusted_peers,
             Self::MAXIMUM_NUMBER_OF_PEERS as u16,
             dev.is_some(),
+            config.router.clone(),
         )
         .await?;
         // Load the coinbase puzzle.
@@ -122,6 +126,7 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
             latest_block_header: Default::default(),
             puzzle_instances: Default::default(),
             max_puzzle_instances: u8::try_from(max_puzzle_instances)?,
+            config: Arc::new(config),
             handles: Default::default(),
             shutdown: Default::default(),
             _phantom: Default::default(),
@@ -141,6 +146,11 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
 
 #[async_trait]
 impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Prover<N, C> {
+    /// Returns the configuration of the node.
+    fn config(&self) -> &NodeConfig {
+        &self.config
+    }
+
     /// Shuts down the node.
     async fn shut_down(&self) {
         info!("Shutting down...");
@@ -165,9 +175,12 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
     async fn initialize_coinbase_puzzle(&self) {
         for _ in 0..self.max_puzzle_instances {
             let prover = self.clone();
-            self.handles.lock().push(tokio::spawn(async move {
-                prover.coinbase_puzzle_loop().await;
-            }));
+            let puzzle_loop = move || {
+                let prover = prover.clone();
+                async move { prover.coinbase_puzzle_loop().await }
+            };
+            let task = supervise(self.clone(), "puzzle", self.shutdown.clone(), puzzle_loop);
+            self.handles.lock().push(tokio::spawn(task));
         }
     }
 
@@ -181,6 +194,13 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
                 continue;
             }
 
+            // If the network has upgraded past this node, then skip this iteration.
+            if self.router.is_outdated() {
+                trace!("Skipping an iteration of the coinbase puzzle (the network has upgraded)");
+                tokio::time::sleep(Duration::from_secs(N::ANCHOR_TIME as u64)).await;
+                continue;
+            }
+
             // If the number of instances of the coinbase puzzle exceeds the maximum, then skip this iteration.
             if self.num_puzzle_instances() > self.max_puzzle_instances {
                 // Sleep for a brief period of time.
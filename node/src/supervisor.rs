@@ -0,0 +1,164 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::traits::{shut_down_with_timeout, NodeInterface};
+use snarkvm::prelude::Network;
+
+use anyhow::{bail, Result};
+use core::{future::Future, panic::AssertUnwindSafe, str::FromStr, time::Duration};
+use futures_util::FutureExt;
+use indexmap::IndexMap;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// The delay before the first restart of a failed task.
+const INITIAL_RESTART_DELAY: Duration = Duration::from_secs(1);
+/// The maximum delay in between restarts of a failed task.
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+
+/// The action taken when a supervised background task of the node panics or exits unexpectedly.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TaskFailurePolicy {
+    /// The task is restarted, with an exponential backoff.
+    Restart,
+    /// The node is shut down.
+    Shutdown,
+    /// The failure is logged, and the task is not restarted.
+    #[default]
+    Ignore,
+}
+
+impl FromStr for TaskFailurePolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(policy: &str) -> Result<Self> {
+        match policy {
+            "restart" => Ok(Self::Restart),
+            "shutdown" => Ok(Self::Shutdown),
+            "ignore" => Ok(Self::Ignore),
+            _ => bail!("Invalid task failure policy '{policy}' (expected 'restart', 'shutdown', or 'ignore')"),
+        }
+    }
+}
+
+/// The task failure policies, with a default policy, and the overrides for specific task classes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TaskFailurePolicies {
+    /// The policy of the task classes without an override.
+    default: TaskFailurePolicy,
+    /// The policies of specific task classes.
+    overrides: IndexMap<String, TaskFailurePolicy>,
+}
+
+impl TaskFailurePolicies {
+    /// The classes of the supervised tasks.
+    pub const TASK_CLASSES: [&'static str; 3] = ["sync", "puzzle", "transaction-pool"];
+
+    /// Returns the policy of the given task class.
+    pub fn get(&self, class: &str) -> TaskFailurePolicy {
+        self.overrides.get(class).copied().unwrap_or(self.default)
+    }
+}
+
+impl FromStr for TaskFailurePolicies {
+    type Err = anyhow::Error;
+
+    /// Parses the given comma-separated policies, each of which is either `<policy>` to set the default policy,
+    /// or `<class>=<policy>` to set the policy of a task class.
+    fn from_str(policies: &str) -> Result<Self> {
+        let mut result = Self::default();
+        for policy in policies.split(',').map(str::trim).filter(|policy| !policy.is_empty()) {
+            match policy.split_once('=') {
+                Some((class, policy)) => {
+                    if !Self::TASK_CLASSES.contains(&class) {
+                        bail!("Unknown task class '{class}' (expected one of {})", Self::TASK_CLASSES.join(", "));
+                    }
+                    result.overrides.insert(class.to_string(), TaskFailurePolicy::from_str(policy)?);
+                }
+                None => result.default = TaskFailurePolicy::from_str(policy)?,
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Runs the task produced by the given function, which is expected to run until the node shuts down.
+/// If the task panics or exits beforehand, the task failure policy of its class is applied, as configured for the node.
+pub(crate) async fn supervise<N, I, F, T>(node: I, class: &'static str, shutdown: Arc<AtomicBool>, task: F)
+where
+    N: Network,
+    I: NodeInterface<N> + Send + 'static,
+    F: Fn() -> T + Send,
+    T: Future<Output = ()> + Send,
+{
+    let mut delay = INITIAL_RESTART_DELAY;
+    loop {
+        // Run the task, catching a panic.
+        let result = AssertUnwindSafe(task()).catch_unwind().await;
+        // If the node is shutting down, the task is expected to exit.
+        if shutdown.load(Ordering::Relaxed) {
+            return;
+        }
+        let reason = match result {
+            Ok(()) => "exited unexpectedly",
+            Err(_) => "panicked",
+        };
+
+        match node.config().task_failure_policies.get(class) {
+            TaskFailurePolicy::Restart => {
+                warn!("The '{class}' task {reason}, restarting it in {} seconds", delay.as_secs());
+                tokio::time::sleep(delay).await;
+                delay = delay.saturating_mul(2).min(MAX_RESTART_DELAY);
+            }
+            TaskFailurePolicy::Shutdown => {
+                error!("The '{class}' task {reason}, shutting down the node");
+                // Shut down in a separate task, as shutting down aborts this task.
+                tokio::spawn(async move {
+                    shut_down_with_timeout(&node).await;
+                    std::process::exit(1);
+                });
+                return;
+            }
+            TaskFailurePolicy::Ignore => {
+                error!("The '{class}' task {reason}, the node may be degraded (see '--on-task-failure')");
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_failure_policies() {
+        // By default, failures are ignored.
+        let policies = TaskFailurePolicies::from_str("").unwrap();
+        assert_eq!(policies.get("sync"), TaskFailurePolicy::Ignore);
+
+        // Set a default policy, and override a task class.
+        let policies = TaskFailurePolicies::from_str("restart,puzzle=shutdown").unwrap();
+        assert_eq!(policies.get("sync"), TaskFailurePolicy::Restart);
+        assert_eq!(policies.get("puzzle"), TaskFailurePolicy::Shutdown);
+        assert_eq!(policies.get("transaction-pool"), TaskFailurePolicy::Restart);
+
+        // Ensure invalid policies and task classes are rejected.
+        assert!(TaskFailurePolicies::from_str("reboot").is_err());
+        assert!(TaskFailurePolicies::from_str("router=restart").is_err());
+        assert!(TaskFailurePolicies::from_str("sync=").is_err());
+    }
+}
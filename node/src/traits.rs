@@ -12,14 +12,39 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::NodeConfig;
 use snarkos_node_router::{messages::NodeType, Routing};
 use snarkvm::prelude::{Address, Network, PrivateKey, ViewKey};
 
+use anyhow::Result;
 use once_cell::sync::OnceCell;
-use std::sync::Arc;
+use serde_json::json;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+/// The default number of seconds the node may take to shut down, before the process exits regardless.
+pub const DEFAULT_SHUTDOWN_TIMEOUT_IN_SECS: u64 = 30;
+
+/// Shuts down the node, returning `false` if it did not finish within the shutdown timeout of the node.
+pub(crate) async fn shut_down_with_timeout<N: Network, T: NodeInterface<N>>(node: &T) -> bool {
+    let timeout = node.config().shutdown_timeout.unwrap_or(Duration::from_secs(DEFAULT_SHUTDOWN_TIMEOUT_IN_SECS));
+    match tokio::time::timeout(timeout, node.shut_down()).await {
+        Ok(()) => true,
+        Err(_) => {
+            warn!("The node did not shut down within {} seconds, exiting regardless", timeout.as_secs());
+            false
+        }
+    }
+}
 
 #[async_trait]
 pub trait NodeInterface<N: Network>: Routing<N> {
+    /// Returns the configuration of the node.
+    fn config(&self) -> &NodeConfig;
+
     /// Returns the node type.
     fn node_type(&self) -> NodeType {
         self.router().node_type()
@@ -45,22 +70,134 @@ pub trait NodeInterface<N: Network>: Routing<N> {
         self.router().is_dev()
     }
 
+    /// Returns a JSON snapshot of the node status.
+    fn status(&self) -> serde_json::Value {
+        let router = self.router();
+        json!({
+            "node_type": router.node_type(),
+            "address": router.address().to_string(),
+            "local_ip": router.local_ip(),
+            "is_dev": router.is_dev(),
+            "peers": {
+                "connected": router.connected_peers(),
+                "connected_validators": router.number_of_connected_validators(),
+                "connected_provers": router.number_of_connected_provers(),
+                "connected_clients": router.number_of_connected_clients(),
+                "candidate": router.number_of_candidate_peers(),
+                "restricted": router.number_of_restricted_peers(),
+            },
+            "reconnects": router
+                .reconnect_statuses()
+                .into_iter()
+                .map(|(peer_ip, status)| (peer_ip.to_string(), json!(status)))
+                .collect::<serde_json::Map<_, _>>(),
+            "messages": router
+                .connected_message_stats()
+                .into_iter()
+                .map(|(peer_ip, stats)| (peer_ip.to_string(), json!(stats)))
+                .collect::<serde_json::Map<_, _>>(),
+        })
+    }
+
+    /// Reloads the TLS certificate of the REST server from its files, returning `false` if the node does not serve
+    /// its REST endpoints over HTTPS.
+    fn reload_rest_tls_config(&self) -> Result<bool> {
+        Ok(false)
+    }
+
     /// Handles OS signals for the node to intercept and perform a clean shutdown.
-    /// Note: Only Ctrl-C is supported; it should work on both Unix-family systems and Windows.
-    fn handle_signals() -> Arc<OnceCell<Self>> {
+    /// If a crash info path is given, the clean shutdown is recorded in it.
+    /// Note: Ctrl-C is supported on both Unix-family systems and Windows. On Unix-family systems, `SIGTERM`
+    /// (e.g. from `systemctl stop` or `docker stop`) is also supported. On Windows, only Ctrl-C is available.
+    /// On Unix-family systems, `SIGUSR1` additionally logs a JSON snapshot of the node status.
+    fn handle_signals(crash_info_path: Option<PathBuf>) -> Arc<OnceCell<Self>> {
         // In order for the signal handler to be started as early as possible, a reference to the node needs
         // to be passed to it at a later time.
         let node: Arc<OnceCell<Self>> = Default::default();
 
-        let node_clone = node.clone();
-        tokio::task::spawn(async move {
-            match tokio::signal::ctrl_c().await {
-                Ok(()) => {
+        /// Shuts down the node, if it is initialized, records the clean shutdown, and exits the process.
+        /// If the node does not shut down within the shutdown timeout, the process exits with an error.
+        async fn shut_down_and_exit<N: Network, T: NodeInterface<N>>(
+            node: &OnceCell<T>,
+            crash_info_path: Option<&Path>,
+        ) {
+            if let Some(node) = node.get() {
+                // If the node is stuck shutting down (e.g. flushing storage), exit without recording a clean shutdown.
+                if !shut_down_with_timeout(node).await {
+                    std::process::exit(1);
+                }
+            }
+            // Record the clean shutdown, if crash tracking is enabled.
+            if let Some(path) = crash_info_path {
+                snarkos_node_rest::record_clean_shutdown(path);
+            }
+            std::process::exit(0);
+        }
+
+        #[cfg(target_family = "unix")]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+
+            let node_clone = node.clone();
+            tokio::task::spawn(async move {
+                let mut stream = match signal(SignalKind::user_defined1()) {
+                    Ok(stream) => stream,
+                    Err(error) => {
+                        error!("Failed to register the SIGUSR1 handler: {error}");
+                        return;
+                    }
+                };
+                while stream.recv().await.is_some() {
+                    // Note: The snapshot is logged, rather than written to a file at a predictable path.
                     if let Some(node) = node_clone.get() {
-                        node.shut_down().await;
+                        match serde_json::to_string(&node.status()) {
+                            Ok(status) => info!("Node status: {status}"),
+                            Err(error) => error!("Failed to serialize the node status: {error}"),
+                        }
+                    }
+                }
+            });
+
+            let node_clone = node.clone();
+            tokio::task::spawn(async move {
+                let mut stream = match signal(SignalKind::hangup()) {
+                    Ok(stream) => stream,
+                    Err(error) => {
+                        error!("Failed to register the SIGHUP handler: {error}");
+                        return;
+                    }
+                };
+                while stream.recv().await.is_some() {
+                    // Reload the REST TLS certificate, e.g. after a rotation.
+                    // Note: On failure, the REST server keeps serving the current certificate.
+                    let Some(node) = node_clone.get() else { continue };
+                    match node.reload_rest_tls_config() {
+                        Ok(true) => (),
+                        Ok(false) => info!("Received SIGHUP, but the REST server does not use TLS"),
+                        Err(error) => error!("Failed to reload the REST TLS certificate - {error}"),
                     }
-                    std::process::exit(0);
                 }
+            });
+
+            let node_clone = node.clone();
+            let crash_info_path = crash_info_path.clone();
+            tokio::task::spawn(async move {
+                match signal(SignalKind::terminate()) {
+                    Ok(mut stream) => {
+                        if stream.recv().await.is_some() {
+                            info!("Received SIGTERM, shutting down the node...");
+                            shut_down_and_exit::<N, Self>(&node_clone, crash_info_path.as_deref()).await;
+                        }
+                    }
+                    Err(error) => error!("Failed to register the SIGTERM handler: {error}"),
+                }
+            });
+        }
+
+        let node_clone = node.clone();
+        tokio::task::spawn(async move {
+            match tokio::signal::ctrl_c().await {
+                Ok(()) => shut_down_and_exit::<N, Self>(&node_clone, crash_info_path.as_deref()).await,
                 Err(error) => error!("tokio::signal::ctrl_c encountered an error: {}", error),
             }
         });
@@ -14,11 +14,14 @@
 
 mod router;
 
-use crate::traits::NodeInterface;
+use crate::{supervise, traits::NodeInterface, NodeConfig};
 use snarkos_account::Account;
-use snarkos_node_bft::{helpers::init_primary_channels, ledger_service::CoreLedgerService};
+use snarkos_node_bft::{
+    helpers::{init_primary_channels, PrimaryConfig},
+    ledger_service::{CoreLedgerService, DEFAULT_VERIFICATION_CACHE_SIZE},
+};
 use snarkos_node_consensus::Consensus;
-use snarkos_node_rest::Rest;
+use snarkos_node_rest::{Rest, RestConfig};
 use snarkos_node_router::{
     messages::{NodeType, PuzzleResponse, UnconfirmedSolution, UnconfirmedTransaction},
     Heartbeat,
@@ -63,6 +66,8 @@ pub struct Validator<N: Network, C: ConsensusStorage<N>> {
     rest: Option<Rest<N, C, Self>>,
     /// The sync module.
     sync: BlockSync<N>,
+    /// The configuration of the node.
+    config: Arc<NodeConfig>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The shutdown signal.
@@ -81,9 +86,10 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         genesis: Block<N>,
         cdn: Option<String>,
         dev: Option<u16>,
+        config: NodeConfig,
     ) -> Result<Self> {
         // Initialize the signal handler.
-        let signal_node = Self::handle_signals();
+        let signal_node = Self::handle_signals(config.crash_info_path.clone());
 
         // Initialize the ledger.
         let ledger = Ledger::load(genesis, dev)?;
@@ -92,19 +98,24 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         // Initialize the CDN.
         if let Some(base_url) = cdn {
             // Sync the ledger with the CDN.
-            if let Err((_, error)) = snarkos_node_cdn::sync_ledger_with_cdn(&base_url, ledger.clone()).await {
+            if let Err((_, error)) =
+                snarkos_node_cdn::sync_ledger_with_cdn(&base_url, ledger.clone(), config.cdn_verification).await
+            {
                 crate::log_clean_error(dev);
                 return Err(error);
             }
         }
 
         // Initialize the ledger service.
-        let ledger_service = Arc::new(CoreLedgerService::new(ledger.clone()));
+        let cache_size = config.verification_cache_size.unwrap_or(DEFAULT_VERIFICATION_CACHE_SIZE);
+        let ledger_service = Arc::new(CoreLedgerService::new(ledger.clone(), cache_size));
         // Initialize the sync module.
-        let sync = BlockSync::new(BlockSyncMode::Gateway, ledger_service.clone());
+        let sync = BlockSync::new(BlockSyncMode::Gateway, ledger_service.clone(), config.sync.clone());
 
         // Initialize the consensus.
-        let mut consensus = Consensus::new(account.clone(), ledger_service, bft_ip, trusted_validators, dev)?;
+        let primary_config = PrimaryConfig { production_grace: config.production_grace, sync: config.sync.clone() };
+        let mut consensus =
+            Consensus::new(account.clone(), ledger_service, bft_ip, trusted_validators, dev, primary_config)?;
         // Initialize the primary channels.
         let (primary_sender, primary_receiver) = init_primary_channels::<N>();
         // Start the consensus.
@@ -118,6 +129,7 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
             trusted_peers,
             Self::MAXIMUM_NUMBER_OF_PEERS as u16,
             dev.is_some(),
+            config.router.clone(),
         )
         .await?;
 
@@ -128,6 +140,7 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
             router,
             rest: None,
             sync,
+            config: Arc::new(config),
             handles: Default::default(),
             shutdown: Default::default(),
         };
@@ -136,7 +149,14 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest = Some(Rest::start(rest_ip, Some(consensus), ledger.clone(), Arc::new(node.clone()))?);
+            // Note: The REST server reports the crash info and the validation timings of the node.
+            let rest_config = RestConfig {
+                crash_info_path: node.config.crash_info_path.clone(),
+                validation_histogram: node.config.sync.validation_histogram.clone(),
+                ..node.config.rest.clone()
+            };
+            let rest = Rest::start(rest_ip, Some(consensus), ledger.clone(), Arc::new(node.clone()), rest_config)?;
+            node.rest = Some(rest);
         }
         // Initialize the routing.
         node.initialize_routing().await;
@@ -366,46 +386,51 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
             }
         }
 
-        let self_ = self.clone();
-        self.spawn(async move {
-            tokio::time::sleep(Duration::from_secs(3)).await;
-            info!("Starting transaction pool...");
-
-            // Start the transaction loop.
-            loop {
-                tokio::time::sleep(Duration::from_millis(500)).await;
-
-                // Prepare the inputs.
-                let inputs = [Value::from(Literal::Address(self_.address())), Value::from(Literal::U64(U64::new(1)))];
-                // Execute the transaction.
-                let transaction = match self_.ledger.vm().execute(
-                    self_.private_key(),
-                    locator,
-                    inputs.into_iter(),
-                    None,
-                    10_000,
-                    None,
-                    &mut rand::thread_rng(),
-                ) {
-                    Ok(transaction) => transaction,
-                    Err(error) => {
-                        error!("Transaction pool encountered an execution error - {error}");
-                        continue;
+        let node = self.clone();
+        let transaction_pool = move || {
+            let self_ = node.clone();
+            async move {
+                tokio::time::sleep(Duration::from_secs(3)).await;
+                info!("Starting transaction pool...");
+
+                // Start the transaction loop.
+                loop {
+                    tokio::time::sleep(Duration::from_millis(500)).await;
+
+                    // Prepare the inputs.
+                    let inputs =
+                        [Value::from(Literal::Address(self_.address())), Value::from(Literal::U64(U64::new(1)))];
+                    // Execute the transaction.
+                    let transaction = match self_.ledger.vm().execute(
+                        self_.private_key(),
+                        locator,
+                        inputs.into_iter(),
+                        None,
+                        10_000,
+                        None,
+                        &mut rand::thread_rng(),
+                    ) {
+                        Ok(transaction) => transaction,
+                        Err(error) => {
+                            error!("Transaction pool encountered an execution error - {error}");
+                            continue;
+                        }
+                    };
+                    // Broadcast the transaction.
+                    if self_
+                        .unconfirmed_transaction(
+                            self_.router.local_ip(),
+                            UnconfirmedTransaction::from(transaction.clone()),
+                            transaction.clone(),
+                        )
+                        .await
+                    {
+                        info!("Transaction pool broadcasted the transaction");
                     }
-                };
-                // Broadcast the transaction.
-                if self_
-                    .unconfirmed_transaction(
-                        self_.router.local_ip(),
-                        UnconfirmedTransaction::from(transaction.clone()),
-                        transaction.clone(),
-                    )
-                    .await
-                {
-                    info!("Transaction pool broadcasted the transaction");
                 }
             }
-        });
+        };
+        self.spawn(supervise(self.clone(), "transaction-pool", self.shutdown.clone(), transaction_pool));
         Ok(())
     }
 
@@ -417,6 +442,20 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
 
 #[async_trait]
 impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Validator<N, C> {
+    /// Returns the configuration of the node.
+    fn config(&self) -> &NodeConfig {
+        &self.config
+    }
+
+    /// Reloads the TLS certificate of the REST server from its files, returning `false` if the node does not serve
+    /// its REST endpoints over HTTPS.
+    fn reload_rest_tls_config(&self) -> Result<bool> {
+        match &self.rest {
+            Some(rest) => rest.reload_tls_config(),
+            None => Ok(false),
+        }
+    }
+
     /// Shuts down the node.
     async fn shut_down(&self) {
         info!("Shutting down...");
@@ -485,7 +524,14 @@ mod tests {
             &[],
             genesis,
             None,
+            Default::default(),
             dev,
+            None,
+            None,
+            Default::default(),
+            None,
+            None,
+            Default::default(),
         )
         .await
         .unwrap();
@@ -105,8 +105,8 @@ impl<N: Network, C: ConsensusStorage<N>> Reading for Validator<N, C> {
 
     /// Creates a [`Decoder`] used to interpret messages from the network.
     /// The `side` param indicates the connection side **from the node's perspective**.
-    fn codec(&self, _peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
-        Default::default()
+    fn codec(&self, peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
+        self.router.message_codec(peer_addr)
     }
 
     /// Processes a message received from the network.
@@ -162,7 +162,13 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Validator<N, C> {
     fn block_response(&self, peer_ip: SocketAddr, blocks: Vec<Block<N>>) -> bool {
         // Tries to advance with blocks from the sync module.
         match self.sync.advance_with_sync_blocks(peer_ip, blocks) {
-            Ok(()) => true,
+            Ok(invalid_block_senders) => {
+                // Penalize the peers that sent a block which failed verification (this disconnects them).
+                for sender_ip in invalid_block_senders {
+                    self.router.penalize_invalid_block_sender(sender_ip);
+                }
+                true
+            }
             Err(error) => {
                 warn!("{error}");
                 false
@@ -244,6 +250,10 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Validator<N, C> {
             trace!("[UnconfirmedSolution] {error}");
             return true; // Maintain the connection.
         }
+        // Skip the relay if the network has upgraded past this node.
+        if self.router.is_outdated() {
+            return true; // Maintain the connection.
+        }
         let message = Message::UnconfirmedSolution(serialized);
         // Propagate the "UnconfirmedSolution" to the connected validators.
         self.propagate_to_validators(message, &[peer_ip]);
@@ -262,6 +272,10 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Validator<N, C> {
             trace!("[UnconfirmedTransaction] {error}");
             return true; // Maintain the connection.
         }
+        // Skip the relay if the network has upgraded past this node.
+        if self.router.is_outdated() {
+            return true; // Maintain the connection.
+        }
         let message = Message::UnconfirmedTransaction(serialized);
         // Propagate the "UnconfirmedTransaction" to the connected validators.
         self.propagate_to_validators(message, &[peer_ip]);
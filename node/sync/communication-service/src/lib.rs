@@ -28,6 +28,10 @@ pub trait CommunicationService: Send + Sync {
     /// Prepares a block request to be sent.
     fn prepare_block_request(start: u32, end: u32) -> Self::Message;
 
+    /// Returns `true` if the given peer is trusted to decide that the node is on a minority fork,
+    /// which halts the node if it is configured to halt on a minority fork.
+    fn is_trusted_peer(&self, peer_ip: SocketAddr) -> bool;
+
     /// Sends the given message to specified peer.
     ///
     /// This function returns as soon as the message is queued to be sent,
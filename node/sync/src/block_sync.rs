@@ -13,7 +13,15 @@
 // limitations under the License.
 
 use crate::{
-    helpers::{PeerPair, SyncRequest},
+    helpers::{
+        record_validation_phase,
+        stream_block,
+        BlockStream,
+        PeerPair,
+        SyncRequest,
+        ValidationHistogram,
+        ValidationPhase,
+    },
     locators::BlockLocators,
 };
 use snarkos_node_bft_ledger_service::LedgerService;
@@ -29,6 +37,7 @@ use rand::{prelude::IteratorRandom, CryptoRng, Rng};
 use std::{
     collections::BTreeMap,
     net::{IpAddr, Ipv4Addr, SocketAddr},
+    str::FromStr,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -51,6 +60,48 @@ pub const MAX_BLOCKS_BEHIND: u32 = 2; // blocks
 /// Note: This here does not need to be a real IP address, but it must be unique/distinct from all other connections.
 const DUMMY_SELF_IP: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 0);
 
+/// The minimum number of peers with block locators required to decide that the node is on a minority fork.
+const MIN_PEERS_FOR_FORK_DETECTION: usize = REDUNDANCY_FACTOR;
+
+/// The action taken when the node detects that the majority of its peers are on a different chain.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum MinorityForkPolicy {
+    /// A critical alert is logged, and the node continues as usual.
+    #[default]
+    Alert,
+    /// A critical alert is logged, and the node stops syncing and proposing batches until the fork is resolved.
+    Halt,
+}
+
+impl FromStr for MinorityForkPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(policy: &str) -> Result<Self> {
+        match policy {
+            "alert" => Ok(Self::Alert),
+            "halt" => Ok(Self::Halt),
+            _ => bail!("Invalid minority fork policy '{policy}' (expected 'alert' or 'halt')"),
+        }
+    }
+}
+
+/// The configuration of a block sync module.
+#[derive(Clone, Debug, Default)]
+pub struct BlockSyncConfig {
+    /// The maximum number of blocks a peer may advertise above the median peer height, if set.
+    pub height_sanity_threshold: Option<u32>,
+    /// The maximum number of blocks that may be requested or buffered at once, or `None` for `MAX_BLOCK_REQUESTS`.
+    pub sync_window: Option<usize>,
+    /// The number of seconds after which a block request times out, or `None` for `BLOCK_REQUEST_TIMEOUT_IN_SECS`.
+    pub block_request_timeout: Option<u64>,
+    /// The action taken when the node detects it is on a minority fork.
+    pub minority_fork_policy: MinorityForkPolicy,
+    /// The histogram that records the timings of each block validation phase, if profiling is enabled.
+    pub validation_histogram: Option<Arc<ValidationHistogram>>,
+    /// The stream that each newly-applied block is appended to, if streaming is enabled.
+    pub block_stream: Option<BlockStream>,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum BlockSyncMode {
     Router,
@@ -95,9 +146,9 @@ pub struct BlockSync<N: Network> {
     /// The map of block height to the expected block hash and peer IPs.
     /// Each entry is removed when its corresponding entry in the responses map is removed.
     requests: Arc<RwLock<BTreeMap<u32, SyncRequest<N>>>>,
-    /// The map of block height to the received blocks.
+    /// The map of block height to the received blocks, and the peer IPs that sent them.
     /// Removing an entry from this map must remove the corresponding entry from the requests map.
-    responses: Arc<RwLock<BTreeMap<u32, Block<N>>>>,
+    responses: Arc<RwLock<BTreeMap<u32, (Block<N>, IndexSet<SocketAddr>)>>>,
     /// The map of block height to the timestamp of the last time the block was requested.
     /// This map is used to determine which requests to remove if they have been pending for too long.
     request_timestamps: Arc<RwLock<BTreeMap<u32, Instant>>>,
@@ -106,11 +157,37 @@ pub struct BlockSync<N: Network> {
     request_timeouts: Arc<RwLock<IndexMap<SocketAddr, Vec<Instant>>>>,
     /// The boolean indicator of whether the node is synced up to the latest block (within the given tolerance).
     is_block_synced: Arc<AtomicBool>,
+    /// The boolean indicator of whether the majority of peers are on a different chain than the node.
+    is_on_minority_fork: Arc<AtomicBool>,
+    /// The boolean indicator of whether the node is halted, as the majority of its trusted peers are on a
+    /// different chain than the node, and it is configured to halt on a minority fork.
+    is_halted_on_minority_fork: Arc<AtomicBool>,
+    /// The maximum number of blocks a peer may advertise above the median peer height, if set.
+    /// Peers advertising a height beyond this threshold are ignored as sync candidates.
+    height_sanity_threshold: Option<u32>,
+    /// The maximum number of blocks that may be requested or buffered, but not yet applied, at once.
+    sync_window: usize,
+    /// The number of seconds after which an incomplete block request times out.
+    block_request_timeout_in_secs: u64,
+    /// The action taken when the node detects it is on a minority fork.
+    minority_fork_policy: MinorityForkPolicy,
+    /// The histogram of block validation timings, if profiling is enabled.
+    validation_histogram: Option<Arc<ValidationHistogram>>,
+    /// The stream of newly-applied blocks, if streaming is enabled.
+    block_stream: Option<BlockStream>,
 }
 
 impl<N: Network> BlockSync<N> {
     /// Initializes a new block sync module.
-    pub fn new(mode: BlockSyncMode, ledger: Arc<dyn LedgerService<N>>) -> Self {
+    pub fn new(mode: BlockSyncMode, ledger: Arc<dyn LedgerService<N>>, config: BlockSyncConfig) -> Self {
+        let BlockSyncConfig {
+            height_sanity_threshold,
+            sync_window,
+            block_request_timeout,
+            minority_fork_policy,
+            validation_histogram,
+            block_stream,
+        } = config;
         Self {
             mode,
             canon: ledger,
@@ -121,6 +198,14 @@ impl<N: Network> BlockSync<N> {
             request_timestamps: Default::default(),
             request_timeouts: Default::default(),
             is_block_synced: Default::default(),
+            is_on_minority_fork: Default::default(),
+            is_halted_on_minority_fork: Default::default(),
+            height_sanity_threshold,
+            sync_window: sync_window.unwrap_or(MAX_BLOCK_REQUESTS),
+            block_request_timeout_in_secs: block_request_timeout.unwrap_or(BLOCK_REQUEST_TIMEOUT_IN_SECS),
+            minority_fork_policy,
+            validation_histogram,
+            block_stream,
         }
     }
 
@@ -135,6 +220,31 @@ impl<N: Network> BlockSync<N> {
     pub fn is_block_synced(&self) -> bool {
         self.is_block_synced.load(Ordering::SeqCst)
     }
+
+    /// Returns `true` if the majority of peers were on a different chain than the node, as of the last block sync.
+    #[inline]
+    pub fn is_on_minority_fork(&self) -> bool {
+        self.is_on_minority_fork.load(Ordering::SeqCst)
+    }
+
+    /// Returns `true` if the node is on a minority fork of its trusted peers, as of the last block sync,
+    /// and is configured to halt until the fork is resolved.
+    #[inline]
+    pub fn is_halted_on_minority_fork(&self) -> bool {
+        self.is_halted_on_minority_fork.load(Ordering::SeqCst)
+    }
+
+    /// Returns the greatest block height advertised by any peer, if there are any peers.
+    #[inline]
+    pub fn greatest_peer_height(&self) -> Option<u32> {
+        self.locators.read().values().map(|locators| locators.latest_locator_height()).max()
+    }
+
+    /// Returns the number of blocks that are requested or buffered, but not yet applied.
+    #[inline]
+    pub fn num_blocks_in_flight(&self) -> usize {
+        self.requests.read().len()
+    }
 }
 
 #[allow(dead_code)]
@@ -166,6 +276,13 @@ impl<N: Network> BlockSync<N> {
     //         .collect()
     // }
 
+    /// Returns the median of the latest block heights advertised by the peers, if there are any peers.
+    fn get_median_peer_height(&self) -> Option<u32> {
+        let heights =
+            self.locators.read().values().map(|locators| locators.latest_locator_height()).sorted().collect::<Vec<_>>();
+        heights.get(heights.len() / 2).copied()
+    }
+
     /// Returns the common ancestor for the given peer pair, if it exists.
     fn get_common_ancestor(&self, peer_a: SocketAddr, peer_b: SocketAddr) -> Option<u32> {
         self.common_ancestors.read().get(&PeerPair(peer_a, peer_b)).copied()
@@ -210,10 +327,16 @@ impl<N: Network> BlockSync<N> {
     /// Performs one iteration of the block sync.
     #[inline]
     pub async fn try_block_sync<C: CommunicationService>(&self, communication: &C) {
+        // If the node is on a minority fork, and is configured to halt, do not extend its chain.
+        if self.update_is_on_minority_fork(|peer_ip| communication.is_trusted_peer(*peer_ip)) {
+            trace!("Skipping block sync (halted on a minority fork, pending an operator decision)");
+            return;
+        }
+
         // Prepare the block requests, if any.
         // In the process, we update the state of `is_block_synced` for the sync module.
         let block_requests = self.prepare_block_requests();
-        trace!("Prepared {} block requests", block_requests.len());
+        trace!("Prepared {} block requests ({} blocks in flight)", block_requests.len(), self.num_blocks_in_flight());
 
         // Process the block requests.
         'outer: for (height, (hash, previous_hash, sync_ips)) in block_requests {
@@ -243,6 +366,10 @@ impl<N: Network> BlockSync<N> {
             // Sleep for 10 milliseconds to avoid triggering spam detection.
             tokio::time::sleep(std::time::Duration::from_millis(10)).await;
         }
+
+        // Update the number of blocks in flight.
+        #[cfg(feature = "metrics")]
+        metrics::gauge!(metrics::sync::BLOCKS_IN_FLIGHT, self.num_blocks_in_flight() as f64);
     }
 
     /// Processes the block response from the given peer IP.
@@ -261,38 +388,58 @@ impl<N: Network> BlockSync<N> {
     #[inline]
     pub fn process_next_block(&self, next_height: u32) -> Option<Block<N>> {
         // Try to advance the ledger with a block from the sync pool.
-        self.remove_block_response(next_height)
+        self.remove_block_response(next_height).map(|(block, _)| block)
     }
 
     /// Attempts to advance with blocks from the sync pool.
+    /// Returns the peer IPs that sent a block which failed verification, if any.
     #[inline]
-    pub fn advance_with_sync_blocks(&self, peer_ip: SocketAddr, blocks: Vec<Block<N>>) -> Result<()> {
+    pub fn advance_with_sync_blocks(&self, peer_ip: SocketAddr, blocks: Vec<Block<N>>) -> Result<IndexSet<SocketAddr>> {
         // Process the block response from the given peer IP.
         self.process_block_response(peer_ip, blocks)?;
 
         // Retrieve the latest block height.
         let mut current_height = self.canon.latest_block_height();
         // Try to advance the ledger with the sync pool.
-        while let Some(block) = self.remove_block_response(current_height + 1) {
+        while let Some((block, peer_ips)) = self.remove_block_response(current_height + 1) {
             // Ensure the block height matches.
             if block.height() != current_height + 1 {
                 warn!("Block height mismatch: expected {}, found {}", current_height + 1, block.height());
                 break;
             }
-            // Check the next block.
-            if let Err(error) = self.canon.check_next_block(&block) {
-                warn!("The next block ({}) is invalid - {error}", block.height());
-                break;
+            // Check the next block. On failure, drop the block, and stop syncing from the peers that sent it.
+            let timer = Instant::now();
+            let result = self.canon.check_next_block(&block);
+            record_validation_phase(self.validation_histogram.as_deref(), ValidationPhase::Check, timer.elapsed());
+            if let Err(error) = result {
+                warn!(
+                    "Block {} ('{}') from {peer_ips:?} failed verification, dropping it - {error}",
+                    block.height(),
+                    block.hash()
+                );
+                for peer_ip in &peer_ips {
+                    self.remove_peer(peer_ip);
+                    #[cfg(feature = "metrics")]
+                    metrics::increment_counter!(metrics::sync::INVALID_BLOCKS, "peer" => peer_ip.to_string());
+                }
+                return Ok(peer_ips);
             }
             // Attempt to advance to the next block.
-            if let Err(error) = self.canon.advance_to_next_block(&block) {
+            let timer = Instant::now();
+            let result = self.canon.advance_to_next_block(&block);
+            record_validation_phase(self.validation_histogram.as_deref(), ValidationPhase::Advance, timer.elapsed());
+            if let Err(error) = result {
                 warn!("{error}");
                 break;
             }
+            // Append the block to the block stream, if it is enabled.
+            stream_block(self.block_stream.as_ref(), &block);
             // Increment the latest height.
             current_height += 1;
+            #[cfg(feature = "metrics")]
+            metrics::gauge!(metrics::blocks::HEIGHT, current_height as f64);
         }
-        Ok(())
+        Ok(Default::default())
     }
 }
 
@@ -376,6 +523,77 @@ impl<N: Network> BlockSync<N> {
 }
 
 impl<N: Network> BlockSync<N> {
+    /// Returns the number of peers on a different chain than the node, and the number of peers with block locators,
+    /// among the peers selected by `is_counted`, if the majority of them are on a different chain. A peer is on a
+    /// different chain if any of its block locators disagrees with the block hash of the node at the same height.
+    fn find_minority_fork(&self, is_counted: impl Fn(&SocketAddr) -> bool) -> Option<(usize, usize)> {
+        let locators = self.locators.read();
+        let peer_locators = locators.iter().filter(|(peer_ip, _)| is_counted(peer_ip)).map(|(_, locators)| locators);
+        let peer_locators = peer_locators.collect::<Vec<_>>();
+        // Ensure there are enough peers to decide the majority.
+        if peer_locators.len() < MIN_PEERS_FOR_FORK_DETECTION {
+            return None;
+        }
+        // Cache the canonical block hashes, as the peers mostly advertise the same (checkpoint) heights.
+        // Note: The node does not have a block at a height greater than its own height yet.
+        let mut canon_hashes = IndexMap::new();
+        let mut canon_hash =
+            |height: u32| *canon_hashes.entry(height).or_insert_with(|| self.canon.get_block_hash(height).ok());
+        // Count the peers that disagree with the canonical chain at any height.
+        let num_forked = peer_locators
+            .iter()
+            .filter(|locators| {
+                BlockLocators::clone(locators).into_iter().any(|(height, hash)| match canon_hash(height) {
+                    Some(canon_hash) => canon_hash != hash,
+                    None => false,
+                })
+            })
+            .count();
+        // Return the counts, if the majority of peers are on a different chain.
+        match num_forked * 2 > peer_locators.len() {
+            true => Some((num_forked, peer_locators.len())),
+            false => None,
+        }
+    }
+
+    /// Updates the state of `is_on_minority_fork`, logging a critical alert when the node first detects that it is
+    /// on a minority fork, and returns `true` if the node is halted on the minority fork.
+    ///
+    /// Any peers may raise the alert, but only the trusted peers (as selected by `is_trusted`) may halt the node,
+    /// so that untrusted peers cannot halt it by advertising a different chain.
+    fn update_is_on_minority_fork(&self, is_trusted: impl Fn(&SocketAddr) -> bool) -> bool {
+        let is_halted = match self.minority_fork_policy {
+            MinorityForkPolicy::Halt => self.find_minority_fork(is_trusted).is_some(),
+            MinorityForkPolicy::Alert => false,
+        };
+        match self.find_minority_fork(|_| true) {
+            Some((num_forked, num_peers)) => {
+                if !self.is_on_minority_fork.swap(true, Ordering::SeqCst) {
+                    let action = match is_halted {
+                        true => "halting until the fork is resolved",
+                        false => "not halting",
+                    };
+                    error!(
+                        "CRITICAL: This node is on a minority fork ({num_forked} of {num_peers} peers disagree \
+                         with its chain), operator action may be required ({action})"
+                    );
+                }
+            }
+            None => {
+                if self.is_on_minority_fork.swap(false, Ordering::SeqCst) {
+                    info!("This node is no longer on a minority fork");
+                }
+            }
+        }
+        if self.is_halted_on_minority_fork.swap(is_halted, Ordering::SeqCst) != is_halted {
+            match is_halted {
+                true => error!("CRITICAL: Halted on a minority fork, as the majority of trusted peers disagree"),
+                false => info!("Resumed after a minority fork, as the majority of trusted peers agree again"),
+            }
+        }
+        is_halted
+    }
+
     /// Returns a list of block requests, if the node needs to sync.
     fn prepare_block_requests(&self) -> Vec<(u32, SyncRequest<N>)> {
         // Remove timed out block requests.
@@ -445,19 +663,18 @@ impl<N: Network> BlockSync<N> {
 
         // Acquire the write lock on the responses map.
         let mut responses = self.responses.write();
-        // Insert the candidate block into the responses map.
-        if let Some(existing_block) = responses.insert(height, block.clone()) {
-            // If the candidate block was already present, ensure it is the same block.
-            if block != existing_block {
-                // Remove the candidate block.
-                responses.remove(&height);
-                // Drop the write lock on the responses map.
-                drop(responses);
-                // Remove all block requests to the peer.
-                self.remove_block_requests_to_peer(&peer_ip);
-                bail!("Candidate block {height} from '{peer_ip}' is malformed");
-            }
+        // If the candidate block was already present, ensure it is the same block.
+        if responses.get(&height).map(|(existing_block, _)| block != *existing_block).unwrap_or(false) {
+            // Remove the candidate block.
+            responses.remove(&height);
+            // Drop the write lock on the responses map.
+            drop(responses);
+            // Remove all block requests to the peer.
+            self.remove_block_requests_to_peer(&peer_ip);
+            bail!("Candidate block {height} from '{peer_ip}' is malformed");
         }
+        // Insert the candidate block into the responses map, along with the peer IP that sent it.
+        responses.entry(height).or_insert_with(|| (block, Default::default())).1.insert(peer_ip);
 
         Ok(())
     }
@@ -522,8 +739,8 @@ impl<N: Network> BlockSync<N> {
         self.request_timestamps.write().remove(&height);
     }
 
-    /// Removes and returns the block response for the given height, if the request is complete.
-    fn remove_block_response(&self, height: u32) -> Option<Block<N>> {
+    /// Removes and returns the block response (with its senders) for the given height, if the request is complete.
+    fn remove_block_response(&self, height: u32) -> Option<(Block<N>, IndexSet<SocketAddr>)> {
         // Acquire the requests write lock.
         // Note: This lock must be held across the entire scope, due to asynchronous block responses
         // from multiple peers that may be received concurrently.
@@ -601,7 +818,7 @@ impl<N: Network> BlockSync<N> {
         // Remove timed out block requests.
         request_timestamps.retain(|height, timestamp| {
             // Determine if the duration since the request timestamp has exceeded the request timeout.
-            let is_time_passed = now.duration_since(*timestamp).as_secs() > BLOCK_REQUEST_TIMEOUT_IN_SECS;
+            let is_time_passed = now.duration_since(*timestamp).as_secs() > self.block_request_timeout_in_secs;
             // Determine if the request is incomplete.
             let is_request_incomplete =
                 !requests.get(height).map(|(_, _, peer_ips)| peer_ips.is_empty()).unwrap_or(false);
@@ -650,12 +867,28 @@ impl<N: Network> BlockSync<N> {
             .map(|(peer_ip, timestamps)| (*peer_ip, timestamps.len()))
             .collect::<IndexMap<_, _>>();
 
+        // Compute the maximum plausible peer height, if a height sanity threshold is set.
+        let max_plausible_height = match (self.height_sanity_threshold, self.get_median_peer_height()) {
+            (Some(threshold), Some(median)) => Some(median.saturating_add(threshold)),
+            _ => None,
+        };
+
         // Pick a set of peers above the latest canon height, and include their locators.
         let candidate_locators: IndexMap<_, _> = self
             .locators
             .read()
             .iter()
             .filter(|(_, locators)| locators.latest_locator_height() > latest_canon_height)
+            .filter(|(ip, locators)| match max_plausible_height {
+                Some(max_height) if locators.latest_locator_height() > max_height => {
+                    debug!(
+                        "Ignoring sync peer '{ip}' - its height ({}) is implausibly far above its peers ({max_height})",
+                        locators.latest_locator_height()
+                    );
+                    false
+                }
+                _ => true,
+            })
             .filter(|(ip, _)| timeouts.get(*ip).map(|count| *count < MAX_BLOCK_REQUEST_TIMEOUTS).unwrap_or(true))
             .sorted_by(|(_, a), (_, b)| b.latest_locator_height().cmp(&a.latest_locator_height()))
             .take(NUM_SYNC_CANDIDATE_PEERS)
@@ -737,8 +970,8 @@ impl<N: Network> BlockSync<N> {
 
         // Compute the start height for the block request.
         let start_height = latest_canon_height + 1;
-        // Compute the end height for the block request.
-        let end_height = (min_common_ancestor + 1).min(start_height + MAX_BLOCK_REQUESTS as u32);
+        // Compute the end height for the block request, bounded by the sync window.
+        let end_height = (min_common_ancestor + 1).min(start_height + self.sync_window as u32);
 
         let mut requests = Vec::with_capacity((start_height..end_height).len());
 
@@ -857,7 +1090,10 @@ mod tests {
 
     use indexmap::indexset;
     use snarkvm::ledger::committee::Committee;
-    use std::net::{IpAddr, Ipv4Addr};
+    use std::{
+        net::{IpAddr, Ipv4Addr},
+        time::Duration,
+    };
 
     type CurrentNetwork = snarkvm::prelude::Testnet3;
 
@@ -880,7 +1116,8 @@ mod tests {
 
     /// Returns the sync pool, with the canonical ledger initialized to the given height.
     fn sample_sync_at_height(height: u32) -> BlockSync<CurrentNetwork> {
-        BlockSync::<CurrentNetwork>::new(BlockSyncMode::Router, Arc::new(sample_ledger_service(height)))
+        let ledger = Arc::new(sample_ledger_service(height));
+        BlockSync::<CurrentNetwork>::new(BlockSyncMode::Router, ledger, Default::default())
     }
 
     /// Checks that the sync pool (starting at genesis) returns the correct requests.
@@ -981,6 +1218,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_prepare_block_requests_with_height_sanity_threshold() {
+        let ledger = Arc::new(sample_ledger_service(0));
+        let sync = BlockSync::<CurrentNetwork>::new(BlockSyncMode::Router, ledger, BlockSyncConfig {
+            height_sanity_threshold: Some(100),
+            ..Default::default()
+        });
+
+        // Add three honest peers at height 10.
+        let mut peers = indexset![];
+        for peer_id in 1..=3 {
+            sync.update_peer_locators(sample_peer_ip(peer_id), sample_block_locators(10)).unwrap();
+            peers.insert(sample_peer_ip(peer_id));
+        }
+        // Add a peer advertising a height far above the median.
+        sync.update_peer_locators(sample_peer_ip(4), sample_block_locators(10_000)).unwrap();
+
+        // Ensure the outlier is not selected as a sync peer.
+        let (sync_peers, min_common_ancestor) = sync.find_sync_peers().unwrap();
+        assert!(!sync_peers.contains_key(&sample_peer_ip(4)));
+        assert_eq!(min_common_ancestor, 10);
+
+        // The requests should only be prepared from the honest peers.
+        check_prepare_block_requests(sync, 10, peers);
+    }
+
+    #[test]
+    fn test_prepare_block_requests_with_sync_window() {
+        let ledger = Arc::new(sample_ledger_service(0));
+        let sync = BlockSync::<CurrentNetwork>::new(BlockSyncMode::Router, ledger, BlockSyncConfig {
+            sync_window: Some(5),
+            ..Default::default()
+        });
+
+        // Add three peers at height 10.
+        for peer_id in 1..=3 {
+            sync.update_peer_locators(sample_peer_ip(peer_id), sample_block_locators(10)).unwrap();
+        }
+
+        // Ensure only the blocks within the sync window are requested.
+        let requests = sync.prepare_block_requests();
+        assert_eq!(requests.len(), 5);
+        for (height, request) in requests {
+            sync.insert_block_request(height, request).unwrap();
+        }
+        assert_eq!(sync.num_blocks_in_flight(), 5);
+
+        // Ensure no further blocks are requested until the in-flight blocks are applied.
+        assert!(sync.prepare_block_requests().is_empty());
+    }
+
     #[test]
     fn test_prepare_block_requests_with_leading_fork_at_11() {
         let sync = sample_sync_at_height(0);
@@ -1150,6 +1438,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_remove_timed_out_block_requests() {
+        let ledger = Arc::new(sample_ledger_service(0));
+        let sync = BlockSync::<CurrentNetwork>::new(BlockSyncMode::Router, ledger, BlockSyncConfig {
+            block_request_timeout: Some(30),
+            ..Default::default()
+        });
+
+        // Insert two block requests.
+        sync.insert_block_request(1, (None, None, indexset![sample_peer_ip(1)])).unwrap();
+        sync.insert_block_request(2, (None, None, indexset![sample_peer_ip(1)])).unwrap();
+        // Backdate the first request to just past the configured timeout.
+        sync.request_timestamps.write().insert(1, Instant::now() - Duration::from_secs(31));
+        // Backdate the second request to just before the configured timeout (but past the default).
+        sync.request_timestamps.write().insert(2, Instant::now() - Duration::from_secs(20));
+
+        // Only the first request should time out.
+        assert_eq!(sync.remove_timed_out_block_requests(), 1);
+        assert!(sync.get_block_request(1).is_none());
+        assert!(sync.get_block_request(2).is_some());
+    }
+
     #[test]
     fn test_insert_block_requests_fails() {
         let sync = sample_sync_at_height(9);
@@ -1200,6 +1510,73 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_find_minority_fork() {
+        let sync = sample_sync_at_height(10);
+
+        // Ensure a single forked peer is not enough to decide the majority.
+        sync.update_peer_locators(sample_peer_ip(1), sample_block_locators_with_fork(10, 5)).unwrap();
+        assert_eq!(sync.find_minority_fork(|_| true), None);
+
+        // Ensure the node is not on a minority fork, while the majority of peers agree with it.
+        sync.update_peer_locators(sample_peer_ip(2), sample_block_locators(20)).unwrap();
+        sync.update_peer_locators(sample_peer_ip(3), sample_block_locators(10)).unwrap();
+        assert_eq!(sync.find_minority_fork(|_| true), None);
+        assert!(!sync.update_is_on_minority_fork(|_| true));
+        assert!(!sync.is_on_minority_fork());
+
+        // Ensure the node is on a minority fork, once the majority of peers are on a different chain.
+        sync.update_peer_locators(sample_peer_ip(3), sample_block_locators_with_fork(12, 8)).unwrap();
+        assert_eq!(sync.find_minority_fork(|_| true), Some((2, 3)));
+        // Ensure the node does not halt, as it is configured to alert.
+        assert!(!sync.update_is_on_minority_fork(|_| true));
+        assert!(sync.is_on_minority_fork());
+        assert!(!sync.is_halted_on_minority_fork());
+
+        // Ensure only the counted peers decide the majority.
+        sync.update_peer_locators(sample_peer_ip(4), sample_block_locators(15)).unwrap();
+        let is_trusted = |peer_ip: &SocketAddr| *peer_ip != sample_peer_ip(1);
+        assert_eq!(sync.find_minority_fork(is_trusted), None);
+        let is_trusted = |peer_ip: &SocketAddr| *peer_ip != sample_peer_ip(2);
+        assert_eq!(sync.find_minority_fork(is_trusted), Some((2, 3)));
+
+        // Ensure the fork is resolved, once the majority of peers agree with the node again.
+        sync.remove_peer(&sample_peer_ip(1));
+        sync.update_peer_locators(sample_peer_ip(5), sample_block_locators(11)).unwrap();
+        assert_eq!(sync.find_minority_fork(|_| true), None);
+        assert!(!sync.update_is_on_minority_fork(|_| true));
+        assert!(!sync.is_on_minority_fork());
+    }
+
+    #[test]
+    fn test_halt_on_minority_fork() {
+        let ledger = Arc::new(sample_ledger_service(10));
+        let sync = BlockSync::<CurrentNetwork>::new(BlockSyncMode::Router, ledger, BlockSyncConfig {
+            minority_fork_policy: MinorityForkPolicy::Halt,
+            ..Default::default()
+        });
+
+        // Ensure the node halts, once the majority of trusted peers are on a different chain.
+        sync.update_peer_locators(sample_peer_ip(1), sample_block_locators_with_fork(10, 5)).unwrap();
+        sync.update_peer_locators(sample_peer_ip(2), sample_block_locators_with_fork(12, 8)).unwrap();
+        sync.update_peer_locators(sample_peer_ip(3), sample_block_locators(10)).unwrap();
+        assert!(sync.update_is_on_minority_fork(|_| true));
+        assert!(sync.is_halted_on_minority_fork());
+
+        // Ensure the untrusted peers alone do not halt the node.
+        let is_trusted = |peer_ip: &SocketAddr| *peer_ip == sample_peer_ip(3);
+        assert!(!sync.update_is_on_minority_fork(is_trusted));
+        assert!(sync.is_on_minority_fork());
+        assert!(!sync.is_halted_on_minority_fork());
+    }
+
+    #[test]
+    fn test_minority_fork_policy() {
+        assert_eq!(MinorityForkPolicy::from_str("alert").unwrap(), MinorityForkPolicy::Alert);
+        assert_eq!(MinorityForkPolicy::from_str("halt").unwrap(), MinorityForkPolicy::Halt);
+        assert!(MinorityForkPolicy::from_str("reorg").is_err());
+    }
+
     #[test]
     fn test_remove_peer() {
         let sync = sample_sync_at_height(0);
@@ -0,0 +1,118 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{block::Block, Network, ToBytes};
+
+use anyhow::{bail, Result};
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, SyncSender, TrySendError},
+};
+
+/// The maximum number of blocks queued for the block stream.
+/// While the queue is full (e.g. no reader is attached to a named pipe), new blocks are dropped from the stream.
+const BLOCK_STREAM_CAPACITY: usize = 64;
+
+/// Appends the given block to the given block stream, if streaming is enabled.
+/// This never blocks the caller, so a slow or unavailable sink does not delay block application.
+pub fn stream_block<N: Network>(stream: Option<&BlockStream>, block: &Block<N>) {
+    if let Some(stream) = stream {
+        match block.to_bytes_le() {
+            Ok(bytes) => stream.send(block.height(), bytes),
+            Err(error) => warn!("Failed to serialize block {} for the block stream - {error}", block.height()),
+        }
+    }
+}
+
+/// A stream of serialized blocks, written to its sink by a dedicated thread.
+/// Each block is written as its length (`u32`) and its bytes, both in little-endian.
+#[derive(Clone, Debug)]
+pub struct BlockStream {
+    /// The sender of serialized blocks to the writer thread.
+    sender: SyncSender<Vec<u8>>,
+}
+
+impl BlockStream {
+    /// Initializes a new block stream, spawning the thread that writes to the file (or named pipe) at the given path.
+    pub fn new(path: PathBuf) -> Result<Self> {
+        // Ensure the directory of the sink exists, as the sink itself is only opened by the writer.
+        if let Some(directory) = path.parent() {
+            if !directory.as_os_str().is_empty() && !directory.is_dir() {
+                bail!("The directory of the block stream '{}' does not exist", path.display());
+            }
+        }
+        let (sender, receiver) = mpsc::sync_channel(BLOCK_STREAM_CAPACITY);
+        // Note: The writer runs on its own thread, as opening a named pipe blocks until a reader attaches.
+        std::thread::spawn(move || write_blocks(&path, receiver));
+        Ok(Self { sender })
+    }
+
+    /// Queues the given serialized block for the sink, dropping it if the queue is full.
+    fn send(&self, height: u32, bytes: Vec<u8>) {
+        match self.sender.try_send(bytes) {
+            Ok(()) => (),
+            Err(TrySendError::Full(_)) => warn!("Dropped block {height} from the block stream (the sink is not ready)"),
+            Err(TrySendError::Disconnected(_)) => warn!("Dropped block {height} from the block stream (it is closed)"),
+        }
+    }
+}
+
+/// Writes the received blocks to the sink at the given path, until the sender is dropped.
+/// If the sink becomes unavailable (e.g. the reader of a named pipe disconnects), it is reopened for the next block.
+fn write_blocks(path: &Path, receiver: Receiver<Vec<u8>>) {
+    let mut sink: Option<File> = None;
+    while let Ok(bytes) = receiver.recv() {
+        // Open the sink, if it is not open.
+        let file = match &mut sink {
+            Some(file) => file,
+            None => match OpenOptions::new().append(true).create(true).open(path) {
+                Ok(file) => sink.insert(file),
+                Err(error) => {
+                    warn!("Failed to open the block stream '{}' - {error}", path.display());
+                    continue;
+                }
+            },
+        };
+        // Write the length-prefixed block.
+        let result = file.write_all(&(bytes.len() as u32).to_le_bytes()).and_then(|_| file.write_all(&bytes));
+        if let Err(error) = result {
+            warn!("Failed to write to the block stream '{}', reopening it - {error}", path.display());
+            sink = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_blocks() {
+        let path = std::env::temp_dir().join(format!("snarkos-test-block-stream-{}", std::process::id()));
+
+        // Queue two blocks, and close the stream.
+        let (sender, receiver) = mpsc::sync_channel(BLOCK_STREAM_CAPACITY);
+        sender.send(vec![1, 2, 3]).unwrap();
+        sender.send(vec![4]).unwrap();
+        drop(sender);
+        write_blocks(&path, receiver);
+
+        // Ensure the blocks are written in order, with their length prefixes.
+        assert_eq!(std::fs::read(&path).unwrap(), [3, 0, 0, 0, 1, 2, 3, 1, 0, 0, 0, 4]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -12,6 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod block_stream;
+pub use block_stream::*;
+
+mod validation_histogram;
+pub use validation_histogram::*;
+
 use snarkvm::prelude::Network;
 
 use core::hash::Hash;
@@ -0,0 +1,200 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::Serialize;
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// The upper bounds (in milliseconds) of the validation histogram buckets.
+/// Durations above the last bound are counted in a final, unbounded bucket.
+pub const VALIDATION_HISTOGRAM_BUCKETS_MS: [u64; 12] = [1, 5, 10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000];
+
+/// The number of buckets in the validation histogram, including the unbounded bucket.
+const NUM_BUCKETS: usize = VALIDATION_HISTOGRAM_BUCKETS_MS.len() + 1;
+
+/// Records the duration of the given phase of block validation in the given histogram, if recording is enabled.
+pub fn record_validation_phase(histogram: Option<&ValidationHistogram>, phase: ValidationPhase, duration: Duration) {
+    if let Some(histogram) = histogram {
+        histogram.record(phase, duration);
+        #[cfg(feature = "metrics")]
+        metrics::histogram!(phase.metric_name(), duration.as_secs_f64());
+    }
+}
+
+/// A phase of block validation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ValidationPhase {
+    /// Checking the next block, which verifies its proofs and its state transition.
+    Check,
+    /// Advancing the ledger to the next block, which writes the block to storage.
+    Advance,
+}
+
+impl ValidationPhase {
+    /// Returns the name of the phase.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Check => "check",
+            Self::Advance => "advance",
+        }
+    }
+
+    /// Returns the name of the histogram metric of the phase.
+    #[cfg(feature = "metrics")]
+    const fn metric_name(&self) -> &'static str {
+        match self {
+            Self::Check => metrics::blocks::CHECK_DURATION,
+            Self::Advance => metrics::blocks::ADVANCE_DURATION,
+        }
+    }
+}
+
+/// The histogram of block validation timings, with fixed buckets for each phase.
+#[derive(Debug, Default)]
+pub struct ValidationHistogram {
+    /// The timings of checking the next block.
+    check: PhaseHistogram,
+    /// The timings of advancing to the next block.
+    advance: PhaseHistogram,
+}
+
+impl ValidationHistogram {
+    /// Records the duration of the given phase.
+    pub fn record(&self, phase: ValidationPhase, duration: Duration) {
+        match phase {
+            ValidationPhase::Check => self.check.record(duration),
+            ValidationPhase::Advance => self.advance.record(duration),
+        }
+    }
+
+    /// Returns a snapshot of the recorded timings.
+    pub fn snapshot(&self) -> ValidationHistogramSnapshot {
+        ValidationHistogramSnapshot {
+            phases: vec![self.check.snapshot(ValidationPhase::Check), self.advance.snapshot(ValidationPhase::Advance)],
+        }
+    }
+}
+
+/// The histogram of the timings of a single phase.
+#[derive(Debug, Default)]
+struct PhaseHistogram {
+    /// The number of timings in each bucket.
+    buckets: [AtomicU64; NUM_BUCKETS],
+    /// The sum of the timings, in microseconds.
+    sum_us: AtomicU64,
+    /// The longest timing, in microseconds.
+    max_us: AtomicU64,
+}
+
+impl PhaseHistogram {
+    /// Records the given duration.
+    fn record(&self, duration: Duration) {
+        let duration_us = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+        // Find the first bucket whose upper bound is not below the duration.
+        let index = VALIDATION_HISTOGRAM_BUCKETS_MS
+            .iter()
+            .position(|bound_ms| duration_us <= bound_ms.saturating_mul(1000))
+            .unwrap_or(NUM_BUCKETS - 1);
+        self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        self.sum_us.fetch_add(duration_us, Ordering::Relaxed);
+        self.max_us.fetch_max(duration_us, Ordering::Relaxed);
+    }
+
+    /// Returns a snapshot of the recorded timings of the given phase.
+    fn snapshot(&self, phase: ValidationPhase) -> PhaseSnapshot {
+        let buckets = self
+            .buckets
+            .iter()
+            .enumerate()
+            .map(|(index, count)| BucketSnapshot {
+                le_ms: VALIDATION_HISTOGRAM_BUCKETS_MS.get(index).copied(),
+                count: count.load(Ordering::Relaxed),
+            })
+            .collect::<Vec<_>>();
+        let count = buckets.iter().map(|bucket| bucket.count).sum();
+        let sum_us = self.sum_us.load(Ordering::Relaxed);
+        PhaseSnapshot {
+            phase: phase.name(),
+            count,
+            average_ms: match count {
+                0 => 0.0,
+                count => sum_us as f64 / count as f64 / 1000.0,
+            },
+            max_ms: self.max_us.load(Ordering::Relaxed) as f64 / 1000.0,
+            buckets,
+        }
+    }
+}
+
+/// A snapshot of the validation histogram.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ValidationHistogramSnapshot {
+    /// The timings of each phase.
+    pub phases: Vec<PhaseSnapshot>,
+}
+
+/// A snapshot of the timings of a single phase.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PhaseSnapshot {
+    /// The name of the phase.
+    pub phase: &'static str,
+    /// The number of recorded timings.
+    pub count: u64,
+    /// The average timing, in milliseconds.
+    pub average_ms: f64,
+    /// The longest timing, in milliseconds.
+    pub max_ms: f64,
+    /// The number of timings in each bucket.
+    pub buckets: Vec<BucketSnapshot>,
+}
+
+/// A snapshot of a single histogram bucket.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BucketSnapshot {
+    /// The upper bound of the bucket in milliseconds, or `None` for the unbounded bucket.
+    pub le_ms: Option<u64>,
+    /// The number of timings in the bucket.
+    pub count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_histogram() {
+        let histogram = ValidationHistogram::default();
+        histogram.record(ValidationPhase::Check, Duration::from_micros(500));
+        histogram.record(ValidationPhase::Check, Duration::from_millis(1));
+        histogram.record(ValidationPhase::Check, Duration::from_millis(30));
+        histogram.record(ValidationPhase::Advance, Duration::from_secs(60));
+
+        let snapshot = histogram.snapshot();
+        let check = &snapshot.phases[0];
+        assert_eq!(check.phase, "check");
+        assert_eq!(check.count, 3);
+        assert_eq!(check.max_ms, 30.0);
+        // The first two timings are within 1ms, and the third is within 50ms.
+        assert_eq!(check.buckets[0], BucketSnapshot { le_ms: Some(1), count: 2 });
+        assert_eq!(check.buckets[4], BucketSnapshot { le_ms: Some(50), count: 1 });
+
+        // Ensure a timing beyond the last bound is counted in the unbounded bucket.
+        let advance = &snapshot.phases[1];
+        assert_eq!(advance.count, 1);
+        assert_eq!(advance.buckets.len(), NUM_BUCKETS);
+        assert_eq!(advance.buckets[NUM_BUCKETS - 1], BucketSnapshot { le_ms: None, count: 1 });
+    }
+}
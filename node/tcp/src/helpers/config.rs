@@ -37,6 +37,14 @@ pub struct Config {
     ///
     /// note: [`Config::listener_ip`] must not be `None` in order for it to have any effect.
     pub desired_listening_port: Option<u16>,
+    /// The additional addresses the Tcp's connection listener should bind to, e.g. to listen on both IPv4 and IPv6.
+    /// Unlike the listening address, each of these must bind to its exact port (`0` binds to any available port).
+    ///
+    /// note: [`Config::listener_ip`] must not be `None` in order for it to have any effect.
+    pub extra_listening_addrs: Vec<SocketAddr>,
+    /// If set, the `IPV6_V6ONLY` option of the IPv6 listeners: `true` only accepts IPv6 connections, while `false`
+    /// also accepts IPv4 connections (as IPv4-mapped IPv6 addresses). If set to `None`, the OS default is used.
+    pub ipv6_only: Option<bool>,
     /// Allow listening on a different port if [`Config::desired_listening_port`] is unavailable.
     ///
     /// note: [`Config::listener_ip`] must not be `None` in order for it to have any effect.
@@ -52,6 +60,12 @@ pub struct Config {
     pub max_connections: u16,
     /// The maximum time (in milliseconds) allowed to establish a raw (before the [`Handshake`] protocol) TCP connection.
     pub connection_timeout_ms: u16,
+    /// The maximum time (in milliseconds) a connection may go without receiving a message, or spend writing one,
+    /// before it is considered dead and dropped.
+    ///
+    /// note: If set to `None`, there is no deadline. Tcp needs to implement the [`Reading`] and/or [`Writing`]
+    /// protocol in order for it to have any effect.
+    pub io_timeout_ms: Option<u64>,
 }
 
 impl Config {
@@ -84,10 +98,13 @@ impl Default for Config {
             name: None,
             listener_ip: default_ip(),
             desired_listening_port: None,
+            extra_listening_addrs: Vec::new(),
+            ipv6_only: None,
             allow_random_port: true,
             fatal_io_errors: vec![ConnectionReset, ConnectionAborted, BrokenPipe, InvalidData, UnexpectedEof],
             max_connections: 100,
             connection_timeout_ms: 1_000,
+            io_timeout_ms: None,
         }
     }
 }
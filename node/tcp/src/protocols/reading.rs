@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{io, net::SocketAddr};
+use std::{io, net::SocketAddr, time::Duration};
 
 use async_trait::async_trait;
 use bytes::BytesMut;
@@ -164,7 +164,29 @@ impl<R: Reading> ReadingInternal for R {
             // this task gets aborted, so there is no need for a dedicated timeout
             let _ = rx_conn_ready.await;
 
-            while let Some(bytes) = framed.next().await {
+            let io_timeout = node.config().io_timeout_ms.map(Duration::from_millis);
+            loop {
+                // wait for the next message, enforcing the read deadline if one is configured
+                let bytes = match io_timeout {
+                    Some(timeout) => match tokio::time::timeout(timeout, framed.next()).await {
+                        Ok(bytes) => bytes,
+                        Err(_) => {
+                            error!(
+                                parent: node.span(),
+                                "no message from {} in {:?}; dropping the connection",
+                                addr,
+                                timeout
+                            );
+                            node.known_peers().register_failure(addr);
+                            break;
+                        }
+                    },
+                    None => framed.next().await,
+                };
+                let Some(bytes) = bytes else {
+                    break;
+                };
+
                 match bytes {
                     Ok(msg) => {
                         // send the message for further processing
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{any::Any, collections::HashMap, io, net::SocketAddr, sync::Arc};
+use std::{any::Any, collections::HashMap, io, net::SocketAddr, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use futures_util::sink::SinkExt;
@@ -213,10 +213,20 @@ impl<W: Writing> WritingInternal for W {
             // move the cleanup into the task that gets aborted on disconnect
             let _auto_cleanup = auto_cleanup;
 
+            let io_timeout = node.config().io_timeout_ms.map(Duration::from_millis);
+
             while let Some(wrapped_msg) = outbound_message_receiver.recv().await {
                 let msg = wrapped_msg.msg.downcast().unwrap();
 
-                match self_clone.write_to_stream(*msg, &mut framed).await {
+                // write the message, enforcing the write deadline if one is configured
+                let result = match io_timeout {
+                    Some(timeout) => tokio::time::timeout(timeout, self_clone.write_to_stream(*msg, &mut framed))
+                        .await
+                        .unwrap_or_else(|_| Err(io::Error::new(io::ErrorKind::TimedOut, "the write deadline expired"))),
+                    None => self_clone.write_to_stream(*msg, &mut framed).await,
+                };
+
+                match result {
                     Ok(len) => {
                         let _ = wrapped_msg.delivery_notification.send(Ok(()));
                         node.known_peers().register_sent_message(addr, len);
@@ -226,7 +236,8 @@ impl<W: Writing> WritingInternal for W {
                     Err(e) => {
                         node.known_peers().register_failure(addr);
                         error!(parent: node.span(), "couldn't send a message to {}: {}", addr, e);
-                        let is_fatal = node.config().fatal_io_errors.contains(&e.kind());
+                        let is_fatal =
+                            e.kind() == io::ErrorKind::TimedOut || node.config().fatal_io_errors.contains(&e.kind());
                         let _ = wrapped_msg.delivery_notification.send(Err(e));
                         if is_fatal {
                             break;
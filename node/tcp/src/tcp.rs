@@ -27,6 +27,7 @@ use std::{
 
 use once_cell::sync::OnceCell;
 use parking_lot::Mutex;
+use socket2::{Domain, Protocol as SocketProtocol, Socket, Type};
 use tokio::{
     io::split,
     net::{TcpListener, TcpStream},
@@ -67,6 +68,8 @@ pub struct InnerTcp {
     config: Config,
     /// The node's listening address.
     listening_addr: OnceCell<SocketAddr>,
+    /// The node's additional listening addresses, see [`Config::extra_listening_addrs`].
+    extra_listening_addrs: Mutex<Vec<SocketAddr>>,
     /// Contains objects used by the protocols implemented by the node.
     pub(crate) protocols: Protocols,
     /// A list of connections that have not been finalized yet.
@@ -97,6 +100,7 @@ impl Tcp {
             span,
             config,
             listening_addr: Default::default(),
+            extra_listening_addrs: Default::default(),
             protocols: Default::default(),
             connecting: Default::default(),
             connections: Default::default(),
@@ -129,6 +133,11 @@ impl Tcp {
         self.listening_addr.get().copied().ok_or_else(|| io::ErrorKind::AddrNotAvailable.into())
     }
 
+    /// Returns the additional listening addresses, which accept connections alongside the listening address.
+    pub fn extra_listening_addrs(&self) -> Vec<SocketAddr> {
+        self.extra_listening_addrs.lock().clone()
+    }
+
     /// Checks whether the provided address is connected.
     pub fn is_connected(&self, addr: SocketAddr) -> bool {
         self.connections.is_connected(addr)
@@ -305,6 +314,29 @@ impl Tcp {
         let listening_addr = (listener_ip, port).into();
         self.listening_addr.set(listening_addr).expect("The node's listener was started more than once");
 
+        // Initialize the additional TCP listeners, which must bind to their exact addresses.
+        let mut listeners = vec![(listening_addr, listener)];
+        for extra_addr in &self.config().extra_listening_addrs {
+            let listener = self.bind_listener(*extra_addr).await.map_err(|e| {
+                error!(parent: self.span(), "Failed to listen on {extra_addr}: {e}");
+                e
+            })?;
+            let extra_addr = listener.local_addr()?;
+            self.extra_listening_addrs.lock().push(extra_addr);
+            listeners.push((extra_addr, listener));
+        }
+
+        // Spawn a listening task for each listener, all of which feed the same connections.
+        for (addr, listener) in listeners {
+            self.spawn_listening_task(listener).await;
+            debug!(parent: self.span(), "Listening on {addr}");
+        }
+
+        Ok(listening_addr)
+    }
+
+    /// Spawns a task that accepts the incoming connections of the given listener.
+    async fn spawn_listening_task(&self, listener: TcpListener) {
         // Use a channel to know when the listening task is ready.
         let (tx, rx) = oneshot::channel();
 
@@ -323,9 +355,26 @@ impl Tcp {
         });
         self.tasks.lock().push(listening_task);
         let _ = rx.await;
-        debug!(parent: self.span(), "Listening on {listening_addr}");
+    }
 
-        Ok(listening_addr)
+    /// Binds a `TcpListener` to the given address, setting `IPV6_V6ONLY` on an IPv6 socket
+    /// if [`Config::ipv6_only`] is set.
+    async fn bind_listener(&self, addr: SocketAddr) -> io::Result<TcpListener> {
+        let ipv6_only = match self.config().ipv6_only {
+            Some(ipv6_only) if addr.is_ipv6() => ipv6_only,
+            // Otherwise, leave the socket options to the OS defaults.
+            _ => return TcpListener::bind(addr).await,
+        };
+
+        let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(SocketProtocol::TCP))?;
+        socket.set_only_v6(ipv6_only)?;
+        // Note: This matches the behavior of `TcpListener::bind`, which allows rebinding a recently used port.
+        #[cfg(not(windows))]
+        socket.set_reuse_address(true)?;
+        socket.set_nonblocking(true)?;
+        socket.bind(&addr.into())?;
+        socket.listen(1024)?;
+        TcpListener::from_std(socket.into())
     }
 
     /// Creates an instance of `TcpListener` based on the node's configuration.
@@ -335,7 +384,7 @@ impl Tcp {
             // Construct the desired listening IP address.
             let desired_listening_addr = SocketAddr::new(listener_ip, port);
             // If a desired listening port is set, try to bind to it.
-            match TcpListener::bind(desired_listening_addr).await {
+            match self.bind_listener(desired_listening_addr).await {
                 Ok(listener) => listener,
                 Err(e) => {
                     if self.config().allow_random_port {
@@ -344,7 +393,7 @@ impl Tcp {
                             "Trying any listening port, as the desired port is unavailable: {e}"
                         );
                         let random_available_addr = SocketAddr::new(listener_ip, 0);
-                        TcpListener::bind(random_available_addr).await?
+                        self.bind_listener(random_available_addr).await?
                     } else {
                         error!(parent: self.span(), "The desired listening port is unavailable: {e}");
                         return Err(e);
@@ -353,7 +402,7 @@ impl Tcp {
             }
         } else if self.config().allow_random_port {
             let random_available_addr = SocketAddr::new(listener_ip, 0);
-            TcpListener::bind(random_available_addr).await?
+            self.bind_listener(random_available_addr).await?
         } else {
             panic!("As 'listener_ip' is set, either 'desired_listening_port' or 'allow_random_port' must be set");
         };
@@ -387,13 +436,14 @@ impl Tcp {
         // SAFETY: if we're opening connections, this should never fail.
         let listening_addr = self.listening_addr().unwrap();
 
-        match listening_addr.ip().is_loopback() {
+        let is_self = |listening_addr: SocketAddr| match listening_addr.ip().is_loopback() {
             // If localhost, check the ports, this only works on outbound connections, since we
             // don't know the ephemeral port a peer might be using if they initiate the connection.
             true => listening_addr.port() == addr.port(),
             // If it's not localhost, matching IPs indicate a self-connect in both directions.
             false => listening_addr.ip() == addr.ip(),
-        }
+        };
+        is_self(listening_addr) || self.extra_listening_addrs.lock().iter().any(|extra_addr| is_self(*extra_addr))
     }
 
     /// Checks whether the `Tcp` can handle an additional connection.
@@ -521,6 +571,29 @@ mod tests {
         assert_eq!(tcp.num_connecting(), 0);
     }
 
+    #[tokio::test]
+    async fn test_extra_listening_addrs() {
+        let tcp = Tcp::new(Config {
+            listener_ip: Some(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            desired_listening_port: Some(0),
+            extra_listening_addrs: vec![SocketAddr::from((Ipv4Addr::LOCALHOST, 0))],
+            ..Default::default()
+        });
+        let node_ip = tcp.enable_listener().await.unwrap();
+        let extra_ips = tcp.extra_listening_addrs();
+        assert_eq!(extra_ips.len(), 1);
+        assert_ne!(extra_ips[0], node_ip);
+
+        // Ensure self-connecting is not possible through the additional listener either.
+        tcp.connect(extra_ips[0]).await.unwrap_err();
+
+        // Ensure a peer is able to connect through the additional listener.
+        let peer = Tcp::new(Config::default());
+        peer.enable_listener().await.unwrap();
+        peer.connect(extra_ips[0]).await.unwrap();
+        assert!(peer.is_connected(extra_ips[0]));
+    }
+
     #[tokio::test]
     async fn test_connect() {
         let tcp = Tcp::new(Config::default());
@@ -143,7 +143,8 @@ impl Handshake for TestPeer {
                 let signature = self.account().sign_bytes(&peer_request.nonce.to_le_bytes(), rng).unwrap();
 
                 // Send the challenge response.
-                let our_response = ChallengeResponse { genesis_header, signature: Data::Object(signature) };
+                let our_response =
+                    ChallengeResponse { genesis_header, signature: Data::Object(signature), secret_proof: None };
                 framed.send(Message::ChallengeResponse(our_response)).await?;
             }
             ConnectionSide::Responder => {
@@ -154,7 +155,8 @@ impl Handshake for TestPeer {
                 let signature = self.account().sign_bytes(&peer_request.nonce.to_le_bytes(), rng).unwrap();
 
                 // Send our challenge bundle.
-                let our_response = ChallengeResponse { genesis_header, signature: Data::Object(signature) };
+                let our_response =
+                    ChallengeResponse { genesis_header, signature: Data::Object(signature), secret_proof: None };
                 framed.send(Message::ChallengeResponse(our_response)).await?;
                 let our_request = ChallengeRequest::new(local_ip.port(), self.node_type(), self.address(), rng.gen());
                 framed.send(Message::ChallengeRequest(our_request)).await?;
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use snarkos_cli::{commands::CLI, helpers::Updater};
+use snarkos_cli::{
+    commands::CLI,
+    helpers::{expand_config_args, Updater},
+};
 
 use clap::Parser;
 use std::process::exit;
@@ -25,10 +28,18 @@ use tikv_jemallocator::Jemalloc;
 static GLOBAL: Jemalloc = Jemalloc;
 
 fn main() -> anyhow::Result<()> {
+    // Expand the options of the config file, if one is given.
+    let args = match expand_config_args(std::env::args_os()) {
+        Ok(args) => args,
+        Err(error) => {
+            println!("⚠️  {error}\n");
+            exit(1);
+        }
+    };
     // Parse the given arguments.
-    let cli = CLI::parse();
-    // Run the updater.
-    println!("{}", Updater::print_cli());
+    let cli = CLI::parse_from(args);
+    // Run the updater, printing to stderr to keep the command output on stdout.
+    eprintln!("{}", Updater::print_cli());
     // Run the CLI.
     match cli.command.parse() {
         Ok(output) => println!("{output}\n"),